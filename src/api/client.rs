@@ -738,4 +738,118 @@ impl ShortcutApi for ShortcutClient {
 
         Ok(())
     }
+
+    fn get_epic_stories(&self, epic_id: i64) -> Result<Vec<Story>> {
+        let url = format!("{}/epics/{}/stories", self.base_url, epic_id);
+
+        if self.debug {
+            eprintln!("Fetching stories of epic #{epic_id}...");
+        }
+
+        let response = self
+            .client
+            .get(&url)
+            .headers(self.headers())
+            .send()
+            .context("Failed to send epic stories request")?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response
+                .text()
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            anyhow::bail!(
+                "Failed to get stories of epic #{epic_id}: {status}. Error: {error_text}"
+            );
+        }
+
+        response
+            .json()
+            .context("Failed to parse epic stories response")
+    }
+
+    fn get_iterations(&self) -> Result<Vec<Iteration>> {
+        let url = format!("{}/iterations", self.base_url);
+
+        if self.debug {
+            eprintln!("Fetching iterations...");
+        }
+
+        let response = self
+            .client
+            .get(&url)
+            .headers(self.headers())
+            .send()
+            .context("Failed to send iterations request")?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response
+                .text()
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            anyhow::bail!("Failed to get iterations: {status}. Error: {error_text}");
+        }
+
+        response
+            .json()
+            .context("Failed to parse iterations response")
+    }
+
+    fn get_iteration_stories(&self, iteration_id: i64) -> Result<Vec<Story>> {
+        let url = format!("{}/iterations/{}/stories", self.base_url, iteration_id);
+
+        if self.debug {
+            eprintln!("Fetching stories of iteration #{iteration_id}...");
+        }
+
+        let response = self
+            .client
+            .get(&url)
+            .headers(self.headers())
+            .send()
+            .context("Failed to send iteration stories request")?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response
+                .text()
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            anyhow::bail!(
+                "Failed to get stories of iteration #{iteration_id}: {status}. Error: {error_text}"
+            );
+        }
+
+        response
+            .json()
+            .context("Failed to parse iteration stories response")
+    }
+
+    fn get_story_history(&self, story_id: i64) -> Result<Vec<History>> {
+        let url = format!("{}/stories/{}/history", self.base_url, story_id);
+
+        if self.debug {
+            eprintln!("Fetching history of story #{story_id}...");
+        }
+
+        let response = self
+            .client
+            .get(&url)
+            .headers(self.headers())
+            .send()
+            .context("Failed to send story history request")?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response
+                .text()
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            anyhow::bail!(
+                "Failed to get history of story #{story_id}: {status}. Error: {error_text}"
+            );
+        }
+
+        response
+            .json()
+            .context("Failed to parse story history response")
+    }
 }
@@ -15,7 +15,7 @@ pub mod client;
 #[cfg(test)]
 mod tests;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Story {
     pub id: i64,
     pub name: String,
@@ -48,6 +48,14 @@ pub struct Story {
     pub pull_requests: Vec<PullRequest>,
     #[serde(default)]
     pub commits: Vec<Commit>,
+    #[serde(default)]
+    pub blocked: bool,
+    #[serde(default)]
+    pub estimate: Option<i64>,
+    #[serde(default)]
+    pub started_at: Option<String>,
+    #[serde(default)]
+    pub tasks: Vec<Task>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -101,6 +109,17 @@ pub struct Commit {
     pub created_at: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Task {
+    pub id: i64,
+    #[serde(default, deserialize_with = "deserialize_null_string")]
+    pub description: String,
+    #[serde(default)]
+    pub complete: bool,
+    #[serde(default)]
+    pub position: i64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Comment {
     pub id: i64,
@@ -191,6 +210,117 @@ pub struct Epic {
     pub updated_at: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Iteration {
+    pub id: i64,
+    pub name: String,
+    /// `unstarted`, `started` or `done`
+    #[serde(default)]
+    pub status: String,
+    pub start_date: String,
+    pub end_date: String,
+    #[serde(default)]
+    pub app_url: String,
+}
+
+/// One entry of a story's history: the actions a member took at one time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct History {
+    pub id: String,
+    pub changed_at: String,
+    /// Missing for changes made by integrations and automations
+    #[serde(default)]
+    pub member_id: Option<String>,
+    #[serde(default)]
+    pub actions: Vec<HistoryAction>,
+    /// Entities the actions refer to by ID, such as workflow states, epics and labels
+    #[serde(default)]
+    pub references: Vec<HistoryReference>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HistoryActionKind {
+    Create,
+    Update,
+    Delete,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryAction {
+    /// A number for stories, tasks and comments, a UUID for some other entities
+    pub id: serde_json::Value,
+    /// `story`, `story-task`, `story-comment`, `branch`, `pull-request`, ...
+    pub entity_type: String,
+    pub action: HistoryActionKind,
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Task description
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Comment text
+    #[serde(default)]
+    pub text: Option<String>,
+    #[serde(default)]
+    pub changes: HistoryChanges,
+}
+
+/// Fields changed by an update, the common ones typed and the rest kept by name
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HistoryChanges {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workflow_state_id: Option<HistoryChange<i64>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner_ids: Option<HistoryListChange<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<HistoryChange<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<HistoryChange<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub story_type: Option<HistoryChange<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub estimate: Option<HistoryChange<i64>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub epic_id: Option<HistoryChange<i64>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iteration_id: Option<HistoryChange<i64>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label_ids: Option<HistoryListChange<i64>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blocked: Option<HistoryChange<bool>>,
+    /// Task completion
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub complete: Option<HistoryChange<bool>>,
+    #[serde(flatten)]
+    pub other: std::collections::BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryChange<T> {
+    #[serde(default)]
+    pub old: Option<T>,
+    #[serde(default)]
+    pub new: Option<T>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryListChange<T> {
+    #[serde(default)]
+    pub adds: Vec<T>,
+    #[serde(default)]
+    pub removes: Vec<T>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryReference {
+    pub id: serde_json::Value,
+    pub entity_type: String,
+    #[serde(default, deserialize_with = "deserialize_null_string")]
+    pub name: String,
+}
+
 #[derive(Debug, Clone)]
 pub struct SearchStoriesResult {
     pub stories: Vec<Story>,
@@ -233,4 +363,8 @@ pub trait ShortcutApi {
     fn get_epics(&self) -> Result<Vec<Epic>>;
     fn create_epic(&self, name: String, description: String) -> Result<Epic>;
     fn add_comment(&self, story_id: i64, text: &str) -> Result<()>;
+    fn get_epic_stories(&self, epic_id: i64) -> Result<Vec<Story>>;
+    fn get_iterations(&self) -> Result<Vec<Iteration>>;
+    fn get_iteration_stories(&self, iteration_id: i64) -> Result<Vec<Story>>;
+    fn get_story_history(&self, story_id: i64) -> Result<Vec<History>>;
 }
@@ -359,4 +359,30 @@ mod tests {
         assert!(error.to_string().contains("Failed to add comment"));
         assert!(error.to_string().contains("404"));
     }
+
+    #[test]
+    fn test_get_story_history_success() {
+        let mut server = mockito::Server::new();
+        let url = server.url();
+
+        let mock_response = json!([{
+            "id": "a",
+            "changed_at": "2024-01-01T00:00:00Z",
+            "member_id": "user-123",
+            "actions": [{"id": 123, "entity_type": "story", "action": "create", "name": "Story"}]
+        }]);
+
+        let _m = server
+            .mock("GET", "/stories/123/history")
+            .match_header("Shortcut-Token", "test-token")
+            .with_status(200)
+            .with_body(mock_response.to_string())
+            .create();
+
+        let client = create_test_client(&url);
+        let history = client.get_story_history(123).unwrap();
+
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].actions[0].entity_type, "story");
+    }
 }
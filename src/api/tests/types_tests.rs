@@ -131,4 +131,52 @@ mod tests {
         assert_eq!(response.stories.data[1].id, 2);
         assert_eq!(response.stories.data[1].name, "Story 2");
     }
+
+    #[test]
+    fn test_history_deserialization() {
+        let json_data = json!({
+            "id": "595285dc-9c43-4b9c-a1e6-0cd9aff5b084",
+            "changed_at": "2024-01-02T09:00:00Z",
+            "member_id": "alice",
+            "primary_id": 42,
+            "version": "v1",
+            "actions": [
+                {
+                    "id": 42,
+                    "entity_type": "story",
+                    "action": "update",
+                    "name": "Fix login",
+                    "changes": {
+                        "workflow_state_id": {"old": 1, "new": 2},
+                        "owner_ids": {"adds": ["bob"]},
+                        "estimate": {"old": null, "new": 3},
+                        "deadline": {"new": "2024-02-01T00:00:00Z"}
+                    }
+                },
+                {
+                    "id": "3b6f9cd0-0000-0000-0000-000000000000",
+                    "entity_type": "pull-request",
+                    "action": "merge"
+                }
+            ],
+            "references": [
+                {"id": 2, "entity_type": "workflow-state", "name": "In Progress", "type": "started"}
+            ]
+        });
+
+        let history: History = serde_json::from_value(json_data).unwrap();
+
+        assert_eq!(history.member_id.as_deref(), Some("alice"));
+        assert_eq!(history.actions[0].action, HistoryActionKind::Update);
+        let changes = &history.actions[0].changes;
+        let state = changes.workflow_state_id.as_ref().unwrap();
+        assert_eq!((state.old, state.new), (Some(1), Some(2)));
+        assert_eq!(changes.owner_ids.as_ref().unwrap().adds, vec!["bob"]);
+        assert!(changes.owner_ids.as_ref().unwrap().removes.is_empty());
+        assert_eq!(changes.estimate.as_ref().unwrap().old, None);
+        assert!(changes.other.contains_key("deadline"));
+        // Unknown actions still parse
+        assert_eq!(history.actions[1].action, HistoryActionKind::Other);
+        assert_eq!(history.references[0].name, "In Progress");
+    }
 }
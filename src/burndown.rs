@@ -0,0 +1,734 @@
+use crate::api::{Epic, History, HistoryChange, HistoryChanges, Iteration, Story, Workflow};
+use crate::history;
+use anyhow::Result;
+use chrono::{DateTime, Duration, Local, NaiveDate};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::{Color, Style},
+    symbols::Marker,
+    widgets::{Axis, Chart, Dataset, GraphType, Widget},
+};
+use serde::Serialize;
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+/// What the chart counts
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum BurndownUnit {
+    /// Story estimates; stories without one count as zero
+    Points,
+    Stories,
+}
+
+impl BurndownUnit {
+    fn value(self, story: &Story) -> f64 {
+        match self {
+            Self::Points => story.estimate.unwrap_or_default() as f64,
+            Self::Stories => 1.0,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Points => "points",
+            Self::Stories => "stories",
+        }
+    }
+}
+
+/// Totals at the end of a day
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BurndownDay {
+    pub date: NaiveDate,
+    pub scope: f64,
+    pub completed: f64,
+    pub remaining: f64,
+}
+
+/// Scope, completed and remaining work of a set of stories, day by day
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Burndown {
+    pub title: String,
+    pub unit: BurndownUnit,
+    pub days: Vec<BurndownDay>,
+    #[serde(skip)]
+    series: [Vec<(f64, f64)>; 3],
+}
+
+fn local_date(date: &str) -> Option<NaiveDate> {
+    DateTime::parse_from_rfc3339(date)
+        .ok()
+        .map(|date| date.with_timezone(&Local).date_naive())
+}
+
+/// Iteration dates may come as plain dates or as timestamps
+fn parse_day(date: &str) -> Option<NaiveDate> {
+    date.get(..10)?.parse().ok()
+}
+
+/// What a chart is drawn for, which decides when a story joined its scope
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BurndownScope {
+    Epic(i64),
+    Iteration(i64),
+}
+
+/// Whether something held for a story on a given day: from `since` it holds
+/// when `initial` is set, then as each change sets it, oldest first
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Spans {
+    since: Option<NaiveDate>,
+    initial: bool,
+    changes: Vec<(NaiveDate, bool)>,
+}
+
+impl Spans {
+    /// Spans that follow the changes of one field, `holds` telling whether a
+    /// value of it counts
+    fn from_changes<T>(
+        since: Option<NaiveDate>,
+        changes: &[(NaiveDate, &HistoryChange<T>)],
+        holds: impl Fn(Option<&T>) -> bool,
+    ) -> Self {
+        Self {
+            since,
+            initial: changes
+                .first()
+                .is_some_and(|(_, change)| holds(change.old.as_ref())),
+            changes: changes
+                .iter()
+                .map(|(day, change)| (*day, holds(change.new.as_ref())))
+                .collect(),
+        }
+    }
+
+    fn on(&self, day: NaiveDate) -> bool {
+        if self.since.is_none_or(|since| day < since) {
+            return false;
+        }
+        self.changes
+            .iter()
+            .rev()
+            .find(|(changed, _)| *changed <= day)
+            .map_or(self.initial, |(_, holds)| *holds)
+    }
+
+    /// First day it held
+    fn first(&self) -> Option<NaiveDate> {
+        if self.initial {
+            return self.since;
+        }
+        self.changes
+            .iter()
+            .find(|(_, holds)| *holds)
+            .map(|(day, _)| *day)
+    }
+
+    /// Day it last started to hold, when it still does
+    fn current(&self) -> Option<NaiveDate> {
+        match self.changes.last() {
+            Some((_, false)) => None,
+            Some(_) => self
+                .changes
+                .iter()
+                .rev()
+                .take_while(|(_, holds)| *holds)
+                .last()
+                .map(|(day, _)| *day),
+            None if self.initial => self.since,
+            None => None,
+        }
+    }
+}
+
+/// The days a story was part of a chart's scope and done, from its history.
+/// A story that never moved in or out of the epic or iteration counts from
+/// its creation, and one whose state never changed is done from its
+/// completion.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoryDays {
+    scope: Spans,
+    done: Spans,
+}
+
+impl StoryDays {
+    /// `state_types` maps workflow state IDs to their type
+    fn new(
+        story: &Story,
+        history: &[History],
+        scope: BurndownScope,
+        state_types: &HashMap<i64, &str>,
+    ) -> Self {
+        let created = local_date(&story.created_at);
+        let changes = history::story_changes(history);
+        let field = |field: fn(&HistoryChanges) -> Option<&HistoryChange<i64>>| {
+            changes
+                .iter()
+                .filter_map(|(changed_at, changes)| {
+                    Some((
+                        changed_at.with_timezone(&Local).date_naive(),
+                        field(changes)?,
+                    ))
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let (id, moves) = match scope {
+            BurndownScope::Epic(id) => (id, field(|changes| changes.epic_id.as_ref())),
+            BurndownScope::Iteration(id) => (id, field(|changes| changes.iteration_id.as_ref())),
+        };
+        let fallback = Self::without_history(story);
+        let scope = if moves.is_empty() {
+            fallback.scope
+        } else {
+            Spans::from_changes(created, &moves, |value| value == Some(&id))
+        };
+
+        let states = field(|changes| changes.workflow_state_id.as_ref());
+        let done = if states.is_empty() {
+            fallback.done
+        } else {
+            Spans::from_changes(created, &states, |state| {
+                state.is_some_and(|id| state_types.get(id) == Some(&"done"))
+            })
+        };
+        Self { scope, done }
+    }
+
+    /// The days of `story` in `story_days`, or the ones of its creation and
+    /// completion when it's missing
+    fn of<'a>(story: &Story, story_days: &'a HashMap<i64, StoryDays>) -> Cow<'a, Self> {
+        story_days
+            .get(&story.id)
+            .map_or_else(|| Cow::Owned(Self::without_history(story)), Cow::Borrowed)
+    }
+
+    fn without_history(story: &Story) -> Self {
+        let created = local_date(&story.created_at);
+        Self {
+            scope: Spans {
+                since: created,
+                initial: true,
+                changes: Vec::new(),
+            },
+            done: Spans {
+                since: created,
+                initial: false,
+                changes: story
+                    .completed_at
+                    .as_deref()
+                    .and_then(local_date)
+                    .map(|day| (day, true))
+                    .into_iter()
+                    .collect(),
+            },
+        }
+    }
+}
+
+/// The days each story was in scope and done, by story ID. `histories` holds
+/// the history of each story by ID, and `workflows` tell which states are
+/// done.
+pub fn story_days(
+    stories: &[Story],
+    histories: &HashMap<i64, Vec<History>>,
+    workflows: &[Workflow],
+    scope: BurndownScope,
+) -> HashMap<i64, StoryDays> {
+    let state_types: HashMap<i64, &str> = workflows
+        .iter()
+        .flat_map(|workflow| &workflow.states)
+        .map(|state| (state.id, state.state_type.as_str()))
+        .collect();
+    stories
+        .iter()
+        .map(|story| {
+            let history = histories.get(&story.id).map_or(&[][..], Vec::as_slice);
+            (
+                story.id,
+                StoryDays::new(story, history, scope, &state_types),
+            )
+        })
+        .collect()
+}
+
+impl Burndown {
+    /// Stories count towards the scope from the day they joined the epic or
+    /// iteration, or from `start` when they joined before it, so the scope
+    /// line shows the stories added along the way, and they count as
+    /// completed while in a done state. Stories missing from `story_days`
+    /// count from their creation and are done from their completion. Days
+    /// after `end` are not plotted.
+    pub fn new(
+        title: String,
+        stories: &[Story],
+        story_days: &HashMap<i64, StoryDays>,
+        unit: BurndownUnit,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Self {
+        let stories: Vec<(&Story, Cow<StoryDays>)> = stories
+            .iter()
+            .map(|story| (story, StoryDays::of(story, story_days)))
+            .collect();
+        let mut days = Vec::new();
+        let mut day = start;
+        while day <= end.max(start) {
+            let total = |done: bool| -> f64 {
+                stories
+                    .iter()
+                    .filter(|(_, days)| days.scope.on(day) && (!done || days.done.on(day)))
+                    .map(|(story, _)| unit.value(story))
+                    .sum()
+            };
+            let scope = total(false);
+            let completed = total(true);
+            days.push(BurndownDay {
+                date: day,
+                scope,
+                completed,
+                remaining: scope - completed,
+            });
+            day += Duration::days(1);
+        }
+
+        let series = |value: fn(&BurndownDay) -> f64| {
+            days.iter()
+                .enumerate()
+                .map(|(x, day)| (x as f64, value(day)))
+                .collect()
+        };
+        let series = [
+            series(|day| day.scope),
+            series(|day| day.completed),
+            series(|day| day.remaining),
+        ];
+        Self {
+            title,
+            unit,
+            days,
+            series,
+        }
+    }
+
+    /// From the day the first story joined the epic to today, or to the last
+    /// completion once every story is done
+    pub fn for_epic(
+        name: &str,
+        stories: &[Story],
+        story_days: &HashMap<i64, StoryDays>,
+        unit: BurndownUnit,
+        today: NaiveDate,
+    ) -> Self {
+        let days: Vec<Cow<StoryDays>> = stories
+            .iter()
+            .map(|story| StoryDays::of(story, story_days))
+            .collect();
+        let start = days
+            .iter()
+            .filter_map(|days| days.scope.first())
+            .min()
+            .unwrap_or(today);
+        let completed: Option<Vec<NaiveDate>> =
+            days.iter().map(|days| days.done.current()).collect();
+        let end = completed
+            .and_then(|dates| dates.into_iter().max())
+            .unwrap_or(today);
+        Self::new(
+            format!("Epic: {name}"),
+            stories,
+            story_days,
+            unit,
+            start,
+            end,
+        )
+    }
+
+    /// Over the iteration's dates, stopping at today
+    pub fn for_iteration(
+        iteration: &Iteration,
+        stories: &[Story],
+        story_days: &HashMap<i64, StoryDays>,
+        unit: BurndownUnit,
+        today: NaiveDate,
+    ) -> Self {
+        let start = parse_day(&iteration.start_date).unwrap_or(today);
+        let end = parse_day(&iteration.end_date).map_or(today, |end| end.min(today));
+        Self::new(
+            format!("Iteration: {}", iteration.name),
+            stories,
+            story_days,
+            unit,
+            start,
+            end,
+        )
+    }
+
+    /// Scope added after the first day
+    pub fn scope_change(&self) -> f64 {
+        match (self.days.first(), self.days.last()) {
+            (Some(first), Some(last)) => last.scope - first.scope,
+            _ => 0.0,
+        }
+    }
+
+    /// One line summary of the last day
+    pub fn summary(&self) -> String {
+        let Some(last) = self.days.last() else {
+            return String::new();
+        };
+        format!(
+            "{} of {} {} completed, {} remaining, scope {:+} since {}",
+            last.completed,
+            last.scope,
+            self.unit.label(),
+            last.remaining,
+            self.scope_change(),
+            self.days[0].date
+        )
+    }
+
+    /// Scope, completed and remaining lines, for the TUI and the terminal
+    pub fn chart(&self) -> Chart<'_> {
+        let [scope, completed, remaining] = &self.series;
+        let line = |name: &'static str, color: Color, data| {
+            Dataset::default()
+                .name(name)
+                .marker(Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(color))
+                .data(data)
+        };
+        let max_x = self.days.len().saturating_sub(1).max(1) as f64;
+        let max_y = self.days.iter().map(|day| day.scope).fold(1.0, f64::max);
+        let dates = match (self.days.first(), self.days.last()) {
+            (Some(first), Some(last)) => {
+                vec![
+                    first.date.format("%b %d").to_string(),
+                    last.date.format("%b %d").to_string(),
+                ]
+            }
+            _ => Vec::new(),
+        };
+
+        Chart::new(vec![
+            line("Scope", Color::Yellow, scope),
+            line("Completed", Color::Green, completed),
+            line("Remaining", Color::Red, remaining),
+        ])
+        .hidden_legend_constraints((Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)))
+        .x_axis(
+            Axis::default()
+                .style(Style::default().fg(Color::Gray))
+                .bounds([0.0, max_x])
+                .labels(dates),
+        )
+        .y_axis(
+            Axis::default()
+                .title(self.unit.label())
+                .style(Style::default().fg(Color::Gray))
+                .bounds([0.0, max_y])
+                .labels([
+                    "0".to_string(),
+                    format!("{}", max_y / 2.0),
+                    max_y.to_string(),
+                ]),
+        )
+    }
+
+    /// The chart drawn as plain text, `width` by `height` characters
+    pub fn render_text(&self, width: u16, height: u16) -> String {
+        let area = Rect::new(0, 0, width, height);
+        let mut buffer = Buffer::empty(area);
+        self.chart().render(area, &mut buffer);
+        (0..height)
+            .map(|y| {
+                let line: String = (0..width).map(|x| buffer[(x, y)].symbol()).collect();
+                format!("{}\n", line.trim_end())
+            })
+            .collect()
+    }
+}
+
+/// Find an epic by ID or by name, ignoring case
+pub fn find_epic<'a>(epics: &'a [Epic], epic: &str) -> Result<&'a Epic> {
+    let found = match epic.trim_start_matches('#').parse::<i64>() {
+        Ok(id) => epics.iter().find(|e| e.id == id),
+        Err(_) => epics.iter().find(|e| e.name.eq_ignore_ascii_case(epic)),
+    };
+    found.ok_or_else(|| anyhow::anyhow!("No epic matches '{epic}'"))
+}
+
+/// Find an iteration by ID, or the one in progress for `current`
+pub fn find_iteration<'a>(iterations: &'a [Iteration], iteration: &str) -> Result<&'a Iteration> {
+    if !iteration.eq_ignore_ascii_case("current") {
+        let id: i64 = iteration.trim_start_matches('#').parse().map_err(|_| {
+            anyhow::anyhow!("Invalid iteration '{iteration}', expected an ID or 'current'")
+        })?;
+        return iterations
+            .iter()
+            .find(|i| i.id == id)
+            .ok_or_else(|| anyhow::anyhow!("No iteration with ID {id}"));
+    }
+
+    let started: Vec<&Iteration> = iterations
+        .iter()
+        .filter(|i| i.status == "started")
+        .collect();
+    match started.as_slice() {
+        [] => anyhow::bail!("No iteration is in progress"),
+        [current] => Ok(current),
+        several => anyhow::bail!(
+            "Several iterations are in progress: {}. Pass one of their IDs",
+            several
+                .iter()
+                .map(|i| format!("{} (#{})", i.name, i.id))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::WorkflowState;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    fn date(date: &str) -> NaiveDate {
+        date.parse().unwrap()
+    }
+
+    // Noon UTC stays on the same day in every timezone the tests run in
+    fn create_test_story(id: i64, created: &str, completed: Option<&str>) -> Story {
+        Story {
+            id,
+            name: format!("Story {id}"),
+            workflow_state_id: 1,
+            story_type: "feature".to_string(),
+            position: 1000,
+            created_at: format!("{created}T12:00:00Z"),
+            updated_at: format!("{created}T12:00:00Z"),
+            completed_at: completed.map(|day| format!("{day}T12:00:00Z")),
+            epic_id: Some(1),
+            estimate: Some(id),
+            ..Default::default()
+        }
+    }
+
+    fn create_test_iteration(id: i64, status: &str) -> Iteration {
+        Iteration {
+            id,
+            name: format!("Sprint {id}"),
+            status: status.to_string(),
+            start_date: "2024-01-01".to_string(),
+            end_date: "2024-01-14".to_string(),
+            app_url: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_burndown_days() {
+        let stories = vec![
+            create_test_story(1, "2023-12-20", Some("2024-01-02")),
+            create_test_story(2, "2023-12-20", None),
+            // Added on the third day
+            create_test_story(4, "2024-01-03", Some("2024-01-03")),
+        ];
+        let burndown = Burndown::for_iteration(
+            &create_test_iteration(1, "started"),
+            &stories,
+            &HashMap::new(),
+            BurndownUnit::Points,
+            date("2024-01-03"),
+        );
+
+        let days: Vec<(f64, f64, f64)> = burndown
+            .days
+            .iter()
+            .map(|day| (day.scope, day.completed, day.remaining))
+            .collect();
+        assert_eq!(
+            days,
+            vec![(3.0, 0.0, 3.0), (3.0, 1.0, 2.0), (7.0, 5.0, 2.0)]
+        );
+        assert_eq!(burndown.scope_change(), 4.0);
+        assert_eq!(
+            burndown.summary(),
+            "5 of 7 points completed, 2 remaining, scope +4 since 2024-01-01"
+        );
+
+        let stories = Burndown::new(
+            "Stories".to_string(),
+            &stories,
+            &HashMap::new(),
+            BurndownUnit::Stories,
+            date("2024-01-03"),
+            date("2024-01-03"),
+        );
+        assert_eq!(stories.days[0].remaining, 1.0);
+    }
+
+    #[test]
+    fn test_burndown_from_history() {
+        let update = |day: &str, changes: serde_json::Value| {
+            json!({
+                "id": day,
+                "changed_at": format!("{day}T12:00:00Z"),
+                "actions": [{
+                    "id": 1,
+                    "entity_type": "story",
+                    "action": "update",
+                    "changes": changes
+                }]
+            })
+        };
+        // Created long before it was pulled into the iteration on the second
+        // day, done on the third and reopened on the fourth
+        let moved: Vec<History> = serde_json::from_value(json!([
+            update("2024-01-02", json!({"iteration_id": {"new": 1}})),
+            update(
+                "2024-01-03",
+                json!({"workflow_state_id": {"old": 1, "new": 2}})
+            ),
+            update(
+                "2024-01-04",
+                json!({"workflow_state_id": {"old": 2, "new": 1}})
+            ),
+        ]))
+        .unwrap();
+        let state = |id, state_type: &str| WorkflowState {
+            id,
+            name: state_type.to_string(),
+            color: String::new(),
+            position: id,
+            state_type: state_type.to_string(),
+        };
+        let workflows = [Workflow {
+            id: 1,
+            name: "Engineering".to_string(),
+            states: vec![state(1, "started"), state(2, "done")],
+        }];
+        let stories = vec![
+            create_test_story(1, "2023-12-01", None),
+            create_test_story(2, "2023-12-20", None),
+        ];
+        let days = story_days(
+            &stories,
+            &HashMap::from([(1, moved)]),
+            &workflows,
+            BurndownScope::Iteration(1),
+        );
+
+        let burndown = Burndown::for_iteration(
+            &create_test_iteration(1, "started"),
+            &stories,
+            &days,
+            BurndownUnit::Points,
+            date("2024-01-04"),
+        );
+
+        let days: Vec<(f64, f64, f64)> = burndown
+            .days
+            .iter()
+            .map(|day| (day.scope, day.completed, day.remaining))
+            .collect();
+        assert_eq!(
+            days,
+            vec![
+                (2.0, 0.0, 2.0),
+                (3.0, 0.0, 3.0),
+                (3.0, 1.0, 2.0),
+                (3.0, 0.0, 3.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_epic_burndown_range() {
+        let done = vec![
+            create_test_story(1, "2024-01-02", Some("2024-01-05")),
+            create_test_story(2, "2024-01-03", Some("2024-01-04")),
+        ];
+        let burndown = Burndown::for_epic(
+            "Checkout",
+            &done,
+            &HashMap::new(),
+            BurndownUnit::Stories,
+            date("2024-02-01"),
+        );
+        assert_eq!(burndown.title, "Epic: Checkout");
+        assert_eq!(burndown.days.first().unwrap().date, date("2024-01-02"));
+        assert_eq!(burndown.days.last().unwrap().date, date("2024-01-05"));
+
+        let mut open = done;
+        open[0].completed_at = None;
+        let burndown = Burndown::for_epic(
+            "Checkout",
+            &open,
+            &HashMap::new(),
+            BurndownUnit::Stories,
+            date("2024-01-10"),
+        );
+        assert_eq!(burndown.days.last().unwrap().date, date("2024-01-10"));
+    }
+
+    #[test]
+    fn test_render_text() {
+        let stories = vec![create_test_story(2, "2024-01-01", Some("2024-01-03"))];
+        let burndown = Burndown::new(
+            "Test".to_string(),
+            &stories,
+            &HashMap::new(),
+            BurndownUnit::Points,
+            date("2024-01-01"),
+            date("2024-01-04"),
+        );
+        let text = burndown.render_text(60, 12);
+        assert_eq!(text.lines().count(), 12);
+        assert!(text.contains("Scope"));
+        assert!(text.contains("Remaining"));
+        assert!(text.contains("Jan 01"));
+        assert!(text.contains("Jan 04"));
+    }
+
+    #[test]
+    fn test_find_epic_and_iteration() {
+        let epics = vec![Epic {
+            id: 7,
+            name: "Checkout".to_string(),
+            description: String::new(),
+            app_url: String::new(),
+            state: String::new(),
+            owner_ids: vec![],
+            created_at: String::new(),
+            updated_at: String::new(),
+        }];
+        assert_eq!(find_epic(&epics, "7").unwrap().id, 7);
+        assert_eq!(find_epic(&epics, "checkout").unwrap().id, 7);
+        assert_eq!(
+            find_epic(&epics, "Search").unwrap_err().to_string(),
+            "No epic matches 'Search'"
+        );
+
+        let iterations = vec![
+            create_test_iteration(1, "done"),
+            create_test_iteration(2, "started"),
+        ];
+        assert_eq!(find_iteration(&iterations, "current").unwrap().id, 2);
+        assert_eq!(find_iteration(&iterations, "1").unwrap().id, 1);
+        assert!(find_iteration(&iterations, "next").is_err());
+
+        let several = vec![
+            create_test_iteration(2, "started"),
+            create_test_iteration(3, "started"),
+        ];
+        assert_eq!(
+            find_iteration(&several, "current").unwrap_err().to_string(),
+            "Several iterations are in progress: Sprint 2 (#2), Sprint 3 (#3). Pass one of their IDs"
+        );
+    }
+}
@@ -0,0 +1,468 @@
+use crate::api::{Epic, ShortcutApi, Story};
+use crate::git::LogEntry;
+use crate::story_ref::StoryRef;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+
+/// Story requests sent at the same time when fetching the stories of a range
+const CONCURRENT_REQUESTS: usize = 8;
+
+/// Story types in the order their sections appear; other types follow alphabetically
+const TYPE_ORDER: [&str; 3] = ["feature", "bug", "chore"];
+
+/// Story IDs mentioned in a commit message, e.g. `[sc-42]` or a merged branch
+/// name like `edo/sc-42/fix-login`, in order of appearance
+pub fn story_refs(text: &str) -> Vec<i64> {
+    let mut ids = Vec::new();
+    for id in text
+        .split_whitespace()
+        .filter_map(StoryRef::from_name)
+        .map(StoryRef::id)
+    {
+        if !ids.contains(&id) {
+            ids.push(id);
+        }
+    }
+    ids
+}
+
+/// Commits of a range grouped by the stories they reference
+#[derive(Debug, Default, PartialEq)]
+pub struct ScannedCommits {
+    pub by_story: BTreeMap<i64, Vec<LogEntry>>,
+    pub unreferenced: Vec<LogEntry>,
+}
+
+/// Attribute each commit to the stories its message mentions. Commits without
+/// a reference inherit the stories of the merge that brought them in (listed
+/// by `merged`), which covers branches named after a story. Merge commits
+/// without a reference are left out.
+pub fn scan_commits(
+    entries: &[LogEntry],
+    merged: impl Fn(&LogEntry) -> Vec<String>,
+) -> ScannedCommits {
+    let message_refs = |entry: &LogEntry| story_refs(&format!("{}\n{}", entry.subject, entry.body));
+
+    let mut inherited: HashMap<String, Vec<i64>> = HashMap::new();
+    for merge in entries.iter().filter(|entry| entry.is_merge()) {
+        let refs = message_refs(merge);
+        if refs.is_empty() {
+            continue;
+        }
+        for hash in merged(merge) {
+            let ids = inherited.entry(hash).or_default();
+            for id in &refs {
+                if !ids.contains(id) {
+                    ids.push(*id);
+                }
+            }
+        }
+    }
+
+    let mut scanned = ScannedCommits::default();
+    for entry in entries {
+        let mut refs = message_refs(entry);
+        if refs.is_empty() {
+            refs = inherited.get(&entry.hash).cloned().unwrap_or_default();
+        }
+        if refs.is_empty() {
+            if !entry.is_merge() {
+                scanned.unreferenced.push(entry.clone());
+            }
+            continue;
+        }
+        for id in refs {
+            scanned.by_story.entry(id).or_default().push(entry.clone());
+        }
+    }
+    scanned
+}
+
+/// Fetch stories a few at a time in parallel. Returns the stories found and
+/// the IDs that could not be fetched.
+pub fn fetch_stories<C: ShortcutApi + Sync>(
+    client: &C,
+    ids: &[i64],
+) -> (HashMap<i64, Story>, Vec<i64>) {
+    let mut stories = HashMap::new();
+    let mut missing = Vec::new();
+
+    for chunk in ids.chunks(CONCURRENT_REQUESTS) {
+        let results: Vec<_> = std::thread::scope(|scope| {
+            let handles: Vec<_> = chunk
+                .iter()
+                .map(|&id| scope.spawn(move || (id, client.get_story(id))))
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().expect("story request thread panicked"))
+                .collect()
+        });
+
+        for (id, result) in results {
+            match result {
+                Ok(story) => {
+                    stories.insert(id, story);
+                }
+                Err(_) => missing.push(id),
+            }
+        }
+    }
+
+    (stories, missing)
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ChangelogCommit {
+    pub hash: String,
+    pub subject: String,
+}
+
+impl From<&LogEntry> for ChangelogCommit {
+    fn from(entry: &LogEntry) -> Self {
+        Self {
+            hash: entry.short_hash().to_string(),
+            subject: entry.subject.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ChangelogStory {
+    pub id: i64,
+    pub name: String,
+    pub app_url: String,
+    pub commits: Vec<ChangelogCommit>,
+}
+
+/// Stories of one epic, or of no epic when `epic_id` is `None`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EpicGroup {
+    pub epic_id: Option<i64>,
+    pub epic_name: Option<String>,
+    pub stories: Vec<ChangelogStory>,
+}
+
+/// Epic groups keyed so named epics sort by name and stories without an epic come last
+type EpicGroups<'a> = BTreeMap<(bool, &'a str, i64), EpicGroup>;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TypeSection {
+    pub story_type: String,
+    pub epics: Vec<EpicGroup>,
+}
+
+/// A referenced story that could not be fetched
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UnresolvedStory {
+    pub id: i64,
+    pub commits: Vec<ChangelogCommit>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Changelog {
+    pub range: String,
+    pub sections: Vec<TypeSection>,
+    pub unresolved: Vec<UnresolvedStory>,
+    pub unreferenced: Vec<ChangelogCommit>,
+}
+
+impl Changelog {
+    /// Group the scanned stories by story type, then by epic. Named epics are
+    /// sorted by name and stories without an epic come last.
+    pub fn new(
+        range: &str,
+        scanned: &ScannedCommits,
+        stories: &HashMap<i64, Story>,
+        epics: &[Epic],
+    ) -> Self {
+        let epic_names: HashMap<i64, &str> =
+            epics.iter().map(|e| (e.id, e.name.as_str())).collect();
+        let mut by_type: BTreeMap<(usize, &str), EpicGroups> = BTreeMap::new();
+        let mut unresolved = Vec::new();
+
+        for (id, entries) in &scanned.by_story {
+            let commits = entries.iter().map(ChangelogCommit::from).collect();
+            let Some(story) = stories.get(id) else {
+                unresolved.push(UnresolvedStory { id: *id, commits });
+                continue;
+            };
+
+            let type_rank = TYPE_ORDER
+                .iter()
+                .position(|t| *t == story.story_type)
+                .unwrap_or(TYPE_ORDER.len());
+            let epic_name = story.epic_id.and_then(|id| epic_names.get(&id).copied());
+            let epic_key = (
+                epic_name.is_none(),
+                epic_name.unwrap_or_default(),
+                story.epic_id.unwrap_or_default(),
+            );
+
+            by_type
+                .entry((type_rank, story.story_type.as_str()))
+                .or_default()
+                .entry(epic_key)
+                .or_insert_with(|| EpicGroup {
+                    epic_id: story.epic_id,
+                    epic_name: epic_name.map(str::to_string),
+                    stories: Vec::new(),
+                })
+                .stories
+                .push(ChangelogStory {
+                    id: story.id,
+                    name: story.name.clone(),
+                    app_url: story.app_url.clone(),
+                    commits,
+                });
+        }
+
+        Self {
+            range: range.to_string(),
+            sections: by_type
+                .into_iter()
+                .map(|((_, story_type), groups)| TypeSection {
+                    story_type: story_type.to_string(),
+                    epics: groups.into_values().collect(),
+                })
+                .collect(),
+            unresolved,
+            unreferenced: scanned
+                .unreferenced
+                .iter()
+                .map(ChangelogCommit::from)
+                .collect(),
+        }
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!("# Changelog ({})\n", self.range);
+
+        if self.sections.is_empty() && self.unresolved.is_empty() && self.unreferenced.is_empty() {
+            out.push_str("\nNo commits in this range.\n");
+            return out;
+        }
+
+        for section in &self.sections {
+            let _ = write!(out, "\n## {}\n", section_title(&section.story_type));
+            let single_group = section.epics.len() == 1 && section.epics[0].epic_id.is_none();
+            for group in &section.epics {
+                if !single_group {
+                    let heading = group.epic_name.as_deref().unwrap_or(match group.epic_id {
+                        Some(_) => "Unknown epic",
+                        None => "No epic",
+                    });
+                    let _ = write!(out, "\n### {heading}\n");
+                }
+                out.push('\n');
+                for story in &group.stories {
+                    let _ = writeln!(
+                        out,
+                        "- {} ([sc-{}]({}))",
+                        story.name, story.id, story.app_url
+                    );
+                }
+            }
+        }
+
+        if !self.unresolved.is_empty() {
+            out.push_str("\n## Unresolved Story References\n\n");
+            for story in &self.unresolved {
+                for commit in &story.commits {
+                    let _ = writeln!(
+                        out,
+                        "- sc-{}: `{}` {}",
+                        story.id, commit.hash, commit.subject
+                    );
+                }
+            }
+        }
+
+        if !self.unreferenced.is_empty() {
+            out.push_str("\n## Unreferenced Commits\n\n");
+            for commit in &self.unreferenced {
+                let _ = writeln!(out, "- `{}` {}", commit.hash, commit.subject);
+            }
+        }
+
+        out
+    }
+}
+
+fn section_title(story_type: &str) -> String {
+    match story_type {
+        "feature" => "Features".to_string(),
+        "bug" => "Bug Fixes".to_string(),
+        "chore" => "Chores".to_string(),
+        "" => "Other".to_string(),
+        other => {
+            let mut chars = other.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect())
+                .unwrap_or_default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn entry(hash: &str, parents: &[&str], subject: &str) -> LogEntry {
+        LogEntry {
+            hash: hash.to_string(),
+            parents: parents.iter().map(|p| p.to_string()).collect(),
+            subject: subject.to_string(),
+            body: String::new(),
+        }
+    }
+
+    fn create_test_story(id: i64, story_type: &str, epic_id: Option<i64>) -> Story {
+        Story {
+            id,
+            name: format!("Story {id}"),
+            workflow_state_id: 1,
+            app_url: format!("https://app.shortcut.com/org/story/{id}"),
+            story_type: story_type.to_string(),
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+            updated_at: "2024-01-01T00:00:00Z".to_string(),
+            epic_id,
+            ..Default::default()
+        }
+    }
+
+    fn create_test_epic(id: i64, name: &str) -> Epic {
+        Epic {
+            id,
+            name: name.to_string(),
+            description: String::new(),
+            app_url: String::new(),
+            state: String::new(),
+            owner_ids: vec![],
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+            updated_at: "2024-01-01T00:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_story_refs() {
+        assert_eq!(story_refs("Fix login [sc-42]"), vec![42]);
+        assert_eq!(
+            story_refs("Merge pull request #9 from org/sc-7-x\n\nAlso sc-8, SC-7"),
+            vec![7, 8]
+        );
+        assert_eq!(story_refs("Bump deps to misc-42"), Vec::<i64>::new());
+    }
+
+    #[test]
+    fn test_scan_commits_inherits_refs_from_merges() {
+        let entries = vec![
+            entry("m1", &["a0", "b2"], "Merge branch 'sc-7-login'"),
+            entry("b2", &["b1"], "Polish"),
+            entry("b1", &["a0"], "Start login [sc-8]"),
+            entry("m0", &["z0", "z1"], "Merge branch 'main'"),
+            entry("a0", &["z0"], "Bump deps"),
+        ];
+        let scanned = scan_commits(&entries, |merge| match merge.hash.as_str() {
+            "m1" => vec!["b2".to_string(), "b1".to_string()],
+            _ => vec!["a0".to_string()],
+        });
+
+        let hashes = |id| -> Vec<&str> {
+            scanned.by_story[&id]
+                .iter()
+                .map(|e| e.hash.as_str())
+                .collect()
+        };
+        assert_eq!(hashes(7), vec!["m1", "b2"]);
+        assert_eq!(hashes(8), vec!["b1"]);
+        assert_eq!(scanned.unreferenced, vec![entries[4].clone()]);
+    }
+
+    #[test]
+    fn test_changelog_groups_by_type_and_epic() {
+        let scanned = ScannedCommits {
+            by_story: BTreeMap::from([
+                (1, vec![entry("aaaa", &[], "One [sc-1]")]),
+                (2, vec![entry("bbbb", &[], "Two [sc-2]")]),
+                (3, vec![entry("cccc", &[], "Three [sc-3]")]),
+                (4, vec![entry("dddd", &[], "Four [sc-4]")]),
+                (99, vec![entry("eeee", &[], "Gone [sc-99]")]),
+            ]),
+            unreferenced: vec![entry("ffff", &[], "Bump deps")],
+        };
+        let stories = HashMap::from([
+            (1, create_test_story(1, "chore", None)),
+            (2, create_test_story(2, "feature", None)),
+            (3, create_test_story(3, "feature", Some(10))),
+            (4, create_test_story(4, "bug", None)),
+        ]);
+        let epics = vec![create_test_epic(10, "Login revamp")];
+
+        let changelog = Changelog::new("v1.0.0..HEAD", &scanned, &stories, &epics);
+
+        let types: Vec<&str> = changelog
+            .sections
+            .iter()
+            .map(|s| s.story_type.as_str())
+            .collect();
+        assert_eq!(types, vec!["feature", "bug", "chore"]);
+        assert_eq!(changelog.unresolved[0].id, 99);
+
+        assert_eq!(
+            changelog.to_markdown(),
+            "# Changelog (v1.0.0..HEAD)\n\
+             \n## Features\n\
+             \n### Login revamp\n\
+             \n- Story 3 ([sc-3](https://app.shortcut.com/org/story/3))\n\
+             \n### No epic\n\
+             \n- Story 2 ([sc-2](https://app.shortcut.com/org/story/2))\n\
+             \n## Bug Fixes\n\
+             \n- Story 4 ([sc-4](https://app.shortcut.com/org/story/4))\n\
+             \n## Chores\n\
+             \n- Story 1 ([sc-1](https://app.shortcut.com/org/story/1))\n\
+             \n## Unresolved Story References\n\
+             \n- sc-99: `eeee` Gone [sc-99]\n\
+             \n## Unreferenced Commits\n\
+             \n- `ffff` Bump deps\n"
+        );
+    }
+
+    #[test]
+    fn test_changelog_json_lists_commits() {
+        let scanned = ScannedCommits {
+            by_story: BTreeMap::from([(2, vec![entry("bbbb1234567", &[], "Two [sc-2]")])]),
+            unreferenced: vec![],
+        };
+        let stories = HashMap::from([(2, create_test_story(2, "feature", None))]);
+        let changelog = Changelog::new("v1..v2", &scanned, &stories, &[]);
+
+        let json = serde_json::to_value(&changelog).unwrap();
+        assert_eq!(json["sections"][0]["story_type"], "feature");
+        assert_eq!(json["sections"][0]["epics"][0]["stories"][0]["id"], 2);
+        assert_eq!(
+            json["sections"][0]["epics"][0]["stories"][0]["commits"][0]["hash"],
+            "bbbb123"
+        );
+    }
+
+    #[test]
+    fn test_empty_changelog() {
+        let changelog = Changelog::new("v1..v1", &ScannedCommits::default(), &HashMap::new(), &[]);
+        assert!(
+            changelog
+                .to_markdown()
+                .contains("No commits in this range.")
+        );
+    }
+
+    #[test]
+    fn test_section_titles() {
+        assert_eq!(section_title("bug"), "Bug Fixes");
+        assert_eq!(section_title("spike"), "Spike");
+        assert_eq!(section_title(""), "Other");
+    }
+}
@@ -1,7 +1,8 @@
 use anyhow::{Context, Result};
+use chrono::{DateTime, Datelike, Duration, FixedOffset, Local, NaiveDate, TimeZone, Utc, Weekday};
 use dialoguer::{Confirm, Input, Select};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::PathBuf;
 
@@ -10,6 +11,9 @@ pub struct Config {
     pub workspaces: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub default_workspace: Option<String>,
+    /// Pager command for `show` (overrides $PAGER)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pager: Option<String>,
     #[serde(flatten)]
     pub workspace_configs: HashMap<String, WorkspaceConfig>,
 }
@@ -20,12 +24,242 @@ pub struct WorkspaceConfig {
     pub user_id: String,
     #[serde(default = "default_fetch_limit")]
     pub fetch_limit: usize,
+    /// Named output templates for `show --template <name>`
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub templates: HashMap<String, String>,
+    /// Steps performed by `sc-cli start`
+    #[serde(default, skip_serializing_if = "StartConfig::is_default")]
+    pub start: StartConfig,
+    /// Local git settings used by `finish` and the branch commands
+    #[serde(default, skip_serializing_if = "GitConfig::is_default")]
+    pub git: GitConfig,
+    /// How weeks are counted by the TUI, `timesheet` and `report`
+    #[serde(default, skip_serializing_if = "WeekConfig::is_default")]
+    pub week: WeekConfig,
+}
+
+/// Steps performed by `sc-cli start`, each can be overridden on the command line
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StartConfig {
+    /// Add the current member to the story owners
+    pub assign: bool,
+    /// Move the story to the first "started" state of its workflow
+    #[serde(rename = "move")]
+    pub move_story: bool,
+    /// Create or check out the story branch
+    pub branch: bool,
+    /// Use a worktree instead of a branch checkout
+    pub worktree: bool,
+    /// Comment to post on the story, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+impl Default for StartConfig {
+    fn default() -> Self {
+        Self {
+            assign: true,
+            move_story: true,
+            branch: true,
+            worktree: false,
+            comment: None,
+        }
+    }
+}
+
+impl StartConfig {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Local git settings for a workspace
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GitConfig {
+    /// Branch new story branches start from and finished work is merged into.
+    /// New branches start from the current HEAD and `finish` uses the remote's
+    /// default branch when not set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_branch: Option<String>,
+    /// Remote `branch --push` publishes new branches to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote: Option<String>,
+    /// Template for new branch names, e.g. `{user}/sc-{id}/{type}/{slug:40}`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub branch_template: Option<String>,
+    /// Template for new worktree paths, e.g. `~/wt/{repo}/{id}`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub worktree_template: Option<String>,
+    /// Branch names are cut to this many characters
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_branch_length: Option<usize>,
+    /// How the commit-msg hook adds the story ID, e.g. `{subject} [sc-{id}]`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit_tag_format: Option<String>,
+    /// Local repositories by name, for stories spanning several of them
+    /// (`branch --repos api,web`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub repos: BTreeMap<String, String>,
+}
+
+impl GitConfig {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Paths of the named repositories, in the given order. A leading `~/`
+    /// is expanded to the home directory.
+    pub fn repo_paths(&self, names: &[String]) -> Result<Vec<(String, PathBuf)>> {
+        names
+            .iter()
+            .map(|name| {
+                let Some(path) = self.repos.get(name) else {
+                    let known: Vec<&str> = self.repos.keys().map(String::as_str).collect();
+                    anyhow::bail!(
+                        "Unknown repository '{name}'. Configured repositories: {}",
+                        if known.is_empty() {
+                            "none".to_string()
+                        } else {
+                            known.join(", ")
+                        }
+                    );
+                };
+                Ok((name.clone(), expand_home(path)))
+            })
+            .collect()
+    }
+
+    /// Paths of all configured repositories, ordered by name
+    pub fn all_repo_paths(&self) -> Vec<(String, PathBuf)> {
+        self.repos
+            .iter()
+            .map(|(name, path)| (name.clone(), expand_home(path)))
+            .collect()
+    }
+}
+
+/// First day and timezone of a week
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WeekConfig {
+    /// Day weeks start on, e.g. `monday` or `sunday`
+    pub start: Weekday,
+    /// `local`, `utc` or a fixed offset such as `+02:00`
+    pub timezone: WeekTimezone,
+}
+
+impl Default for WeekConfig {
+    fn default() -> Self {
+        Self {
+            start: Weekday::Mon,
+            timezone: WeekTimezone::Utc,
+        }
+    }
+}
+
+impl WeekConfig {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Start of the week containing `now`, at midnight in the week's timezone
+    pub fn start_of_week(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        match self.timezone {
+            WeekTimezone::Local => week_start(&now.with_timezone(&Local), self.start),
+            WeekTimezone::Utc => week_start(&now, self.start),
+            WeekTimezone::Offset(offset) => week_start(&now.with_timezone(&offset), self.start),
+        }
+    }
+
+    /// Calendar date of `at` in the week's timezone
+    pub fn date(&self, at: DateTime<Utc>) -> NaiveDate {
+        match self.timezone {
+            WeekTimezone::Local => at.with_timezone(&Local).date_naive(),
+            WeekTimezone::Utc => at.date_naive(),
+            WeekTimezone::Offset(offset) => at.with_timezone(&offset).date_naive(),
+        }
+    }
+
+    /// Whether `date` falls in the week containing `now`
+    pub fn contains(&self, date: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+        let start = self.start_of_week(now);
+        date >= start && date < start + Duration::days(7)
+    }
+}
+
+fn week_start<Tz: TimeZone>(now: &DateTime<Tz>, first_day: Weekday) -> DateTime<Utc> {
+    let days = now.weekday().days_since(first_day);
+    let midnight = (now.date_naive() - Duration::days(days.into()))
+        .and_hms_opt(0, 0, 0)
+        .unwrap_or_default();
+    now.timezone()
+        .from_local_datetime(&midnight)
+        .earliest()
+        .map_or_else(|| midnight.and_utc(), |start| start.with_timezone(&Utc))
+}
+
+/// Timezone used to decide where a week starts
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum WeekTimezone {
+    Local,
+    Utc,
+    Offset(FixedOffset),
+}
+
+impl TryFrom<String> for WeekTimezone {
+    type Error = String;
+
+    fn try_from(value: String) -> std::result::Result<Self, Self::Error> {
+        match value.to_lowercase().as_str() {
+            "local" => Ok(Self::Local),
+            "utc" => Ok(Self::Utc),
+            _ => value.parse().map(Self::Offset).map_err(|_| {
+                format!(
+                    "Invalid week timezone '{value}', expected local, utc or an offset like +02:00"
+                )
+            }),
+        }
+    }
+}
+
+impl From<WeekTimezone> for String {
+    fn from(timezone: WeekTimezone) -> Self {
+        match timezone {
+            WeekTimezone::Local => "local".to_string(),
+            WeekTimezone::Utc => "utc".to_string(),
+            WeekTimezone::Offset(offset) => offset.to_string(),
+        }
+    }
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
 }
 
 fn default_fetch_limit() -> usize {
     50
 }
 
+impl Default for WorkspaceConfig {
+    fn default() -> Self {
+        Self {
+            api_key: String::new(),
+            user_id: String::new(),
+            fetch_limit: default_fetch_limit(),
+            templates: HashMap::new(),
+            start: StartConfig::default(),
+            git: GitConfig::default(),
+            week: WeekConfig::default(),
+        }
+    }
+}
+
 impl Config {
     pub fn load() -> Result<Self> {
         let config_path = Self::find_config_path()?;
@@ -105,6 +339,7 @@ impl Config {
         let config = Config {
             workspaces: vec![workspace_name.to_string()],
             default_workspace: Some(workspace_name.to_string()),
+            pager: None,
             workspace_configs,
         };
 
@@ -132,6 +367,7 @@ impl Config {
             api_key,
             user_id,
             fetch_limit,
+            ..Default::default()
         })
     }
 
@@ -256,6 +492,9 @@ workspaces = ["personal", "work", "client"]
 # Optional: specify default workspace (if not set, single workspace will be used as default)
 default_workspace = "personal"
 
+# Optional: pager used by `sc-cli show` on a terminal (defaults to $PAGER, then "less -R")
+# pager = "less -R"
+
 # Configuration for 'personal' workspace
 [personal]
 api_key = "your-personal-api-key"
@@ -273,6 +512,38 @@ fetch_limit = 50  # Fetch more stories for work workspace
 api_key = "your-client-api-key"
 user_id = "your-client-mention-name"
 # fetch_limit not specified, will use default of 50
+
+# Optional: named templates for `sc-cli show --template <name>`
+[client.templates]
+short = "{{id}}\t{{state}}\t{{owners}}\t{{name}}"
+
+# Optional: steps performed by `sc-cli start` (defaults shown)
+[client.start]
+assign = true
+move = true
+branch = true
+worktree = false
+# comment = "Started working on this"
+
+# Optional: local git settings
+[client.git]
+base_branch = "main"  # branch new story branches start from and `sc-cli finish` checks merges against
+remote = "origin"     # remote `sc-cli branch --push` pushes to
+# Placeholders: {id}, {user}, {type}, {slug} or {slug:N}, {repo} and, for worktrees, {branch}
+branch_template = "{user}/sc-{id}/{type}/{slug:40}"
+worktree_template = "~/wt/{repo}/{id}"
+max_branch_length = 60
+commit_tag_format = "{subject} [sc-{id}]"  # used by `sc-cli hooks install`
+
+# Optional: local repositories for `sc-cli branch --repos api,web`
+[client.git.repos]
+api = "~/code/api"
+web = "~/code/web"
+
+# Optional: how weeks are counted by the TUI, `timesheet --week` and `report --week`
+[client.week]
+start = "monday"
+timezone = "utc"  # "local" or a fixed offset such as "+02:00"
 "#
         .to_string()
     }
@@ -314,6 +585,210 @@ user_id = "prod.user"
         assert_eq!(prod_workspace.fetch_limit, 50); // Default value
     }
 
+    #[test]
+    fn test_config_parsing_templates() {
+        let config_content = r#"
+workspaces = ["test"]
+
+[test]
+api_key = "test-key"
+user_id = "test.user"
+
+[test.templates]
+short = "{{id}} {{name}}"
+"#;
+
+        let config: Config = toml::from_str(config_content).unwrap();
+        let workspace = config.get_workspace("test").unwrap();
+        assert_eq!(workspace.templates.len(), 1);
+        assert_eq!(workspace.templates["short"], "{{id}} {{name}}");
+
+        // Templates are optional and omitted when saving an empty set
+        let serialized = toml::to_string_pretty(&WorkspaceConfig::default()).unwrap();
+        assert!(!serialized.contains("templates"));
+    }
+
+    #[test]
+    fn test_config_parsing_pager() {
+        let config_content = r#"
+workspaces = ["test"]
+pager = "bat --paging=always"
+
+[test]
+api_key = "test-key"
+user_id = "test.user"
+"#;
+
+        let config: Config = toml::from_str(config_content).unwrap();
+        assert_eq!(config.pager.as_deref(), Some("bat --paging=always"));
+        // The pager key must not be mistaken for a workspace
+        assert_eq!(config.workspace_configs.len(), 1);
+    }
+
+    #[test]
+    fn test_config_parsing_start_steps() {
+        let config_content = r#"
+workspaces = ["test", "prod"]
+
+[test]
+api_key = "test-key"
+user_id = "test.user"
+
+[test.start]
+move = false
+worktree = true
+comment = "On it"
+
+[prod]
+api_key = "prod-key"
+user_id = "prod.user"
+"#;
+
+        let config: Config = toml::from_str(config_content).unwrap();
+
+        let test_start = &config.get_workspace("test").unwrap().start;
+        assert!(test_start.assign);
+        assert!(!test_start.move_story);
+        assert!(test_start.branch);
+        assert!(test_start.worktree);
+        assert_eq!(test_start.comment.as_deref(), Some("On it"));
+
+        assert_eq!(
+            config.get_workspace("prod").unwrap().start,
+            StartConfig::default()
+        );
+    }
+
+    #[test]
+    fn test_config_parsing_git() {
+        let config_content = r#"
+workspaces = ["test", "prod"]
+
+[test]
+api_key = "test-key"
+user_id = "test.user"
+
+[test.git]
+base_branch = "develop"
+remote = "upstream"
+branch_template = "{user}/sc-{id}/{slug:40}"
+max_branch_length = 60
+commit_tag_format = "sc-{id}: {subject}"
+
+[test.git.repos]
+api = "/src/api"
+web = "~/src/web"
+
+[prod]
+api_key = "prod-key"
+user_id = "prod.user"
+"#;
+
+        let config: Config = toml::from_str(config_content).unwrap();
+
+        assert_eq!(
+            config
+                .get_workspace("test")
+                .unwrap()
+                .git
+                .base_branch
+                .as_deref(),
+            Some("develop")
+        );
+        assert_eq!(
+            config.get_workspace("test").unwrap().git.remote.as_deref(),
+            Some("upstream")
+        );
+        assert_eq!(
+            config
+                .get_workspace("test")
+                .unwrap()
+                .git
+                .branch_template
+                .as_deref(),
+            Some("{user}/sc-{id}/{slug:40}")
+        );
+        assert_eq!(
+            config.get_workspace("test").unwrap().git.max_branch_length,
+            Some(60)
+        );
+        assert_eq!(
+            config
+                .get_workspace("test")
+                .unwrap()
+                .git
+                .commit_tag_format
+                .as_deref(),
+            Some("sc-{id}: {subject}")
+        );
+        assert_eq!(config.get_workspace("prod").unwrap().git.base_branch, None);
+
+        let git = &config.get_workspace("test").unwrap().git;
+        let paths = git
+            .repo_paths(&["web".to_string(), "api".to_string()])
+            .unwrap();
+        assert_eq!(paths[0].0, "web");
+        assert!(paths[0].1.ends_with("src/web"));
+        assert!(!paths[0].1.starts_with("~"));
+        assert_eq!(paths[1], ("api".to_string(), PathBuf::from("/src/api")));
+
+        assert_eq!(git.all_repo_paths()[0].0, "api");
+
+        let err = git.repo_paths(&["mobile".to_string()]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Unknown repository 'mobile'. Configured repositories: api, web"
+        );
+    }
+
+    #[test]
+    fn test_config_parsing_week() {
+        let config_content = r#"
+workspaces = ["test", "prod"]
+
+[test]
+api_key = "test-key"
+user_id = "test.user"
+
+[test.week]
+start = "sunday"
+timezone = "+02:00"
+
+[prod]
+api_key = "prod-key"
+user_id = "prod.user"
+"#;
+
+        let config: Config = toml::from_str(config_content).unwrap();
+        let week = &config.get_workspace("test").unwrap().week;
+        assert_eq!(week.start, Weekday::Sun);
+
+        // Saturday 23:00 UTC is already Sunday in UTC+2
+        let now: DateTime<Utc> = "2024-01-13T23:00:00Z".parse().unwrap();
+        assert_eq!(
+            week.start_of_week(now).to_rfc3339(),
+            "2024-01-13T22:00:00+00:00"
+        );
+        assert_eq!(week.date(now).to_string(), "2024-01-14");
+        assert!(week.contains("2024-01-20T21:59:00Z".parse().unwrap(), now));
+        assert!(!week.contains("2024-01-20T22:00:00Z".parse().unwrap(), now));
+
+        // Weeks start on Monday in UTC unless configured otherwise
+        assert_eq!(
+            WeekConfig::default().start_of_week(now).to_rfc3339(),
+            "2024-01-08T00:00:00+00:00"
+        );
+        assert_eq!(
+            config.get_workspace("prod").unwrap().week,
+            WeekConfig::default()
+        );
+
+        let invalid =
+            "[test]\napi_key = \"k\"\nuser_id = \"u\"\n[test.week]\ntimezone = \"Mars\"\n";
+        let err = toml::from_str::<HashMap<String, WorkspaceConfig>>(invalid).unwrap_err();
+        assert!(err.to_string().contains("Invalid week timezone 'Mars'"));
+    }
+
     #[test]
     fn test_workspace_not_found() {
         let config_content = r#"
@@ -0,0 +1,261 @@
+use crate::api::{PullRequest, Story};
+use crate::git::{self, Worktree};
+use crate::story_ref::StoryRef;
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+
+/// A local git action performed after a story is finished
+#[derive(Debug, Clone, PartialEq)]
+pub enum CleanupStep {
+    SwitchBranch(String),
+    RemoveWorktree(PathBuf),
+    DeleteBranch { name: String, force: bool },
+}
+
+impl CleanupStep {
+    /// Question asked before performing the step
+    pub fn prompt(&self) -> String {
+        match self {
+            CleanupStep::SwitchBranch(base) => format!("Switch back to '{base}'?"),
+            CleanupStep::RemoveWorktree(path) => {
+                format!("Remove worktree '{}'?", path.display())
+            }
+            CleanupStep::DeleteBranch { name, .. } => format!("Delete branch '{name}'?"),
+        }
+    }
+
+    /// Perform the step with git running in `dir` and return a message
+    /// describing what was done
+    pub fn run(&self, dir: &Path) -> Result<String> {
+        match self {
+            CleanupStep::SwitchBranch(base) => {
+                git::checkout_branch(dir, base)?;
+                Ok(format!("Switched to '{base}'"))
+            }
+            CleanupStep::RemoveWorktree(path) => {
+                let main = git::main_worktree()?;
+                git::remove_worktree(&main.path, path)?;
+                Ok(format!("Removed worktree '{}'", path.display()))
+            }
+            CleanupStep::DeleteBranch { name, force } => {
+                git::delete_branch(dir, name, *force)?;
+                Ok(format!("Deleted branch '{name}'"))
+            }
+        }
+    }
+}
+
+/// Linked pull requests that are neither merged nor closed
+pub fn open_pull_requests(story: &Story) -> Vec<&PullRequest> {
+    story
+        .pull_requests
+        .iter()
+        .filter(|pr| !pr.merged && !pr.closed)
+        .collect()
+}
+
+/// Whether the story's pull requests were all merged, which covers branches
+/// merged with squash or rebase that git no longer sees as ancestors
+pub fn merged_by_pull_request(story: &Story) -> bool {
+    story.pull_requests.iter().any(|pr| pr.merged) && open_pull_requests(story).is_empty()
+}
+
+/// Find the local branch of a story: Shortcut's formatted VCS branch name when
+/// it exists locally, otherwise the first branch referencing `sc-<id>`
+pub fn find_story_branch(story: &Story, local_branches: &[String]) -> Option<String> {
+    if let Some(name) = &story.formatted_vcs_branch_name
+        && local_branches.contains(name)
+    {
+        return Some(name.clone());
+    }
+
+    local_branches
+        .iter()
+        .find(|name| StoryRef::from_name(name).is_some_and(|r| r.id() == story.id))
+        .cloned()
+}
+
+/// Find the linked worktree that has `branch` checked out. The main worktree
+/// (the first entry) is never returned since it cannot be removed.
+pub fn find_story_worktree<'a>(branch: &str, worktrees: &'a [Worktree]) -> Option<&'a Worktree> {
+    worktrees
+        .iter()
+        .skip(1)
+        .find(|w| w.branch.as_deref() == Some(branch))
+}
+
+/// Work out the cleanup for a merged story branch.
+///
+/// A branch checked out in a linked worktree is removed together with the
+/// worktree; a branch checked out in the current worktree is left for `base`
+/// first, since git cannot delete the branch it is on.
+pub fn plan_cleanup(
+    branch: &str,
+    worktree: Option<&Worktree>,
+    current_branch: Option<&str>,
+    base: &str,
+    force_delete: bool,
+) -> Vec<CleanupStep> {
+    if branch == base {
+        return Vec::new();
+    }
+
+    let mut steps = Vec::new();
+    if let Some(worktree) = worktree {
+        steps.push(CleanupStep::RemoveWorktree(worktree.path.clone()));
+    } else if current_branch == Some(branch) {
+        steps.push(CleanupStep::SwitchBranch(base.to_string()));
+    }
+    steps.push(CleanupStep::DeleteBranch {
+        name: branch.to_string(),
+        force: force_delete,
+    });
+
+    steps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_story() -> Story {
+        Story {
+            id: 42,
+            name: "Fix login".to_string(),
+            workflow_state_id: 500,
+            app_url: "https://app.shortcut.com/org/story/42".to_string(),
+            story_type: "bug".to_string(),
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+            updated_at: "2024-01-01T00:00:00Z".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn pull_request(id: i64, merged: bool, closed: bool) -> PullRequest {
+        PullRequest {
+            id,
+            title: format!("PR {id}"),
+            url: format!("https://github.com/org/repo/pull/{id}"),
+            number: Some(id),
+            draft: false,
+            closed,
+            merged,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    fn worktree(path: &str, branch: &str) -> Worktree {
+        Worktree {
+            path: PathBuf::from(path),
+            head: None,
+            branch: Some(branch.to_string()),
+            bare: false,
+        }
+    }
+
+    fn branches(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn test_find_story_branch_prefers_formatted_name() {
+        let mut story = create_test_story();
+        story.formatted_vcs_branch_name = Some("edo/sc-42/fix-login".to_string());
+        let local = branches(&["main", "sc-42-old", "edo/sc-42/fix-login"]);
+        assert_eq!(
+            find_story_branch(&story, &local).as_deref(),
+            Some("edo/sc-42/fix-login")
+        );
+    }
+
+    #[test]
+    fn test_find_story_branch_by_pattern() {
+        let story = create_test_story();
+        let local = branches(&["main", "sc-420-other", "sc-42-fix-login"]);
+        assert_eq!(
+            find_story_branch(&story, &local).as_deref(),
+            Some("sc-42-fix-login")
+        );
+        assert_eq!(find_story_branch(&story, &branches(&["main"])), None);
+    }
+
+    #[test]
+    fn test_find_story_worktree_skips_main() {
+        let worktrees = vec![
+            worktree("/repo", "sc-42-fix"),
+            worktree("/repo-sc-42", "sc-42-fix"),
+        ];
+        assert_eq!(
+            find_story_worktree("sc-42-fix", &worktrees).map(|w| w.path.clone()),
+            Some(PathBuf::from("/repo-sc-42"))
+        );
+        assert_eq!(find_story_worktree("other", &worktrees), None);
+    }
+
+    #[test]
+    fn test_plan_cleanup_for_worktree() {
+        let wt = worktree("/repo-sc-42", "sc-42-fix");
+        let steps = plan_cleanup("sc-42-fix", Some(&wt), Some("main"), "main", false);
+        assert_eq!(
+            steps,
+            vec![
+                CleanupStep::RemoveWorktree(PathBuf::from("/repo-sc-42")),
+                CleanupStep::DeleteBranch {
+                    name: "sc-42-fix".to_string(),
+                    force: false,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_plan_cleanup_switches_away_from_current_branch() {
+        let steps = plan_cleanup("sc-42-fix", None, Some("sc-42-fix"), "develop", true);
+        assert_eq!(
+            steps,
+            vec![
+                CleanupStep::SwitchBranch("develop".to_string()),
+                CleanupStep::DeleteBranch {
+                    name: "sc-42-fix".to_string(),
+                    force: true,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_plan_cleanup_never_deletes_base() {
+        assert!(plan_cleanup("main", None, Some("main"), "main", false).is_empty());
+    }
+
+    #[test]
+    fn test_open_and_merged_pull_requests() {
+        let mut story = create_test_story();
+        assert!(!merged_by_pull_request(&story));
+
+        story.pull_requests = vec![pull_request(1, true, true), pull_request(2, false, true)];
+        assert!(open_pull_requests(&story).is_empty());
+        assert!(merged_by_pull_request(&story));
+
+        story.pull_requests.push(pull_request(3, false, false));
+        assert_eq!(open_pull_requests(&story).len(), 1);
+        assert!(!merged_by_pull_request(&story));
+    }
+
+    #[test]
+    fn test_prompts() {
+        assert_eq!(
+            CleanupStep::SwitchBranch("main".to_string()).prompt(),
+            "Switch back to 'main'?"
+        );
+        assert_eq!(
+            CleanupStep::DeleteBranch {
+                name: "sc-42".to_string(),
+                force: false
+            }
+            .prompt(),
+            "Delete branch 'sc-42'?"
+        );
+    }
+}
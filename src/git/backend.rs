@@ -0,0 +1,226 @@
+use super::GitRepoType;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use thiserror::Error;
+
+/// Errors reported by a [`GitBackend`]
+#[derive(Debug, Error)]
+pub enum GitError {
+    #[error("Not a git repository")]
+    NotARepo,
+    #[error("Branch '{0}' already exists")]
+    BranchExists(String),
+    #[error("Failed to run git: {0}")]
+    Spawn(#[from] std::io::Error),
+    #[error("git {args} failed: {stderr}")]
+    Command { args: String, stderr: String },
+    #[error("Failed to read the git repository: {0}")]
+    Repository(Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl GitError {
+    fn repository(error: impl std::error::Error + Send + Sync + 'static) -> Self {
+        GitError::Repository(Box::new(error))
+    }
+}
+
+/// Git operations needed to create and switch story branches, run against
+/// the repository containing the backend's directory (the current one by default)
+pub trait GitBackend {
+    /// Whether the directory is in a normal or bare repository
+    fn repo_type(&self) -> Result<GitRepoType, GitError>;
+
+    /// Name of the checked out branch, or `HEAD` when it is detached
+    fn current_branch(&self) -> Result<String, GitError>;
+
+    fn branch_exists(&self, branch_name: &str) -> Result<bool, GitError>;
+
+    /// Create a branch from `base` (the current HEAD when `None`) and check it out
+    fn create_branch(&self, branch_name: &str, base: Option<&str>) -> Result<(), GitError>;
+
+    /// Create a branch from `base` (the current HEAD when `None`) in a new worktree
+    fn create_worktree(
+        &self,
+        branch_name: &str,
+        worktree_path: &str,
+        base: Option<&str>,
+    ) -> Result<(), GitError>;
+
+    fn checkout_branch(&self, branch_name: &str) -> Result<(), GitError>;
+
+    /// Create a worktree for a branch that already exists
+    fn add_worktree(&self, branch_name: &str, worktree_path: &str) -> Result<(), GitError>;
+}
+
+/// Backend that runs the `git` binary
+#[derive(Debug, Clone, Default)]
+pub struct CommandBackend {
+    dir: Option<PathBuf>,
+}
+
+impl CommandBackend {
+    /// Run git in `dir` instead of the current directory
+    #[allow(dead_code)]
+    pub fn at(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: Some(dir.into()),
+        }
+    }
+
+    fn run(&self, args: &[&str]) -> Result<String, GitError> {
+        let mut command = Command::new("git");
+        if let Some(dir) = &self.dir {
+            command.current_dir(dir);
+        }
+        let output = command.args(args).output()?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+            if stderr.contains("not a git repository") {
+                return Err(GitError::NotARepo);
+            }
+            return Err(GitError::Command {
+                args: args.join(" "),
+                stderr,
+            });
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    fn run_creating(&self, branch_name: &str, args: &[&str]) -> Result<(), GitError> {
+        match self.run(args) {
+            Err(GitError::Command { stderr, .. })
+                if stderr.contains(&format!("a branch named '{branch_name}' already exists")) =>
+            {
+                Err(GitError::BranchExists(branch_name.to_string()))
+            }
+            result => result.map(|_| ()),
+        }
+    }
+}
+
+impl GitBackend for CommandBackend {
+    fn repo_type(&self) -> Result<GitRepoType, GitError> {
+        match self.run(&["rev-parse", "--is-bare-repository"]) {
+            Ok(is_bare) if is_bare.eq_ignore_ascii_case("true") => Ok(GitRepoType::Bare),
+            Ok(_) => Ok(GitRepoType::Normal),
+            Err(GitError::NotARepo | GitError::Command { .. }) => Ok(GitRepoType::NotARepo),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn current_branch(&self) -> Result<String, GitError> {
+        self.run(&["rev-parse", "--abbrev-ref", "HEAD"])
+    }
+
+    fn branch_exists(&self, branch_name: &str) -> Result<bool, GitError> {
+        let reference = format!("refs/heads/{branch_name}");
+        match self.run(&["show-ref", "--verify", "--quiet", &reference]) {
+            Ok(_) => Ok(true),
+            Err(GitError::Command { .. }) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn create_branch(&self, branch_name: &str, base: Option<&str>) -> Result<(), GitError> {
+        let mut args = vec!["checkout", "-b", branch_name];
+        args.extend(base);
+        self.run_creating(branch_name, &args)
+    }
+
+    fn create_worktree(
+        &self,
+        branch_name: &str,
+        worktree_path: &str,
+        base: Option<&str>,
+    ) -> Result<(), GitError> {
+        let mut args = vec!["worktree", "add", "-b", branch_name, worktree_path];
+        args.extend(base);
+        self.run_creating(branch_name, &args)
+    }
+
+    fn checkout_branch(&self, branch_name: &str) -> Result<(), GitError> {
+        self.run(&["checkout", branch_name]).map(|_| ())
+    }
+
+    fn add_worktree(&self, branch_name: &str, worktree_path: &str) -> Result<(), GitError> {
+        self.run(&["worktree", "add", worktree_path, branch_name])
+            .map(|_| ())
+    }
+}
+
+/// Backend that reads the repository in-process with gix, avoiding a `git`
+/// process for every check. Changes to branches and worktrees still go
+/// through [`CommandBackend`], since they also update the working tree.
+#[derive(Debug, Clone, Default)]
+pub struct GixBackend {
+    dir: Option<PathBuf>,
+}
+
+impl GixBackend {
+    /// Read the repository containing `dir` instead of the current directory
+    pub fn at(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: Some(dir.into()),
+        }
+    }
+
+    fn open(&self) -> Result<gix::Repository, GitError> {
+        let dir = self.dir.as_deref().unwrap_or(Path::new("."));
+        gix::discover(dir).map_err(|_| GitError::NotARepo)
+    }
+
+    /// The command backend for the same directory
+    fn command(&self) -> CommandBackend {
+        CommandBackend {
+            dir: self.dir.clone(),
+        }
+    }
+}
+
+impl GitBackend for GixBackend {
+    fn repo_type(&self) -> Result<GitRepoType, GitError> {
+        match self.open() {
+            Ok(repo) if repo.is_bare() => Ok(GitRepoType::Bare),
+            Ok(_) => Ok(GitRepoType::Normal),
+            Err(_) => Ok(GitRepoType::NotARepo),
+        }
+    }
+
+    fn current_branch(&self) -> Result<String, GitError> {
+        let head = self.open()?.head_name().map_err(GitError::repository)?;
+        Ok(head.map_or_else(|| "HEAD".to_string(), |name| name.shorten().to_string()))
+    }
+
+    fn branch_exists(&self, branch_name: &str) -> Result<bool, GitError> {
+        let reference = format!("refs/heads/{branch_name}");
+        let repo = self.open()?;
+        let found = repo
+            .try_find_reference(reference.as_str())
+            .map_err(GitError::repository)?;
+        Ok(found.is_some())
+    }
+
+    fn create_branch(&self, branch_name: &str, base: Option<&str>) -> Result<(), GitError> {
+        self.command().create_branch(branch_name, base)
+    }
+
+    fn create_worktree(
+        &self,
+        branch_name: &str,
+        worktree_path: &str,
+        base: Option<&str>,
+    ) -> Result<(), GitError> {
+        self.command()
+            .create_worktree(branch_name, worktree_path, base)
+    }
+
+    fn checkout_branch(&self, branch_name: &str) -> Result<(), GitError> {
+        self.command().checkout_branch(branch_name)
+    }
+
+    fn add_worktree(&self, branch_name: &str, worktree_path: &str) -> Result<(), GitError> {
+        self.command().add_worktree(branch_name, worktree_path)
+    }
+}
@@ -1,8 +1,17 @@
+use crate::story_ref::StoryRef;
 use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
+pub mod backend;
+pub mod naming;
 pub mod operations;
 
+pub use backend::{GitBackend, GitError, GixBackend};
+
+/// Remote new branches are pushed to when none is configured
+pub const DEFAULT_REMOTE: &str = "origin";
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum GitRepoType {
     Normal,
@@ -18,9 +27,9 @@ pub struct GitContext {
 
 impl GitContext {
     pub fn detect() -> Result<Self> {
-        let repo_type = detect_git_repo_type()?;
+        let repo_type = GixBackend::default().repo_type()?;
         let current_branch = if repo_type != GitRepoType::NotARepo {
-            get_current_branch().ok()
+            GixBackend::default().current_branch().ok()
         } else {
             None
         };
@@ -40,47 +49,118 @@ impl GitContext {
     }
 }
 
-/// Detect if the current directory is a git repository and what type
-pub fn detect_git_repo_type() -> Result<GitRepoType> {
-    // First check if we're in a git repository at all
-    let is_repo = Command::new("git")
-        .args(["rev-parse", "--git-dir"])
+/// Get the current git branch name
+pub fn get_current_branch() -> Result<String> {
+    Ok(GixBackend::default().current_branch()?)
+}
+
+/// Switch the repository in `dir` to an existing branch
+pub fn checkout_branch(dir: &Path, branch_name: &str) -> Result<()> {
+    Ok(GixBackend::at(dir).checkout_branch(branch_name)?)
+}
+
+/// Switch the repository in `dir` to an existing branch without carrying
+/// uncommitted changes over: they are stashed first with `stash`, otherwise
+/// the switch is refused. Returns whether changes were stashed; when the
+/// checkout fails they are popped again.
+pub fn switch_branch(dir: &Path, branch_name: &str, stash: bool) -> Result<bool> {
+    let dirty = has_uncommitted_changes(dir)?;
+    if dirty && !stash {
+        anyhow::bail!(
+            "You have uncommitted changes. Commit or stash them before switching to '{branch_name}'"
+        );
+    }
+    if dirty {
+        stash_changes(dir, &format!("sc-cli: before switching to {branch_name}"))?;
+    }
+    if let Err(e) = checkout_branch(dir, branch_name) {
+        if !dirty {
+            return Err(e);
+        }
+        match stash_pop(dir) {
+            Ok(()) => anyhow::bail!("{e} (stashed changes restored)"),
+            Err(pop) => anyhow::bail!("{e}\n{pop}"),
+        }
+    }
+    Ok(dirty)
+}
+
+/// Check whether tracked files of the repository in `dir` have uncommitted
+/// changes. Untracked files are ignored since switching branches leaves them alone.
+pub fn has_uncommitted_changes(dir: &Path) -> Result<bool> {
+    let output = Command::new("git")
+        .current_dir(dir)
+        .args(["status", "--porcelain", "--untracked-files=no"])
         .output()
-        .context("Failed to execute git command")?
-        .status
-        .success();
+        .context("Failed to check git status")?;
 
-    if !is_repo {
-        return Ok(GitRepoType::NotARepo);
+    if !output.status.success() {
+        let error = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("Failed to check git status: {}", error);
     }
 
-    // Check if it's a bare repository
-    let is_bare_output = Command::new("git")
-        .args(["rev-parse", "--is-bare-repository"])
+    Ok(!output.stdout.trim_ascii().is_empty())
+}
+
+/// Stash uncommitted changes of the repository in `dir`, including untracked files
+pub fn stash_changes(dir: &Path, message: &str) -> Result<()> {
+    let output = Command::new("git")
+        .current_dir(dir)
+        .args(["stash", "push", "--include-untracked", "-m", message])
         .output()
-        .context("Failed to check if repository is bare")?;
+        .context("Failed to stash changes")?;
 
-    if !is_bare_output.status.success() {
-        return Ok(GitRepoType::NotARepo);
+    if !output.status.success() {
+        let error = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("Failed to stash changes: {}", error);
     }
 
-    let is_bare = String::from_utf8_lossy(&is_bare_output.stdout)
-        .trim()
-        .eq_ignore_ascii_case("true");
+    Ok(())
+}
 
-    if is_bare {
-        Ok(GitRepoType::Bare)
-    } else {
-        Ok(GitRepoType::Normal)
+/// Restore the most recent stash of the repository in `dir`
+pub fn stash_pop(dir: &Path) -> Result<()> {
+    let output = Command::new("git")
+        .current_dir(dir)
+        .args(["stash", "pop"])
+        .output()
+        .context("Failed to restore stashed changes")?;
+
+    if !output.status.success() {
+        let error = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("Failed to restore stashed changes: {}", error);
     }
+
+    Ok(())
 }
 
-/// Get the current git branch name
-pub fn get_current_branch() -> Result<String> {
+/// Push a branch of the repository in `dir` and set it to track the remote branch
+pub fn push_branch(dir: &Path, remote: &str, branch_name: &str) -> Result<()> {
+    let output = Command::new("git")
+        .current_dir(dir)
+        .args(["push", "--set-upstream", remote, branch_name])
+        .output()
+        .context("Failed to push git branch")?;
+
+    if !output.status.success() {
+        let error = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!(
+            "Failed to push branch '{}' to '{}': {}",
+            branch_name,
+            remote,
+            error
+        );
+    }
+
+    Ok(())
+}
+
+/// List the local branch names
+pub fn list_local_branches() -> Result<Vec<String>> {
     let output = Command::new("git")
-        .args(["rev-parse", "--abbrev-ref", "HEAD"])
+        .args(["for-each-ref", "--format=%(refname:short)", "refs/heads"])
         .output()
-        .context("Failed to get current branch")?;
+        .context("Failed to list git branches")?;
 
     if !output.status.success() {
         anyhow::bail!(
@@ -89,37 +169,86 @@ pub fn get_current_branch() -> Result<String> {
         );
     }
 
-    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect())
+}
+
+/// Check whether every commit of `branch` is reachable from `base`
+pub fn is_branch_merged(branch_name: &str, base: &str) -> Result<bool> {
+    let output = Command::new("git")
+        .args(["merge-base", "--is-ancestor", branch_name, base])
+        .output()
+        .context("Failed to check if branch is merged")?;
+
+    match output.status.code() {
+        Some(0) => Ok(true),
+        Some(1) => Ok(false),
+        _ => anyhow::bail!(
+            "Failed to check if '{}' is merged into '{}': {}",
+            branch_name,
+            base,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ),
+    }
+}
+
+/// Whether a branch of the repository in `dir` points at the same commit as
+/// its upstream, so it holds no commits that were not pushed. False when it
+/// has no upstream.
+pub fn is_branch_pushed(dir: &Path, branch_name: &str) -> Result<bool> {
+    let rev_parse = |rev: &str| -> Result<Option<String>> {
+        let output = Command::new("git")
+            .current_dir(dir)
+            .args(["rev-parse", "--verify", "--quiet", rev])
+            .output()
+            .context("Failed to resolve git revision")?;
+        Ok(output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string()))
+    };
+    let tip = rev_parse(&format!("refs/heads/{branch_name}"))?;
+    let upstream = rev_parse(&format!("{branch_name}@{{upstream}}"))?;
+    Ok(tip.is_some() && tip == upstream)
 }
 
-/// Create a new git branch
-pub fn create_branch(branch_name: &str) -> Result<()> {
+/// Delete a local branch of the repository in `dir`. Without `force` git
+/// refuses to delete unmerged work.
+pub fn delete_branch(dir: &Path, branch_name: &str, force: bool) -> Result<()> {
+    let flag = if force { "-D" } else { "-d" };
     let output = Command::new("git")
-        .args(["checkout", "-b", branch_name])
+        .current_dir(dir)
+        .args(["branch", flag, branch_name])
         .output()
-        .context("Failed to create git branch")?;
+        .context("Failed to delete git branch")?;
 
     if !output.status.success() {
         let error = String::from_utf8_lossy(&output.stderr);
-        anyhow::bail!("Failed to create branch '{}': {}", branch_name, error);
+        anyhow::bail!("Failed to delete branch '{}': {}", branch_name, error);
     }
 
     Ok(())
 }
 
-/// Create a new git worktree for bare repositories
-pub fn create_worktree(branch_name: &str, worktree_path: &str) -> Result<()> {
+/// Remove a linked worktree, running git from `repo_dir` so the directory
+/// being removed is not in use. Git refuses when it has uncommitted changes.
+pub fn remove_worktree(repo_dir: &Path, worktree_path: &Path) -> Result<()> {
     let output = Command::new("git")
-        .args(["worktree", "add", "-b", branch_name, worktree_path])
+        .current_dir(repo_dir)
+        .arg("worktree")
+        .arg("remove")
+        .arg(worktree_path)
         .output()
-        .context("Failed to create git worktree")?;
+        .context("Failed to remove git worktree")?;
 
     if !output.status.success() {
         let error = String::from_utf8_lossy(&output.stderr);
         anyhow::bail!(
-            "Failed to create worktree '{}' at '{}': {}",
-            branch_name,
-            worktree_path,
+            "Failed to remove worktree '{}': {}",
+            worktree_path.display(),
             error
         );
     }
@@ -127,27 +256,267 @@ pub fn create_worktree(branch_name: &str, worktree_path: &str) -> Result<()> {
     Ok(())
 }
 
-/// Check if a branch already exists
-pub fn branch_exists(branch_name: &str) -> Result<bool> {
+/// Drop the records of worktrees whose directories no longer exist
+pub fn prune_worktrees() -> Result<()> {
+    let output = Command::new("git")
+        .args(["worktree", "prune"])
+        .output()
+        .context("Failed to prune git worktrees")?;
+
+    if !output.status.success() {
+        let error = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("Failed to prune worktrees: {}", error);
+    }
+
+    Ok(())
+}
+
+/// A worktree as listed by `git worktree list --porcelain`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Worktree {
+    pub path: PathBuf,
+    pub head: Option<String>,
+    /// Short branch name, `None` for bare or detached worktrees
+    pub branch: Option<String>,
+    pub bare: bool,
+}
+
+/// List the worktrees of the current repository. The first entry is the main
+/// worktree (or the bare repository itself).
+pub fn list_worktrees() -> Result<Vec<Worktree>> {
+    let output = Command::new("git")
+        .args(["worktree", "list", "--porcelain"])
+        .output()
+        .context("Failed to list git worktrees")?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "Git command failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    Ok(parse_worktree_list(&String::from_utf8_lossy(
+        &output.stdout,
+    )))
+}
+
+/// The main worktree of the current repository, where git can run while a
+/// linked worktree is removed
+pub fn main_worktree() -> Result<Worktree> {
+    list_worktrees()?
+        .into_iter()
+        .next()
+        .context("Failed to find the main worktree")
+}
+
+/// Parse the output of `git worktree list --porcelain`
+pub fn parse_worktree_list(porcelain: &str) -> Vec<Worktree> {
+    let mut worktrees = Vec::new();
+    let mut current: Option<Worktree> = None;
+
+    for line in porcelain.lines() {
+        if let Some(path) = line.strip_prefix("worktree ") {
+            worktrees.extend(current.take());
+            current = Some(Worktree {
+                path: PathBuf::from(path),
+                head: None,
+                branch: None,
+                bare: false,
+            });
+        } else if let Some(worktree) = current.as_mut() {
+            if let Some(head) = line.strip_prefix("HEAD ") {
+                worktree.head = Some(head.to_string());
+            } else if let Some(branch) = line.strip_prefix("branch ") {
+                let short = branch.strip_prefix("refs/heads/").unwrap_or(branch);
+                worktree.branch = Some(short.to_string());
+            } else if line == "bare" {
+                worktree.bare = true;
+            }
+        }
+    }
+    worktrees.extend(current);
+
+    worktrees
+}
+
+/// A commit as listed by `git log`
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogEntry {
+    pub hash: String,
+    pub parents: Vec<String>,
+    pub subject: String,
+    pub body: String,
+}
+
+impl LogEntry {
+    pub fn short_hash(&self) -> &str {
+        &self.hash[..self.hash.len().min(7)]
+    }
+
+    pub fn is_merge(&self) -> bool {
+        self.parents.len() > 1
+    }
+}
+
+/// Field and record separators that cannot appear in commit messages
+const LOG_FORMAT: &str = "--format=%H%x1f%P%x1f%s%x1f%b%x1e";
+
+/// List the commits in a revision range such as `v1.2.0..HEAD`, newest first
+pub fn log_range(range: &str) -> Result<Vec<LogEntry>> {
+    let output = Command::new("git")
+        .args(["log", LOG_FORMAT, range, "--"])
+        .output()
+        .context("Failed to read git log")?;
+
+    if !output.status.success() {
+        let error = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("Failed to read the commits in '{}': {}", range, error);
+    }
+
+    Ok(parse_log(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Parse the output of `git log` with [`LOG_FORMAT`]
+pub fn parse_log(output: &str) -> Vec<LogEntry> {
+    output
+        .split('\x1e')
+        .filter_map(|record| {
+            let mut fields = record.trim_start_matches('\n').split('\x1f');
+            let hash = fields.next().filter(|hash| !hash.is_empty())?;
+            Some(LogEntry {
+                hash: hash.to_string(),
+                parents: fields
+                    .next()
+                    .unwrap_or_default()
+                    .split_whitespace()
+                    .map(str::to_string)
+                    .collect(),
+                subject: fields.next().unwrap_or_default().to_string(),
+                body: fields.next().unwrap_or_default().trim().to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Hashes of the commits a merge brought in from its other parents
+pub fn merged_commits(merge: &LogEntry) -> Result<Vec<String>> {
+    let Some((first, others)) = merge.parents.split_first() else {
+        return Ok(Vec::new());
+    };
+    let output = Command::new("git")
+        .arg("rev-list")
+        .args(others)
+        .arg(format!("^{first}"))
+        .output()
+        .context("Failed to list merged commits")?;
+
+    if !output.status.success() {
+        let error = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!(
+            "Failed to list the commits merged by {}: {}",
+            merge.hash,
+            error
+        );
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::to_string)
+        .collect())
+}
+
+/// Guess the base branch: the remote's default branch, then `main`, then `master`
+pub fn default_base_branch() -> String {
+    let output = Command::new("git")
+        .args(["symbolic-ref", "--short", "refs/remotes/origin/HEAD"])
+        .output();
+    if let Ok(output) = output
+        && output.status.success()
+    {
+        let remote_head = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if let Some(branch) = remote_head.strip_prefix("origin/") {
+            return branch.to_string();
+        }
+    }
+
+    let git = GixBackend::default();
+    let exists = |name| git.branch_exists(name).unwrap_or(false);
+    if !exists("main") && exists("master") {
+        return "master".to_string();
+    }
+    "main".to_string()
+}
+
+/// Directory git runs hooks from. It honours `core.hooksPath` and is shared
+/// by all worktrees of a repository, bare or not.
+pub fn hooks_dir() -> Result<PathBuf> {
     let output = Command::new("git")
-        .args([
-            "show-ref",
-            "--verify",
-            "--quiet",
-            &format!("refs/heads/{branch_name}"),
-        ])
+        .args(["rev-parse", "--path-format=absolute", "--git-path", "hooks"])
         .output()
-        .context("Failed to check if branch exists")?;
+        .context("Failed to find the git hooks directory")?;
 
-    Ok(output.status.success())
+    if !output.status.success() {
+        anyhow::bail!(
+            "Git command failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    Ok(PathBuf::from(
+        String::from_utf8_lossy(&output.stdout).trim(),
+    ))
+}
+
+/// Where the story for the current directory was detected from
+#[derive(Debug, Clone, PartialEq)]
+pub enum StorySource {
+    Branch(String),
+    Directory(String),
+}
+
+impl std::fmt::Display for StorySource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StorySource::Branch(name) => write!(f, "branch '{name}'"),
+            StorySource::Directory(name) => write!(f, "directory '{name}'"),
+        }
+    }
+}
+
+/// Get the top-level directory of the current worktree
+pub fn get_toplevel_dir() -> Result<PathBuf> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--show-toplevel"])
+        .output()
+        .context("Failed to get repository top-level directory")?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "Git command failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    Ok(PathBuf::from(
+        String::from_utf8_lossy(&output.stdout).trim(),
+    ))
 }
 
-/// Generate a safe worktree directory name from branch name
-pub fn generate_worktree_path(branch_name: &str) -> String {
-    // Replace slashes and other problematic characters with dashes
-    let safe_name = branch_name.replace(['/', '\\', ' '], "-");
+/// Detect the story being worked on from the checked-out branch, falling back
+/// to the worktree (or current) directory name
+pub fn detect_current_story() -> Option<(StoryRef, StorySource)> {
+    if let Ok(branch) = get_current_branch()
+        && let Some(story_ref) = StoryRef::from_name(&branch)
+    {
+        return Some((story_ref, StorySource::Branch(branch)));
+    }
 
-    format!("../{safe_name}")
+    let dir = get_toplevel_dir()
+        .ok()
+        .or_else(|| std::env::current_dir().ok())?;
+    let dir_name = dir.file_name()?.to_string_lossy().to_string();
+    StoryRef::from_name(&dir_name).map(|story_ref| (story_ref, StorySource::Directory(dir_name)))
 }
 
 #[cfg(test)]
@@ -199,27 +568,69 @@ mod tests {
     }
 
     #[test]
-    fn test_generate_worktree_path() {
-        assert_eq!(generate_worktree_path("feature/test"), "../feature-test");
+    fn test_parse_worktree_list() {
+        let porcelain = "worktree /repo\nHEAD 1111\nbranch refs/heads/main\n\n\
+                         worktree /repo-sc-42\nHEAD 2222\nbranch refs/heads/edo/sc-42/fix\n\n\
+                         worktree /repo-detached\nHEAD 3333\ndetached\n";
+        let worktrees = parse_worktree_list(porcelain);
+
+        assert_eq!(worktrees.len(), 3);
+        assert_eq!(worktrees[0].path, PathBuf::from("/repo"));
+        assert_eq!(worktrees[0].branch.as_deref(), Some("main"));
+        assert_eq!(worktrees[1].branch.as_deref(), Some("edo/sc-42/fix"));
+        assert_eq!(worktrees[1].head.as_deref(), Some("2222"));
+        assert_eq!(worktrees[2].branch, None);
+    }
+
+    #[test]
+    fn test_parse_log() {
+        let output = "aaaa111122223333\x1fpppp\x1fFix login [sc-42]\x1fLonger\nbody\n\x1e\n\
+                      bbbb111122223333\x1fp1 p2\x1fMerge branch 'sc-7-x'\x1f\x1e\n";
+        let entries = parse_log(output);
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].short_hash(), "aaaa111");
+        assert_eq!(entries[0].subject, "Fix login [sc-42]");
+        assert_eq!(entries[0].body, "Longer\nbody");
+        assert!(!entries[0].is_merge());
+        assert_eq!(entries[1].parents, vec!["p1", "p2"]);
+        assert!(entries[1].is_merge());
+        assert_eq!(parse_log(""), vec![]);
+    }
+
+    #[test]
+    fn test_parse_worktree_list_bare() {
+        let worktrees = parse_worktree_list("worktree /repo.git\nbare\n");
+        assert_eq!(worktrees.len(), 1);
+        assert!(worktrees[0].bare);
+        assert_eq!(parse_worktree_list(""), vec![]);
+    }
+
+    #[test]
+    fn test_story_source_display() {
         assert_eq!(
-            generate_worktree_path("edo/sc-63/story-name"),
-            "../edo-sc-63-story-name"
+            StorySource::Branch("sc-42-fix".to_string()).to_string(),
+            "branch 'sc-42-fix'"
+        );
+        assert_eq!(
+            StorySource::Directory("sc-42".to_string()).to_string(),
+            "directory 'sc-42'"
         );
-        assert_eq!(generate_worktree_path("simple"), "../simple");
     }
 
     #[test]
     fn test_detect_non_git_directory() {
         let temp_dir = TempDir::new().unwrap();
-        let original_dir = std::env::current_dir().unwrap();
-
-        // Change to temp directory
-        std::env::set_current_dir(temp_dir.path()).unwrap();
 
-        let result = detect_git_repo_type().unwrap();
-        assert_eq!(result, GitRepoType::NotARepo);
-
-        // Restore original directory
-        std::env::set_current_dir(original_dir).unwrap();
+        assert_eq!(
+            GixBackend::at(temp_dir.path()).repo_type().unwrap(),
+            GitRepoType::NotARepo
+        );
+        assert_eq!(
+            backend::CommandBackend::at(temp_dir.path())
+                .repo_type()
+                .unwrap(),
+            GitRepoType::NotARepo
+        );
     }
 }
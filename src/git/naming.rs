@@ -0,0 +1,416 @@
+use crate::api::Story;
+use crate::config::GitConfig;
+use anyhow::Result;
+use std::path::Path;
+use std::process::Command;
+
+/// Branch name used when no template is configured and Shortcut has no
+/// formatted VCS branch name for the story
+pub const DEFAULT_BRANCH_TEMPLATE: &str = "sc-{id}-{slug:40}";
+
+/// Worktree path used when no template is configured
+pub const DEFAULT_WORKTREE_TEMPLATE: &str = "../{branch}";
+
+const PLACEHOLDERS: [&str; 6] = ["id", "user", "type", "slug", "repo", "branch"];
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Literal(String),
+    Placeholder {
+        name: String,
+        max_len: Option<usize>,
+    },
+}
+
+/// A branch or worktree template such as `{user}/sc-{id}/{type}/{slug:40}`.
+///
+/// Placeholders are `{id}`, `{user}`, `{type}`, `{slug}`, `{repo}` and, for
+/// worktree paths, `{branch}`. `{slug:N}` limits the slug to N characters.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NameTemplate {
+    segments: Vec<Segment>,
+}
+
+impl NameTemplate {
+    pub fn parse(source: &str) -> Result<Self> {
+        let mut segments = Vec::new();
+        let mut rest = source;
+
+        while let Some(start) = rest.find('{') {
+            if start > 0 {
+                segments.push(Segment::Literal(rest[..start].to_string()));
+            }
+            let Some(end) = rest[start..].find('}') else {
+                anyhow::bail!("Unclosed '{{' in template: {source}");
+            };
+            let placeholder = &rest[start + 1..start + end];
+            let (name, max_len) = match placeholder.split_once(':') {
+                Some((name, len)) => {
+                    let len = len.trim().parse::<usize>().map_err(|_| {
+                        anyhow::anyhow!(
+                            "Invalid length in '{{{placeholder}}}' in template: {source}"
+                        )
+                    })?;
+                    (name.trim(), Some(len))
+                }
+                None => (placeholder.trim(), None),
+            };
+            if !PLACEHOLDERS.contains(&name) {
+                anyhow::bail!(
+                    "Unknown placeholder '{{{}}}' in template: {}. Available placeholders: {}",
+                    name,
+                    source,
+                    PLACEHOLDERS.map(|p| format!("{{{p}}}")).join(", ")
+                );
+            }
+            segments.push(Segment::Placeholder {
+                name: name.to_string(),
+                max_len,
+            });
+            rest = &rest[start + end + 1..];
+        }
+        if !rest.is_empty() {
+            segments.push(Segment::Literal(rest.to_string()));
+        }
+
+        Ok(Self { segments })
+    }
+
+    fn uses(&self, placeholder: &str) -> bool {
+        self.segments
+            .iter()
+            .any(|s| matches!(s, Segment::Placeholder { name, .. } if name == placeholder))
+    }
+
+    fn render(&self, values: &NameValues) -> String {
+        let mut output = String::new();
+        for segment in &self.segments {
+            match segment {
+                Segment::Literal(text) => output.push_str(text),
+                Segment::Placeholder { name, max_len } => {
+                    if name == "slug" {
+                        output.push_str(&slugify(values.title, *max_len));
+                        continue;
+                    }
+                    let value = match name.as_str() {
+                        "id" => values.id.to_string(),
+                        "user" => values.user.to_string(),
+                        "type" => values.story_type.to_string(),
+                        "repo" => values.repo.to_string(),
+                        "branch" => values.branch.replace(['/', '\\', ' '], "-"),
+                        _ => String::new(),
+                    };
+                    match max_len {
+                        Some(len) => output.extend(value.chars().take(*len)),
+                        None => output.push_str(&value),
+                    }
+                }
+            }
+        }
+        output
+    }
+}
+
+struct NameValues<'a> {
+    id: i64,
+    user: &'a str,
+    story_type: &'a str,
+    title: &'a str,
+    repo: &'a str,
+    branch: &'a str,
+}
+
+/// Turn a story title into a branch-safe slug: lowercase, runs of anything
+/// that is not a letter or digit become a single `-`. With `max_len` the slug
+/// is cut at the last word that fits, or hard-cut when the first word is
+/// already too long.
+pub fn slugify(title: &str, max_len: Option<usize>) -> String {
+    let lowered = title.to_lowercase();
+    let words = lowered
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty());
+
+    let mut slug = String::new();
+    for word in words {
+        let separator = usize::from(!slug.is_empty());
+        if let Some(max_len) = max_len
+            && slug.chars().count() + separator + word.chars().count() > max_len
+        {
+            if slug.is_empty() {
+                slug.extend(word.chars().take(max_len));
+            }
+            break;
+        }
+        if separator == 1 {
+            slug.push('-');
+        }
+        slug.push_str(word);
+    }
+    slug
+}
+
+/// Cut a branch name to `max_len` characters without leaving a dangling
+/// separator, which git would reject or make awkward to type
+fn truncate_branch(name: &str, max_len: usize) -> String {
+    let truncated: String = name.chars().take(max_len).collect();
+    truncated.trim_end_matches(['-', '/', '.', '_']).to_string()
+}
+
+/// How branch names and worktree paths are built for a workspace, shared by
+/// the CLI commands and the TUI so both suggest the same names
+#[derive(Debug, Clone, PartialEq)]
+pub struct BranchNaming {
+    branch_template: Option<NameTemplate>,
+    worktree_template: NameTemplate,
+    max_branch_length: Option<usize>,
+    user: String,
+    repo: String,
+}
+
+impl Default for BranchNaming {
+    fn default() -> Self {
+        Self {
+            branch_template: None,
+            worktree_template: NameTemplate::parse(DEFAULT_WORKTREE_TEMPLATE)
+                .expect("default worktree template is valid"),
+            max_branch_length: None,
+            user: String::new(),
+            repo: String::new(),
+        }
+    }
+}
+
+impl BranchNaming {
+    /// Build the naming rules from a workspace's git settings. `user` fills
+    /// the `{user}` placeholder and the repository name is detected from git.
+    pub fn new(config: &GitConfig, user: &str) -> Result<Self> {
+        let branch_template = config
+            .branch_template
+            .as_deref()
+            .map(NameTemplate::parse)
+            .transpose()?;
+        if branch_template.as_ref().is_some_and(|t| t.uses("branch")) {
+            anyhow::bail!("The branch template cannot use the {{branch}} placeholder");
+        }
+        let worktree_template = NameTemplate::parse(
+            config
+                .worktree_template
+                .as_deref()
+                .unwrap_or(DEFAULT_WORKTREE_TEMPLATE),
+        )?;
+
+        Ok(Self {
+            branch_template,
+            worktree_template,
+            max_branch_length: config.max_branch_length,
+            user: user.to_string(),
+            repo: repo_name(Path::new(".")).unwrap_or_default(),
+        })
+    }
+
+    /// The same rules with `{repo}` taken from the repository in `dir`, for
+    /// running in several repositories one after another
+    pub fn for_repo(&self, dir: &Path) -> Self {
+        Self {
+            repo: repo_name(dir).unwrap_or_default(),
+            ..self.clone()
+        }
+    }
+
+    /// Suggest a branch name for a story: the configured template, otherwise
+    /// Shortcut's formatted VCS branch name, otherwise `sc-<id>-<slug>`
+    pub fn branch_name(&self, story: &Story) -> String {
+        let name = match (&self.branch_template, &story.formatted_vcs_branch_name) {
+            (Some(template), _) => template.render(&self.values(story, "")),
+            (None, Some(formatted)) => formatted.clone(),
+            (None, None) => NameTemplate::parse(DEFAULT_BRANCH_TEMPLATE)
+                .expect("default branch template is valid")
+                .render(&self.values(story, "")),
+        };
+        match self.max_branch_length {
+            Some(max_len) => truncate_branch(&name, max_len),
+            None => name,
+        }
+    }
+
+    /// Suggest a worktree path for a story's branch. A leading `~/` is
+    /// expanded to the home directory since git does not do it.
+    pub fn worktree_path(&self, story: &Story, branch_name: &str) -> String {
+        let path = self
+            .worktree_template
+            .render(&self.values(story, branch_name));
+        match (path.strip_prefix("~/"), dirs::home_dir()) {
+            (Some(rest), Some(home)) => home.join(rest).to_string_lossy().to_string(),
+            _ => path,
+        }
+    }
+
+    fn values<'a>(&'a self, story: &'a Story, branch: &'a str) -> NameValues<'a> {
+        NameValues {
+            id: story.id,
+            user: &self.user,
+            story_type: &story.story_type,
+            title: &story.name,
+            repo: &self.repo,
+            branch,
+        }
+    }
+}
+
+/// Name of the repository in `dir`, shared by all of its worktrees: the
+/// directory holding `.git`, or the bare repository without its `.git` suffix
+pub fn repo_name(dir: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .current_dir(dir)
+        .args(["rev-parse", "--path-format=absolute", "--git-common-dir"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let common_dir = std::path::PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+    repo_name_from_git_dir(&common_dir)
+}
+
+fn repo_name_from_git_dir(git_dir: &std::path::Path) -> Option<String> {
+    let name = git_dir.file_name()?.to_string_lossy().to_string();
+    if name == ".git" {
+        return Some(git_dir.parent()?.file_name()?.to_string_lossy().to_string());
+    }
+    Some(name.strip_suffix(".git").unwrap_or(&name).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_story() -> Story {
+        Story {
+            id: 42,
+            name: "Fix the login/logout flow for SSO users".to_string(),
+            workflow_state_id: 500,
+            story_type: "bug".to_string(),
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+            updated_at: "2024-01-01T00:00:00Z".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn naming(branch: Option<&str>, worktree: Option<&str>, max: Option<usize>) -> BranchNaming {
+        let config = GitConfig {
+            branch_template: branch.map(str::to_string),
+            worktree_template: worktree.map(str::to_string),
+            max_branch_length: max,
+            ..Default::default()
+        };
+        BranchNaming {
+            repo: "api".to_string(),
+            ..BranchNaming::new(&config, "edo").unwrap()
+        }
+    }
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(
+            slugify("Fix the login/logout flow!", None),
+            "fix-the-login-logout-flow"
+        );
+        assert_eq!(slugify("  --Hello,   World--  ", None), "hello-world");
+        assert_eq!(slugify("Ünïcode names", None), "ünïcode-names");
+        assert_eq!(slugify("", None), "");
+    }
+
+    #[test]
+    fn test_slugify_max_length_cuts_at_words() {
+        assert_eq!(
+            slugify("Fix the login/logout flow", Some(13)),
+            "fix-the-login"
+        );
+        assert_eq!(slugify("Fix the login/logout flow", Some(12)), "fix-the");
+        assert_eq!(slugify("Supercalifragilistic", Some(5)), "super");
+    }
+
+    #[test]
+    fn test_default_branch_name() {
+        let mut story = create_test_story();
+        let naming = BranchNaming::default();
+        assert_eq!(
+            naming.branch_name(&story),
+            "sc-42-fix-the-login-logout-flow-for-sso-users"
+        );
+
+        story.formatted_vcs_branch_name = Some("edo/sc-42/fix-login".to_string());
+        assert_eq!(naming.branch_name(&story), "edo/sc-42/fix-login");
+    }
+
+    #[test]
+    fn test_branch_template() {
+        let mut story = create_test_story();
+        story.formatted_vcs_branch_name = Some("ignored".to_string());
+        let naming = naming(Some("{user}/sc-{id}/{type}/{slug:16}"), None, None);
+        assert_eq!(naming.branch_name(&story), "edo/sc-42/bug/fix-the-login");
+    }
+
+    #[test]
+    fn test_branch_max_length_trims_separators() {
+        let naming = naming(Some("sc-{id}-{slug}"), None, Some(13));
+        assert_eq!(naming.branch_name(&create_test_story()), "sc-42-fix-the");
+    }
+
+    #[test]
+    fn test_default_worktree_path() {
+        let story = create_test_story();
+        assert_eq!(
+            BranchNaming::default().worktree_path(&story, "edo/sc-42/fix"),
+            "../edo-sc-42-fix"
+        );
+    }
+
+    #[test]
+    fn test_worktree_template() {
+        let story = create_test_story();
+        let naming = naming(None, Some("/tmp/wt/{repo}/{id}-{slug:9}"), None);
+        assert_eq!(
+            naming.worktree_path(&story, "sc-42"),
+            "/tmp/wt/api/42-fix-the"
+        );
+    }
+
+    #[test]
+    fn test_worktree_template_expands_home() {
+        let Some(home) = dirs::home_dir() else {
+            return;
+        };
+        let naming = naming(None, Some("~/wt/{repo}/{id}"), None);
+        assert_eq!(
+            naming.worktree_path(&create_test_story(), "sc-42"),
+            home.join("wt/api/42").to_string_lossy()
+        );
+    }
+
+    #[test]
+    fn test_template_errors() {
+        assert!(NameTemplate::parse("sc-{id").is_err());
+        assert!(NameTemplate::parse("{slug:abc}").is_err());
+        let err = NameTemplate::parse("{owner}/{id}").unwrap_err();
+        assert!(err.to_string().contains("Unknown placeholder '{owner}'"));
+
+        let config = GitConfig {
+            branch_template: Some("{branch}-2".to_string()),
+            ..Default::default()
+        };
+        assert!(BranchNaming::new(&config, "edo").is_err());
+    }
+
+    #[test]
+    fn test_repo_name_from_git_dir() {
+        assert_eq!(
+            repo_name_from_git_dir(Path::new("/src/api/.git")).as_deref(),
+            Some("api")
+        );
+        assert_eq!(
+            repo_name_from_git_dir(Path::new("/src/web.git")).as_deref(),
+            Some("web")
+        );
+    }
+}
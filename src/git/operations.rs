@@ -1,14 +1,18 @@
 use crate::api::{ShortcutApi, Story, Workflow};
+use anyhow::Result;
+use std::path::{Path, PathBuf};
 
-use super::{GitContext, GitRepoType};
+use super::{GitBackend, GitContext, GitError, GitRepoType, GixBackend};
 
 /// Request to create a git branch or worktree
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct GitBranchRequest {
     pub branch_name: String,
     pub worktree_path: String,
     pub operation: GitOperation,
     pub story_id: i64,
+    /// Ref new branches start from; the current HEAD when not set
+    pub base: Option<String>,
 }
 
 /// Type of git operation to perform
@@ -58,23 +62,48 @@ impl GitBranchResult {
 }
 
 /// Execute a git branch or worktree creation operation
-pub fn execute_git_operation(request: &GitBranchRequest) -> GitBranchResult {
+pub fn execute_git_operation<G: GitBackend>(
+    git: &G,
+    request: &GitBranchRequest,
+) -> GitBranchResult {
     match &request.operation {
-        GitOperation::CreateBranch => execute_create_branch(request),
-        GitOperation::CreateWorktree => execute_create_worktree(request),
+        GitOperation::CreateBranch => execute_create_branch(git, request),
+        GitOperation::CreateWorktree => execute_create_worktree(git, request),
+    }
+}
+
+/// Stash the uncommitted changes in `dir` and run the request there. When it
+/// fails the changes are popped again, so the user is never left stashed on
+/// the branch they started from.
+pub fn execute_after_stash<G: GitBackend>(
+    git: &G,
+    dir: &Path,
+    request: &GitBranchRequest,
+) -> Result<GitBranchResult> {
+    super::stash_changes(
+        dir,
+        &format!("sc-cli: before switching to {}", request.branch_name),
+    )?;
+    let mut result = execute_git_operation(git, request);
+    if !result.success {
+        match super::stash_pop(dir) {
+            Ok(()) => result.message.push_str(" (stashed changes restored)"),
+            Err(e) => result.message = format!("{}\n{e}", result.message),
+        }
     }
+    Ok(result)
 }
 
-fn execute_create_branch(request: &GitBranchRequest) -> GitBranchResult {
+fn execute_create_branch<G: GitBackend>(git: &G, request: &GitBranchRequest) -> GitBranchResult {
     // Check if branch already exists
-    match super::branch_exists(&request.branch_name) {
+    match git.branch_exists(&request.branch_name) {
         Ok(true) => GitBranchResult::failure(
             format!("Branch '{}' already exists", request.branch_name),
             request,
         ),
         Ok(false) => {
             // Create the branch
-            match super::create_branch(&request.branch_name) {
+            match git.create_branch(&request.branch_name, request.base.as_deref()) {
                 Ok(()) => GitBranchResult::success(
                     format!(
                         "Successfully created and switched to branch '{}'",
@@ -94,8 +123,12 @@ fn execute_create_branch(request: &GitBranchRequest) -> GitBranchResult {
     }
 }
 
-fn execute_create_worktree(request: &GitBranchRequest) -> GitBranchResult {
-    match super::create_worktree(&request.branch_name, &request.worktree_path) {
+fn execute_create_worktree<G: GitBackend>(git: &G, request: &GitBranchRequest) -> GitBranchResult {
+    match git.create_worktree(
+        &request.branch_name,
+        &request.worktree_path,
+        request.base.as_deref(),
+    ) {
         Ok(()) => GitBranchResult::success(
             format!(
                 "Successfully created worktree '{}' at '{}'",
@@ -103,10 +136,113 @@ fn execute_create_worktree(request: &GitBranchRequest) -> GitBranchResult {
             ),
             request,
         ),
+        Err(e @ GitError::BranchExists(_)) => GitBranchResult::failure(e.to_string(), request),
         Err(e) => GitBranchResult::failure(format!("Failed to create worktree: {e}"), request),
     }
 }
 
+/// Like `execute_git_operation`, but reuse the branch when it already exists:
+/// check it out, or add a worktree for it, instead of failing
+pub fn execute_checkout_or_create<G: GitBackend>(
+    git: &G,
+    request: &GitBranchRequest,
+) -> GitBranchResult {
+    match git.branch_exists(&request.branch_name) {
+        Ok(false) => execute_git_operation(git, request),
+        Ok(true) => match request.operation {
+            GitOperation::CreateBranch => match git.checkout_branch(&request.branch_name) {
+                Ok(()) => GitBranchResult::success(
+                    format!("Switched to existing branch '{}'", request.branch_name),
+                    request,
+                ),
+                Err(e) => GitBranchResult::failure(e.to_string(), request),
+            },
+            GitOperation::CreateWorktree => {
+                match git.add_worktree(&request.branch_name, &request.worktree_path) {
+                    Ok(()) => GitBranchResult::success(
+                        format!(
+                            "Created worktree for existing branch '{}' at '{}'",
+                            request.branch_name, request.worktree_path
+                        ),
+                        request,
+                    ),
+                    Err(e) => GitBranchResult::failure(e.to_string(), request),
+                }
+            }
+        },
+        Err(e) => {
+            GitBranchResult::failure(format!("Failed to check if branch exists: {e}"), request)
+        }
+    }
+}
+
+/// Result of a branch operation in one of several repositories
+#[derive(Debug, Clone)]
+pub struct RepoBranchResult {
+    pub repo: String,
+    pub result: GitBranchResult,
+}
+
+/// Run a request in the repository of the backend when it is one of
+/// several. Bare repositories get a worktree since they cannot check out a
+/// branch, and `worktree_path` suggests the path for this repository.
+pub fn execute_in_repo<G: GitBackend>(
+    git: &G,
+    request: &GitBranchRequest,
+    worktree_path: impl FnOnce() -> String,
+) -> GitBranchResult {
+    let mut request = request.clone();
+    match git.repo_type() {
+        Ok(GitRepoType::Normal) => {}
+        Ok(GitRepoType::Bare) => request.operation = GitOperation::CreateWorktree,
+        Ok(GitRepoType::NotARepo) => {
+            return GitBranchResult::failure(GitError::NotARepo.to_string(), &request);
+        }
+        Err(e) => return GitBranchResult::failure(e.to_string(), &request),
+    }
+    if request.operation == GitOperation::CreateWorktree {
+        request.worktree_path = worktree_path();
+    }
+    execute_git_operation(git, &request)
+}
+
+/// Run the same request in each repository, e.g. the backend and frontend
+/// of a story, and collect the results in order. `worktree_path` suggests
+/// the worktree path for a repository's directory; relative paths are taken
+/// from that directory.
+pub fn execute_in_repos(
+    repos: &[(String, PathBuf)],
+    request: &GitBranchRequest,
+    worktree_path: impl Fn(&Path) -> String,
+) -> Vec<RepoBranchResult> {
+    repos
+        .iter()
+        .map(|(repo, path)| {
+            let result = if path.is_dir() {
+                execute_in_repo(&GixBackend::at(path), request, || worktree_path(path))
+            } else {
+                GitBranchResult::failure(
+                    format!("Repository directory '{}' does not exist", path.display()),
+                    request,
+                )
+            };
+            RepoBranchResult {
+                repo: repo.clone(),
+                result,
+            }
+        })
+        .collect()
+}
+
+/// One-line summary of a multi-repository operation
+pub fn repos_summary(branch_name: &str, results: &[RepoBranchResult]) -> String {
+    let succeeded = results.iter().filter(|r| r.result.success).count();
+    format!(
+        "Branch '{branch_name}' created in {succeeded} of {} repositories",
+        results.len()
+    )
+}
+
 /// Find the "In Progress" state ID from workflows
 pub fn find_in_progress_state_id(workflows: &[Workflow]) -> Option<i64> {
     workflows
@@ -169,9 +305,11 @@ pub fn default_operation_for_context(context: &GitContext) -> GitOperation {
 mod tests {
     use super::*;
     use crate::api::{
-        CurrentMember, Epic, Member, SearchStoriesResult, Story, Workflow, WorkflowState,
+        CurrentMember, Epic, History, Iteration, Member, SearchStoriesResult, Story, Workflow,
+        WorkflowState,
     };
     use anyhow::Result;
+    use std::cell::RefCell;
 
     // Mock implementation of ShortcutApi for testing
     struct MockShortcutApi {
@@ -283,6 +421,22 @@ mod tests {
         fn add_comment(&self, _story_id: i64, _text: &str) -> Result<()> {
             Ok(())
         }
+
+        fn get_epic_stories(&self, _epic_id: i64) -> Result<Vec<Story>> {
+            Ok(vec![])
+        }
+
+        fn get_iterations(&self) -> Result<Vec<Iteration>> {
+            Ok(vec![])
+        }
+
+        fn get_iteration_stories(&self, _iteration_id: i64) -> Result<Vec<Story>> {
+            Ok(vec![])
+        }
+
+        fn get_story_history(&self, _story_id: i64) -> Result<Vec<History>> {
+            Ok(vec![])
+        }
     }
 
     fn create_test_story() -> Story {
@@ -293,19 +447,9 @@ mod tests {
             workflow_state_id: 1,
             app_url: "https://app.shortcut.com/story/123".to_string(),
             story_type: "feature".to_string(),
-            labels: vec![],
-            owner_ids: vec![],
-            position: 0,
             created_at: "2024-01-01T00:00:00Z".to_string(),
             updated_at: "2024-01-01T00:00:00Z".to_string(),
-            completed_at: None,
-            moved_at: None,
-            comments: vec![],
-            formatted_vcs_branch_name: None,
-            epic_id: None,
-            branches: vec![],
-            pull_requests: vec![],
-            commits: vec![],
+            ..Default::default()
         }
     }
 
@@ -339,6 +483,252 @@ mod tests {
         }]
     }
 
+    // Mock implementation of GitBackend recording the git commands it would run
+    #[derive(Default)]
+    struct MockGitBackend {
+        bare: bool,
+        existing_branches: Vec<String>,
+        failure: Option<String>,
+        calls: RefCell<Vec<String>>,
+    }
+
+    impl MockGitBackend {
+        fn with_branches(names: &[&str]) -> Self {
+            Self {
+                existing_branches: names.iter().map(|n| n.to_string()).collect(),
+                ..Default::default()
+            }
+        }
+
+        fn failing(stderr: &str) -> Self {
+            Self {
+                failure: Some(stderr.to_string()),
+                ..Default::default()
+            }
+        }
+
+        fn calls(&self) -> Vec<String> {
+            self.calls.borrow().clone()
+        }
+
+        fn record(&self, args: String) -> std::result::Result<(), GitError> {
+            self.calls.borrow_mut().push(args.clone());
+            match &self.failure {
+                Some(stderr) => Err(GitError::Command {
+                    args,
+                    stderr: stderr.clone(),
+                }),
+                None => Ok(()),
+            }
+        }
+    }
+
+    impl GitBackend for MockGitBackend {
+        fn repo_type(&self) -> std::result::Result<GitRepoType, GitError> {
+            Ok(if self.bare {
+                GitRepoType::Bare
+            } else {
+                GitRepoType::Normal
+            })
+        }
+
+        fn current_branch(&self) -> std::result::Result<String, GitError> {
+            Ok("main".to_string())
+        }
+
+        fn branch_exists(&self, branch_name: &str) -> std::result::Result<bool, GitError> {
+            Ok(self.existing_branches.iter().any(|b| b == branch_name))
+        }
+
+        fn create_branch(
+            &self,
+            branch_name: &str,
+            base: Option<&str>,
+        ) -> std::result::Result<(), GitError> {
+            self.record(format!(
+                "checkout -b {branch_name} {}",
+                base.unwrap_or("HEAD")
+            ))
+        }
+
+        fn create_worktree(
+            &self,
+            branch_name: &str,
+            worktree_path: &str,
+            base: Option<&str>,
+        ) -> std::result::Result<(), GitError> {
+            self.record(format!(
+                "worktree add -b {branch_name} {worktree_path} {}",
+                base.unwrap_or("HEAD")
+            ))
+        }
+
+        fn checkout_branch(&self, branch_name: &str) -> std::result::Result<(), GitError> {
+            self.record(format!("checkout {branch_name}"))
+        }
+
+        fn add_worktree(
+            &self,
+            branch_name: &str,
+            worktree_path: &str,
+        ) -> std::result::Result<(), GitError> {
+            self.record(format!("worktree add {worktree_path} {branch_name}"))
+        }
+    }
+
+    fn create_request(operation: GitOperation, base: Option<&str>) -> GitBranchRequest {
+        GitBranchRequest {
+            branch_name: "sc-123-test-story".to_string(),
+            worktree_path: "../sc-123-test-story".to_string(),
+            operation,
+            story_id: 123,
+            base: base.map(|b| b.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_execute_create_branch_from_base() {
+        let git = MockGitBackend::default();
+        let request = create_request(GitOperation::CreateBranch, Some("main"));
+
+        let result = execute_git_operation(&git, &request);
+
+        assert!(result.success);
+        assert_eq!(
+            result.message,
+            "Successfully created and switched to branch 'sc-123-test-story'"
+        );
+        assert_eq!(git.calls(), vec!["checkout -b sc-123-test-story main"]);
+    }
+
+    #[test]
+    fn test_execute_create_branch_fails_when_branch_exists() {
+        let git = MockGitBackend::with_branches(&["sc-123-test-story"]);
+        let request = create_request(GitOperation::CreateBranch, None);
+
+        let result = execute_git_operation(&git, &request);
+
+        assert!(!result.success);
+        assert_eq!(result.message, "Branch 'sc-123-test-story' already exists");
+        assert!(git.calls().is_empty());
+    }
+
+    #[test]
+    fn test_execute_create_worktree() {
+        let git = MockGitBackend::default();
+        let request = create_request(GitOperation::CreateWorktree, None);
+
+        let result = execute_git_operation(&git, &request);
+
+        assert!(result.success);
+        assert_eq!(
+            result.worktree_path.as_deref(),
+            Some("../sc-123-test-story")
+        );
+        assert_eq!(
+            git.calls(),
+            vec!["worktree add -b sc-123-test-story ../sc-123-test-story HEAD"]
+        );
+    }
+
+    #[test]
+    fn test_execute_git_operation_reports_backend_errors() {
+        let git = MockGitBackend::failing("fatal: invalid reference: nope");
+        let request = create_request(GitOperation::CreateBranch, Some("nope"));
+
+        let result = execute_git_operation(&git, &request);
+
+        assert!(!result.success);
+        assert!(result.message.contains("invalid reference: nope"));
+    }
+
+    #[test]
+    fn test_execute_checkout_or_create_reuses_existing_branch() {
+        let git = MockGitBackend::with_branches(&["sc-123-test-story"]);
+
+        let result = execute_checkout_or_create(
+            &git,
+            &create_request(GitOperation::CreateBranch, Some("main")),
+        );
+        assert!(result.success);
+        assert_eq!(
+            result.message,
+            "Switched to existing branch 'sc-123-test-story'"
+        );
+
+        let result =
+            execute_checkout_or_create(&git, &create_request(GitOperation::CreateWorktree, None));
+        assert!(result.success);
+
+        assert_eq!(
+            git.calls(),
+            vec![
+                "checkout sc-123-test-story",
+                "worktree add ../sc-123-test-story sc-123-test-story",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_execute_checkout_or_create_creates_missing_branch() {
+        let git = MockGitBackend::default();
+        let request = create_request(GitOperation::CreateBranch, Some("develop"));
+
+        let result = execute_checkout_or_create(&git, &request);
+
+        assert!(result.success);
+        assert_eq!(git.calls(), vec!["checkout -b sc-123-test-story develop"]);
+    }
+
+    #[test]
+    fn test_execute_in_repo_keeps_branch_in_normal_repo() {
+        let git = MockGitBackend::default();
+        let request = create_request(GitOperation::CreateBranch, None);
+
+        let result = execute_in_repo(&git, &request, || unreachable!());
+
+        assert!(result.success);
+        assert_eq!(git.calls(), vec!["checkout -b sc-123-test-story HEAD"]);
+    }
+
+    #[test]
+    fn test_execute_in_repo_uses_worktree_in_bare_repo() {
+        let git = MockGitBackend {
+            bare: true,
+            ..Default::default()
+        };
+        let request = create_request(GitOperation::CreateBranch, Some("main"));
+
+        let result = execute_in_repo(&git, &request, || "../web-123".to_string());
+
+        assert!(result.success);
+        assert_eq!(result.worktree_path.as_deref(), Some("../web-123"));
+        assert_eq!(
+            git.calls(),
+            vec!["worktree add -b sc-123-test-story ../web-123 main"]
+        );
+    }
+
+    #[test]
+    fn test_repos_summary() {
+        let request = create_request(GitOperation::CreateBranch, None);
+        let results = vec![
+            RepoBranchResult {
+                repo: "api".to_string(),
+                result: GitBranchResult::success("ok".to_string(), &request),
+            },
+            RepoBranchResult {
+                repo: "web".to_string(),
+                result: GitBranchResult::failure("exists".to_string(), &request),
+            },
+        ];
+
+        assert_eq!(
+            repos_summary("sc-123-test-story", &results),
+            "Branch 'sc-123-test-story' created in 1 of 2 repositories"
+        );
+    }
+
     #[test]
     fn test_git_branch_result_success() {
         let request = GitBranchRequest {
@@ -346,6 +736,7 @@ mod tests {
             worktree_path: "../feature-test".to_string(),
             operation: GitOperation::CreateBranch,
             story_id: 123,
+            base: None,
         };
 
         let result = GitBranchResult::success("Success!".to_string(), &request);
@@ -363,6 +754,7 @@ mod tests {
             worktree_path: "../feature-test".to_string(),
             operation: GitOperation::CreateWorktree,
             story_id: 123,
+            base: None,
         };
 
         let result = GitBranchResult::success("Success!".to_string(), &request);
@@ -378,6 +770,7 @@ mod tests {
             worktree_path: "../feature-test".to_string(),
             operation: GitOperation::CreateBranch,
             story_id: 123,
+            base: None,
         };
 
         let result = GitBranchResult::failure("Error!".to_string(), &request);
@@ -0,0 +1,458 @@
+use crate::api::{
+    History, HistoryAction, HistoryActionKind, HistoryChange, HistoryChanges, HistoryReference,
+    ShortcutApi,
+};
+use crate::template::format_age;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt::{Display, Write};
+
+/// History requests sent at the same time when fetching several stories
+const CONCURRENT_REQUESTS: usize = 8;
+
+/// Fields Shortcut updates as a side effect of other changes, such as the
+/// timestamps set when a story moves to another state
+const DERIVED_FIELDS: &[&str] = &[
+    "position",
+    "started",
+    "started_at",
+    "completed",
+    "completed_at",
+    "moved_at",
+    "updated_at",
+];
+
+/// A history entry described for people: who changed the story, when, and how
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HistoryEvent {
+    pub changed_at: String,
+    pub member: String,
+    pub changes: Vec<String>,
+}
+
+impl HistoryEvent {
+    /// Time since the change, e.g. "5h ago", or the raw timestamp if it can't be parsed
+    pub fn relative_time(&self, now: DateTime<Utc>) -> String {
+        let age = format_age(&self.changed_at, now);
+        if age.is_empty() {
+            self.changed_at.clone()
+        } else {
+            format!("{age} ago")
+        }
+    }
+}
+
+/// Describe a story's history in the order given, oldest first from the API,
+/// naming members from `members` (member ID to display name). Entries without
+/// any change worth showing are left out.
+pub fn timeline(history: &[History], members: &HashMap<String, String>) -> Vec<HistoryEvent> {
+    history
+        .iter()
+        .filter_map(|entry| {
+            let changes: Vec<String> = entry
+                .actions
+                .iter()
+                .flat_map(|action| describe_action(action, &entry.references, members))
+                .collect();
+            if changes.is_empty() {
+                return None;
+            }
+            let member = entry
+                .member_id
+                .as_deref()
+                .map_or_else(|| "Integration".to_string(), |id| member_name(id, members));
+            Some(HistoryEvent {
+                changed_at: entry.changed_at.clone(),
+                member,
+                changes,
+            })
+        })
+        .collect()
+}
+
+/// Updates of the story itself in the order given, oldest first from the
+/// API, with when they were made. Entries with an unreadable time are skipped.
+pub fn story_changes(history: &[History]) -> Vec<(DateTime<Utc>, &HistoryChanges)> {
+    history
+        .iter()
+        .filter_map(|entry| {
+            let changed_at = DateTime::parse_from_rfc3339(&entry.changed_at).ok()?;
+            Some((changed_at.with_timezone(&Utc), entry))
+        })
+        .flat_map(|(changed_at, entry)| {
+            entry
+                .actions
+                .iter()
+                .filter(|action| {
+                    action.entity_type == "story" && action.action == HistoryActionKind::Update
+                })
+                .map(move |action| (changed_at, &action.changes))
+        })
+        .collect()
+}
+
+/// Plain text timeline: the member and time of each entry, then its changes
+pub fn to_text(events: &[HistoryEvent], now: DateTime<Utc>) -> String {
+    let mut text = String::new();
+    for (index, event) in events.iter().enumerate() {
+        if index > 0 {
+            text.push('\n');
+        }
+        let _ = writeln!(text, "{} · {}", event.member, event.relative_time(now));
+        for change in &event.changes {
+            let _ = writeln!(text, "  - {change}");
+        }
+    }
+    text
+}
+
+fn member_name(id: &str, members: &HashMap<String, String>) -> String {
+    members.get(id).cloned().unwrap_or_else(|| id.to_string())
+}
+
+/// Name of a referenced entity, or "#id" when the history doesn't include it
+fn reference_name(references: &[HistoryReference], entity_type: &str, id: i64) -> String {
+    references
+        .iter()
+        .find(|reference| {
+            reference.entity_type == entity_type
+                && reference.id.as_i64() == Some(id)
+                && !reference.name.is_empty()
+        })
+        .map_or_else(|| format!("#{id}"), |reference| reference.name.clone())
+}
+
+/// First line of a comment or task, shortened to fit on one line
+fn excerpt(text: &str) -> String {
+    const MAX_CHARS: usize = 60;
+    let line = text.lines().next().unwrap_or_default().trim();
+    if line.chars().count() > MAX_CHARS {
+        format!("\"{}…\"", line.chars().take(MAX_CHARS).collect::<String>())
+    } else {
+        format!("\"{line}\"")
+    }
+}
+
+fn describe_action(
+    action: &HistoryAction,
+    references: &[HistoryReference],
+    members: &HashMap<String, String>,
+) -> Vec<String> {
+    use HistoryActionKind::*;
+
+    let line = match (action.entity_type.as_str(), action.action) {
+        ("story", Create) => "created the story".to_string(),
+        ("story", Update) => return describe_story_update(action, references, members),
+        ("story", Delete) => "deleted the story".to_string(),
+        ("story-comment", Create) => match &action.text {
+            Some(text) => format!("commented {}", excerpt(text)),
+            None => "commented".to_string(),
+        },
+        ("story-comment", Update) => "edited a comment".to_string(),
+        ("story-comment", Delete) => "deleted a comment".to_string(),
+        ("story-task", kind) => {
+            let verb = match (kind, action.changes.complete.as_ref().and_then(|c| c.new)) {
+                (Create, _) => "added task",
+                (Update, Some(true)) => "completed task",
+                (Update, Some(false)) => "reopened task",
+                (Delete, _) => "removed task",
+                _ => "edited task",
+            };
+            match &action.description {
+                Some(description) => format!("{verb} {}", excerpt(description)),
+                None => verb.to_string(),
+            }
+        }
+        (entity_type, kind) => {
+            let verb = match kind {
+                Create => "added",
+                Update => "updated",
+                Delete => "removed",
+                Other => "changed",
+            };
+            let entity = entity_type.replace('-', " ");
+            match &action.name {
+                Some(name) => format!("{verb} {entity} {name}"),
+                None => format!("{verb} {entity}"),
+            }
+        }
+    };
+    vec![line]
+}
+
+/// "changed the type from feature to bug", "set the estimate to 3", ...
+fn describe_change<T: Display>(what: &str, change: &HistoryChange<T>) -> String {
+    match (&change.old, &change.new) {
+        (Some(old), Some(new)) => format!("changed {what} from {old} to {new}"),
+        (None, Some(new)) => format!("set {what} to {new}"),
+        (Some(old), None) => format!("cleared {what} (was {old})"),
+        (None, None) => format!("changed {what}"),
+    }
+}
+
+fn describe_story_update(
+    action: &HistoryAction,
+    references: &[HistoryReference],
+    members: &HashMap<String, String>,
+) -> Vec<String> {
+    let changes = &action.changes;
+    let named = |entity_type: &str, change: &HistoryChange<i64>| HistoryChange {
+        old: change
+            .old
+            .map(|id| reference_name(references, entity_type, id)),
+        new: change
+            .new
+            .map(|id| reference_name(references, entity_type, id)),
+    };
+    let mut lines = Vec::new();
+
+    if let Some(state) = &changes.workflow_state_id {
+        let state = named("workflow-state", state);
+        lines.push(match (state.old, state.new) {
+            (Some(old), Some(new)) => format!("moved from {old} to {new}"),
+            (None, Some(new)) => format!("moved to {new}"),
+            _ => "changed the state".to_string(),
+        });
+    }
+    if let Some(owners) = &changes.owner_ids {
+        for id in &owners.adds {
+            lines.push(format!("added owner {}", member_name(id, members)));
+        }
+        for id in &owners.removes {
+            lines.push(format!("removed owner {}", member_name(id, members)));
+        }
+    }
+    if let Some(new) = changes.name.as_ref().and_then(|name| name.new.as_ref()) {
+        lines.push(format!("renamed to \"{new}\""));
+    }
+    if changes.description.is_some() {
+        lines.push("edited the description".to_string());
+    }
+    if let Some(story_type) = &changes.story_type {
+        lines.push(describe_change("the type", story_type));
+    }
+    if let Some(estimate) = &changes.estimate {
+        lines.push(describe_change("the estimate", estimate));
+    }
+    if let Some(epic) = &changes.epic_id {
+        lines.push(describe_change("the epic", &named("epic", epic)));
+    }
+    if let Some(iteration) = &changes.iteration_id {
+        lines.push(describe_change(
+            "the iteration",
+            &named("iteration", iteration),
+        ));
+    }
+    if let Some(labels) = &changes.label_ids {
+        for &id in &labels.adds {
+            lines.push(format!(
+                "added label {}",
+                reference_name(references, "label", id)
+            ));
+        }
+        for &id in &labels.removes {
+            lines.push(format!(
+                "removed label {}",
+                reference_name(references, "label", id)
+            ));
+        }
+    }
+    if let Some(blocked) = &changes.blocked {
+        lines.push(if blocked.new == Some(true) {
+            "marked as blocked".to_string()
+        } else {
+            "unblocked".to_string()
+        });
+    }
+    for field in changes.other.keys() {
+        if !DERIVED_FIELDS.contains(&field.as_str()) {
+            lines.push(format!("changed {}", field.replace('_', " ")));
+        }
+    }
+    lines
+}
+
+/// Fetch the history of each story, a few requests at a time. Returns the
+/// histories by story ID and the IDs whose history could not be fetched.
+pub fn fetch_histories<C: ShortcutApi + Sync>(
+    client: &C,
+    ids: &[i64],
+) -> (HashMap<i64, Vec<History>>, Vec<i64>) {
+    let mut histories = HashMap::new();
+    let mut missing = Vec::new();
+
+    for chunk in ids.chunks(CONCURRENT_REQUESTS) {
+        let results: Vec<_> = std::thread::scope(|scope| {
+            let handles: Vec<_> = chunk
+                .iter()
+                .map(|&id| scope.spawn(move || (id, client.get_story_history(id))))
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().expect("history request thread panicked"))
+                .collect()
+        });
+
+        for (id, result) in results {
+            match result {
+                Ok(history) => {
+                    histories.insert(id, history);
+                }
+                Err(_) => missing.push(id),
+            }
+        }
+    }
+
+    (histories, missing)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    fn history(value: serde_json::Value) -> Vec<History> {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn members() -> HashMap<String, String> {
+        HashMap::from([
+            ("alice".to_string(), "Alice (alice)".to_string()),
+            ("bob".to_string(), "Bob (bob)".to_string()),
+        ])
+    }
+
+    #[test]
+    fn test_state_owner_and_edit_changes() {
+        let history = history(json!([
+            {
+                "id": "a",
+                "changed_at": "2024-01-01T09:00:00Z",
+                "member_id": "alice",
+                "actions": [{"id": 42, "entity_type": "story", "action": "create", "name": "Fix login"}]
+            },
+            {
+                "id": "b",
+                "changed_at": "2024-01-02T09:00:00Z",
+                "member_id": "bob",
+                "actions": [{
+                    "id": 42,
+                    "entity_type": "story",
+                    "action": "update",
+                    "changes": {
+                        "workflow_state_id": {"old": 1, "new": 2},
+                        "owner_ids": {"adds": ["bob"], "removes": ["alice"]},
+                        "name": {"old": "Fix login", "new": "Fix the login page"},
+                        "description": {"old": "", "new": "Details"},
+                        "estimate": {"new": 3},
+                        "started_at": {"new": "2024-01-02T09:00:00Z"},
+                        "position": {"old": 1, "new": 2}
+                    }
+                }],
+                "references": [
+                    {"id": 1, "entity_type": "workflow-state", "name": "Ready", "type": "unstarted"},
+                    {"id": 2, "entity_type": "workflow-state", "name": "In Progress", "type": "started"}
+                ]
+            }
+        ]));
+
+        let events = timeline(&history, &members());
+
+        assert_eq!(
+            events,
+            vec![
+                HistoryEvent {
+                    changed_at: "2024-01-01T09:00:00Z".to_string(),
+                    member: "Alice (alice)".to_string(),
+                    changes: vec!["created the story".to_string()],
+                },
+                HistoryEvent {
+                    changed_at: "2024-01-02T09:00:00Z".to_string(),
+                    member: "Bob (bob)".to_string(),
+                    changes: vec![
+                        "moved from Ready to In Progress".to_string(),
+                        "added owner Bob (bob)".to_string(),
+                        "removed owner Alice (alice)".to_string(),
+                        "renamed to \"Fix the login page\"".to_string(),
+                        "edited the description".to_string(),
+                        "set the estimate to 3".to_string(),
+                    ],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_comments_tasks_and_other_entities() {
+        let history = history(json!([{
+            "id": "a",
+            "changed_at": "2024-01-03T09:00:00Z",
+            "actions": [
+                {"id": 7, "entity_type": "story-comment", "action": "create", "text": "Looks good\nmore text"},
+                {"id": 8, "entity_type": "story-task", "action": "update", "description": "Write tests", "changes": {"complete": {"old": false, "new": true}}},
+                {"id": 9, "entity_type": "branch", "action": "create", "name": "sc-42-fix-login"},
+                {"id": 42, "entity_type": "story", "action": "update", "changes": {"epic_id": {"new": 5}, "label_ids": {"adds": [3]}}}
+            ],
+            "references": [{"id": 5, "entity_type": "epic", "name": "Checkout"}]
+        }]));
+
+        let events = timeline(&history, &members());
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].member, "Integration");
+        assert_eq!(
+            events[0].changes,
+            vec![
+                "commented \"Looks good\"",
+                "completed task \"Write tests\"",
+                "added branch sc-42-fix-login",
+                "set the epic to Checkout",
+                "added label #3",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_entries_without_visible_changes_are_skipped() {
+        let history = history(json!([{
+            "id": "a",
+            "changed_at": "2024-01-03T09:00:00Z",
+            "member_id": "carol",
+            "actions": [{"id": 42, "entity_type": "story", "action": "update", "changes": {"position": {"old": 1, "new": 2}}}]
+        }]));
+
+        assert!(timeline(&history, &members()).is_empty());
+    }
+
+    #[test]
+    fn test_to_text() {
+        let events = vec![
+            HistoryEvent {
+                changed_at: "2024-01-01T09:00:00Z".to_string(),
+                member: "Alice (alice)".to_string(),
+                changes: vec!["created the story".to_string()],
+            },
+            HistoryEvent {
+                changed_at: "2024-01-03T07:00:00Z".to_string(),
+                member: "carol".to_string(),
+                changes: vec!["unblocked".to_string(), "commented \"Done\"".to_string()],
+            },
+        ];
+        let now = Utc.with_ymd_and_hms(2024, 1, 3, 9, 0, 0).unwrap();
+
+        assert_eq!(
+            to_text(&events, now),
+            "Alice (alice) · 2d ago\n  - created the story\n\ncarol · 2h ago\n  - unblocked\n  - commented \"Done\"\n"
+        );
+    }
+
+    #[test]
+    fn test_excerpt_shortens_long_lines() {
+        let long = "a".repeat(70);
+        assert_eq!(excerpt(&long), format!("\"{}…\"", "a".repeat(60)));
+        assert_eq!(excerpt("short\nsecond line"), "\"short\"");
+    }
+}
@@ -0,0 +1,255 @@
+use crate::story_ref::StoryRef;
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The hook runs once the message is final, so it sees `-m` and editor
+/// messages alike and an aborted (empty) commit is never tagged
+pub const HOOK_NAME: &str = "commit-msg";
+
+/// Default format for tagged commit subjects, which Shortcut links to the story
+pub const DEFAULT_TAG_FORMAT: &str = "{subject} [sc-{id}]";
+
+/// Identifies hooks written by sc-cli so others are never overwritten or removed
+const HOOK_MARKER: &str = "# Installed by sc-cli";
+
+/// Subjects git's autosquash and merge machinery depend on
+const UNTAGGED_PREFIXES: [&str; 5] = ["fixup!", "squash!", "amend!", "Merge ", "Revert \""];
+
+/// How the story ID is added to a commit subject, e.g. `{subject} [sc-{id}]`
+/// or `sc-{id}: {subject}`
+#[derive(Debug, Clone, PartialEq)]
+pub struct TagFormat(String);
+
+impl Default for TagFormat {
+    fn default() -> Self {
+        Self(DEFAULT_TAG_FORMAT.to_string())
+    }
+}
+
+impl TagFormat {
+    pub fn parse(format: &str) -> Result<Self> {
+        for placeholder in ["{subject}", "{id}"] {
+            if !format.contains(placeholder) {
+                anyhow::bail!("Commit tag format '{format}' must contain {placeholder}");
+            }
+        }
+        Ok(Self(format.to_string()))
+    }
+
+    /// Add the story tag to the subject of a commit message. Returns `None`
+    /// when the message is left as is: it is empty, already mentions the
+    /// story, or is a fixup, merge or revert.
+    pub fn apply(&self, message: &str, story_id: i64) -> Option<String> {
+        let mut lines: Vec<&str> = message.lines().collect();
+        // `git commit --verbose` appends the diff below a scissors line
+        let end = lines
+            .iter()
+            .position(|line| line.starts_with("# ------------------------ >8"))
+            .unwrap_or(lines.len());
+        let content = || lines[..end].iter().filter(|line| !line.starts_with('#'));
+
+        let subject_index = (0..end)
+            .find(|&index| !lines[index].starts_with('#') && !lines[index].trim().is_empty())?;
+        let subject = lines[subject_index];
+        let mentions_story = content()
+            .flat_map(|line| line.split_whitespace())
+            .any(|word| StoryRef::from_name(word).is_some_and(|story| story.id() == story_id));
+        if mentions_story
+            || UNTAGGED_PREFIXES
+                .iter()
+                .any(|prefix| subject.starts_with(prefix))
+        {
+            return None;
+        }
+
+        let tagged = self
+            .0
+            .replace("{id}", &story_id.to_string())
+            .replace("{subject}", subject.trim_end());
+        lines[subject_index] = &tagged;
+
+        let mut result = lines.join("\n");
+        if message.ends_with('\n') {
+            result.push('\n');
+        }
+        Some(result)
+    }
+}
+
+/// Shell script that passes the message file back to this sc-cli binary. A
+/// failure never blocks the commit.
+pub fn hook_script(executable: &Path, workspace: Option<&str>) -> String {
+    let workspace_arg = workspace
+        .map(|name| format!(" --workspace {}", shell_quote(name)))
+        .unwrap_or_default();
+    let executable = shell_quote(&executable.to_string_lossy());
+    format!(
+        "#!/bin/sh\n\
+         {HOOK_MARKER}: adds the story ID of the current branch to commit messages.\n\
+         # Remove with `sc-cli hooks uninstall`.\n\
+         [ -x {executable} ] || exit 0\n\
+         {executable}{workspace_arg} hooks {HOOK_NAME} \"$1\" || true\n"
+    )
+}
+
+fn shell_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', r"'\''"))
+}
+
+fn is_sc_cli_hook(path: &Path) -> bool {
+    fs::read_to_string(path).is_ok_and(|content| content.contains(HOOK_MARKER))
+}
+
+/// Write the hook into `hooks_dir`. A hook that was not written by sc-cli is
+/// only replaced with `force`.
+pub fn install(hooks_dir: &Path, script: &str, force: bool) -> Result<PathBuf> {
+    let path = hooks_dir.join(HOOK_NAME);
+    if path.exists() && !force && !is_sc_cli_hook(&path) {
+        anyhow::bail!(
+            "A {HOOK_NAME} hook already exists at '{}'. Use --force to replace it",
+            path.display()
+        );
+    }
+
+    fs::create_dir_all(hooks_dir)
+        .with_context(|| format!("Failed to create '{}'", hooks_dir.display()))?;
+    fs::write(&path, script).with_context(|| format!("Failed to write '{}'", path.display()))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755))
+            .with_context(|| format!("Failed to make '{}' executable", path.display()))?;
+    }
+
+    Ok(path)
+}
+
+/// Remove the hook written by sc-cli. Returns `None` when there is no hook
+/// to remove.
+pub fn uninstall(hooks_dir: &Path) -> Result<Option<PathBuf>> {
+    let path = hooks_dir.join(HOOK_NAME);
+    if !path.exists() {
+        return Ok(None);
+    }
+    if !is_sc_cli_hook(&path) {
+        anyhow::bail!(
+            "The {HOOK_NAME} hook at '{}' was not installed by sc-cli, leaving it in place",
+            path.display()
+        );
+    }
+
+    fs::remove_file(&path).with_context(|| format!("Failed to remove '{}'", path.display()))?;
+    Ok(Some(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    fn apply(message: &str) -> Option<String> {
+        TagFormat::default().apply(message, 42)
+    }
+
+    #[test]
+    fn test_apply_tags_subject() {
+        assert_eq!(
+            apply("Fix login\n\nDetails\n").as_deref(),
+            Some("Fix login [sc-42]\n\nDetails\n")
+        );
+        assert_eq!(apply("Fix login").as_deref(), Some("Fix login [sc-42]"));
+    }
+
+    #[test]
+    fn test_apply_skips_comments_before_subject() {
+        let message = "\n# Please enter the commit message\nFix login\n# On branch sc-42-fix\n";
+        assert_eq!(
+            apply(message).as_deref(),
+            Some("\n# Please enter the commit message\nFix login [sc-42]\n# On branch sc-42-fix\n")
+        );
+    }
+
+    #[test]
+    fn test_apply_leaves_empty_messages_alone() {
+        assert_eq!(apply(""), None);
+        assert_eq!(apply("\n# Please enter the commit message\n"), None);
+    }
+
+    #[test]
+    fn test_apply_leaves_tagged_messages_alone() {
+        assert_eq!(apply("Fix login [sc-42]\n"), None);
+        assert_eq!(apply("sc-42: Fix login\n"), None);
+        assert_eq!(apply("Fix login\n\nStory: SC-42\n"), None);
+        assert!(apply("Fix login [sc-420]\n").is_some());
+    }
+
+    #[test]
+    fn test_apply_ignores_verbose_diff() {
+        let message = "Fix login\n# ------------------------ >8 ------------------------\n+sc-42\n";
+        assert_eq!(
+            apply(message).as_deref(),
+            Some(
+                "Fix login [sc-42]\n# ------------------------ >8 ------------------------\n+sc-42\n"
+            )
+        );
+    }
+
+    #[test]
+    fn test_apply_skips_fixups_and_merges() {
+        assert_eq!(apply("fixup! Fix login\n"), None);
+        assert_eq!(apply("Merge branch 'main'\n"), None);
+    }
+
+    #[test]
+    fn test_custom_format() {
+        let format = TagFormat::parse("[sc-{id}] {subject}").unwrap();
+        assert_eq!(
+            format.apply("Fix login\n", 7).as_deref(),
+            Some("[sc-7] Fix login\n")
+        );
+        assert!(TagFormat::parse("[sc-{id}]").is_err());
+        assert!(TagFormat::parse("{subject}").is_err());
+    }
+
+    #[test]
+    fn test_hook_script() {
+        let script = hook_script(Path::new("/usr/bin/sc-cli"), Some("work's"));
+        assert!(script.starts_with("#!/bin/sh\n"));
+        assert!(script.contains(HOOK_MARKER));
+        assert!(script.contains(
+            "'/usr/bin/sc-cli' --workspace 'work'\\''s' hooks commit-msg \"$1\" || true"
+        ));
+    }
+
+    #[test]
+    fn test_install_and_uninstall() {
+        let dir = TempDir::new().unwrap();
+        let hooks_dir = dir.path().join("hooks");
+        let script = hook_script(Path::new("/usr/bin/sc-cli"), None);
+
+        let path = install(&hooks_dir, &script, false).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), script);
+        // Reinstalling over our own hook is fine
+        install(&hooks_dir, &script, false).unwrap();
+
+        assert_eq!(uninstall(&hooks_dir).unwrap(), Some(path));
+        assert_eq!(uninstall(&hooks_dir).unwrap(), None);
+    }
+
+    #[test]
+    fn test_foreign_hooks_are_kept() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(HOOK_NAME);
+        fs::write(&path, "#!/bin/sh\nexit 0\n").unwrap();
+
+        assert!(install(dir.path(), "script", false).is_err());
+        assert!(uninstall(dir.path()).is_err());
+        assert!(path.exists());
+
+        install(dir.path(), "script", true).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "script");
+    }
+}
@@ -1,4 +1,22 @@
 pub mod api;
+pub mod burndown;
+pub mod changelog;
 pub mod config;
+pub mod finish;
 pub mod git;
+pub mod history;
+pub mod hooks;
+pub mod markdown;
+pub mod output;
+pub mod pr;
+pub mod report;
+pub mod standup;
+pub mod start;
+pub mod stats;
+pub mod story_ref;
+pub mod story_view;
+pub mod template;
+pub mod timer;
 pub mod ui;
+pub mod workflow;
+pub mod worktree;
@@ -1,24 +1,49 @@
 mod api;
+mod burndown;
+mod changelog;
 mod config;
+mod finish;
 mod git;
+mod history;
+mod hooks;
+mod markdown;
+mod output;
+mod pr;
+mod report;
+mod standup;
+mod start;
+mod stats;
 mod story_creator;
 mod story_editor;
+mod story_ref;
+mod story_view;
+mod template;
+mod timer;
 mod ui;
+mod workflow;
+mod worktree;
 
 use anyhow::{Context, Result};
 use api::{ShortcutApi, client::ShortcutClient};
 use clap::Parser;
-use config::Config;
-use dialoguer::Input;
+use config::{Config, WorkspaceConfig};
+use dialoguer::{Confirm, Input};
+use output::OutputFormat;
 use crossterm::{
     event::{DisableMouseCapture, EnableMouseCapture},
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
 use ratatui::{Terminal, backend::CrosstermBackend};
-use std::{collections::HashMap, io};
+use std::{
+    collections::HashMap,
+    fs,
+    io::{self, IsTerminal},
+    path::{Path, PathBuf},
+};
 use story_creator::StoryCreator;
 use story_editor::StoryEditor;
+use story_ref::StoryRef;
 use ui::App;
 
 fn validate_story_type(s: &str) -> Result<String, String> {
@@ -30,6 +55,17 @@ fn validate_story_type(s: &str) -> Result<String, String> {
     }
 }
 
+fn validate_story_format(s: &str) -> Result<OutputFormat, String> {
+    match s {
+        "text" => Ok(OutputFormat::Text),
+        "json" => Ok(OutputFormat::Json),
+        "markdown" => Ok(OutputFormat::Markdown),
+        _ => Err(format!(
+            "Invalid story format '{s}'. Must be one of: text, json, markdown"
+        )),
+    }
+}
+
 #[derive(Debug)]
 struct ViewCommandArgs {
     workspace: Option<String>,
@@ -41,6 +77,7 @@ struct ViewCommandArgs {
     all: bool,
     _owner: bool,
     requester: bool,
+    output: OutputFormat,
     debug: bool,
 }
 
@@ -55,9 +92,122 @@ struct ShowCommandArgs {
     all: bool,
     _owner: bool,
     requester: bool,
+    output: OutputFormat,
+    template: Option<String>,
+    no_pager: bool,
+    debug: bool,
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum HooksCommand {
+    /// Install a commit-msg hook that adds the story ID of the current branch to commit messages
+    Install {
+        /// Replace an existing commit-msg hook that was not installed by sc-cli
+        #[arg(long)]
+        force: bool,
+    },
+    /// Remove the commit-msg hook installed by sc-cli
+    Uninstall,
+    /// Add the story ID to a commit message file (run by the installed hook)
+    #[command(name = "commit-msg", hide = true)]
+    CommitMsg { file: PathBuf },
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum TimerCommand {
+    /// Start timing a story, stopping the timer of any other story
+    Start {
+        /// Story ID (e.g., 42, sc-42 or a story URL; detected from the current branch if omitted)
+        story_id: Option<StoryRef>,
+    },
+    /// Stop the running timer
+    Stop {
+        /// Only stop the timer if it is running on this story
+        story_id: Option<StoryRef>,
+    },
+    /// Show the running timer and the time logged on a story
+    Status {
+        /// Story ID (e.g., 42, sc-42 or a story URL; detected from the current branch if omitted)
+        story_id: Option<StoryRef>,
+    },
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum WorktreeCommand {
+    /// List worktrees with the state, owners and age of their stories
+    List {
+        /// Shortcut API token (optional if using workspace)
+        #[arg(short, long)]
+        token: Option<String>,
+    },
+    /// Print the path of a story's worktree, e.g. cd "$(sc-cli worktree open 42)"
+    Open {
+        /// Story ID (e.g., 42, sc-42 or a story URL; detected from the current branch if omitted)
+        story_id: Option<StoryRef>,
+    },
+    /// Forget worktrees whose directories were deleted and, with --done, remove the worktrees of finished stories
+    Prune {
+        /// Remove worktrees whose stories are in a done state
+        #[arg(long)]
+        done: bool,
+
+        /// Show what would be removed without removing anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Remove without asking for confirmation
+        #[arg(short, long)]
+        yes: bool,
+
+        /// Shortcut API token (optional if using workspace)
+        #[arg(short, long)]
+        token: Option<String>,
+    },
+}
+
+#[derive(Debug)]
+struct FinishCommandArgs {
+    workspace: Option<String>,
+    token: Option<String>,
+    story_id: Option<StoryRef>,
+    base: Option<String>,
+    cleanup: bool,
+    assume_yes: bool,
+    debug: bool,
+}
+
+#[derive(Debug)]
+struct BranchCommandArgs {
+    workspace: Option<String>,
+    token: Option<String>,
+    story_id: Option<StoryRef>,
+    use_default: bool,
+    use_worktree: bool,
+    base: Option<String>,
+    stash: bool,
+    push: bool,
+    repos: Vec<String>,
+    debug: bool,
+}
+
+#[derive(Debug)]
+struct StartCommandArgs {
+    workspace: Option<String>,
+    token: Option<String>,
+    story_id: Option<StoryRef>,
+    no_assign: bool,
+    no_move: bool,
+    no_branch: bool,
+    worktree: bool,
+    comment: Option<String>,
+    no_comment: bool,
+    dry_run: bool,
     debug: bool,
 }
 
+/// Comment posted by `start --comment` when no text is given
+const DEFAULT_START_COMMENT: &str = "🚀 Started working on this story";
+
 #[derive(Parser, Debug)]
 #[command(author, version, about = "CLI and TUI client for Shortcut stories", long_about = None)]
 struct Args {
@@ -93,6 +243,10 @@ struct Args {
     #[arg(short, long, global = true)]
     search: Option<String>,
 
+    /// Output format; for view/show anything but text prints all matching stories and exits
+    #[arg(long, global = true, value_enum, default_value = "text")]
+    output: OutputFormat,
+
     #[command(subcommand)]
     command: Option<Command>,
 }
@@ -115,8 +269,20 @@ enum Command {
     },
     /// Mark a story as finished (Done state)
     Finish {
-        /// Story ID to mark as finished (e.g., 42 or sc-42)
-        story_id: String,
+        /// Story ID to mark as finished (e.g., 42, sc-42 or a story URL; detected from the current branch if omitted)
+        story_id: Option<StoryRef>,
+
+        /// Branch the story branch must be merged into before it is deleted (defaults to the workspace's git.base_branch)
+        #[arg(long, value_name = "BRANCH")]
+        base: Option<String>,
+
+        /// Only mark the story as finished, leave local branches and worktrees alone
+        #[arg(long)]
+        no_cleanup: bool,
+
+        /// Clean up the merged branch and worktree without asking
+        #[arg(short, long)]
+        yes: bool,
 
         /// Shortcut API token (optional if using workspace)
         #[arg(short, long)]
@@ -187,11 +353,20 @@ enum Command {
         /// Show stories where user is the requester
         #[arg(long, conflicts_with_all = ["all", "owner"])]
         requester: bool,
+
+        /// Print one line per story using a template (e.g. '{{id}}\t{{state}}\t{{name}}')
+        /// or the name of a template from the workspace config
+        #[arg(long, conflicts_with = "output")]
+        template: Option<String>,
+
+        /// Use the built-in paginator instead of $PAGER / less
+        #[arg(long)]
+        no_pager: bool,
     },
     /// Edit an existing story
     Edit {
-        /// Story ID to edit (e.g., 42 or sc-42)
-        story_id: String,
+        /// Story ID to edit (e.g., 42, sc-42 or a story URL; detected from the current branch if omitted)
+        story_id: Option<StoryRef>,
 
         /// Shortcut API token (optional if using workspace)
         #[arg(short, long)]
@@ -199,8 +374,8 @@ enum Command {
     },
     /// Add a comment to a story
     Comment {
-        /// Story ID to comment on (e.g., 42 or sc-42)
-        story_id: String,
+        /// Story ID to comment on (e.g., 42, sc-42 or a story URL; detected from the current branch if omitted)
+        story_id: Option<StoryRef>,
 
         /// Comment message (will prompt if not provided)
         #[arg(short, long)]
@@ -212,8 +387,8 @@ enum Command {
     },
     /// Create a git branch for a story
     Branch {
-        /// Story ID to create branch for (e.g., 42 or sc-42)
-        story_id: String,
+        /// Story ID to create branch for (e.g., 42, sc-42 or a story URL; detected from the current branch if omitted)
+        story_id: Option<StoryRef>,
 
         /// Use the default branch name without prompting
         #[arg(long)]
@@ -223,8 +398,235 @@ enum Command {
         #[arg(long)]
         worktree: bool,
 
+        /// Ref the new branch starts from (defaults to the workspace's git.base_branch, or the current HEAD)
+        #[arg(long, value_name = "REF")]
+        base: Option<String>,
+
+        /// Stash uncommitted changes before switching to the new branch without asking
+        #[arg(long)]
+        stash: bool,
+
+        /// Push the new branch and set its upstream on the workspace's git.remote (default: origin)
+        #[arg(long)]
+        push: bool,
+
+        /// Create the branch in these repositories from the workspace's git.repos instead of the current one (e.g. api,web)
+        #[arg(long, value_delimiter = ',', value_name = "NAMES")]
+        repos: Vec<String>,
+
+        /// Shortcut API token (optional if using workspace)
+        #[arg(long)]
+        token: Option<String>,
+    },
+    /// Start working on a story: take ownership, move it to started and check out its branch
+    Start {
+        /// Story ID to start (e.g., 42, sc-42 or a story URL; detected from the current branch if omitted)
+        story_id: Option<StoryRef>,
+
+        /// Don't add yourself to the story owners
+        #[arg(long)]
+        no_assign: bool,
+
+        /// Don't move the story to the started state
+        #[arg(long)]
+        no_move: bool,
+
+        /// Don't create or check out a git branch
+        #[arg(long, conflicts_with = "worktree")]
+        no_branch: bool,
+
+        /// Create a worktree instead of checking out the branch
+        #[arg(long)]
+        worktree: bool,
+
+        /// Post a comment on the story (a default text is used when no value is given)
+        #[arg(long, num_args = 0..=1, default_missing_value = DEFAULT_START_COMMENT, conflicts_with = "no_comment")]
+        comment: Option<String>,
+
+        /// Don't post the comment configured for the workspace
+        #[arg(long)]
+        no_comment: bool,
+
+        /// Show what would be done without changing anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Shortcut API token (optional if using workspace)
+        #[arg(short, long)]
+        token: Option<String>,
+    },
+    /// Move one or more stories to another workflow state
+    Move {
+        /// Story IDs followed by the target state name (e.g., 42 sc-43 "in review").
+        /// Omit the state when using --next or --prev, and the IDs to move the
+        /// story detected from the current branch
+        #[arg(value_name = "STORY_ID... STATE")]
+        args: Vec<String>,
+
+        /// Move to the next state in the workflow
+        #[arg(long, conflicts_with = "prev")]
+        next: bool,
+
+        /// Move to the previous state in the workflow
+        #[arg(long, conflicts_with = "next")]
+        prev: bool,
+
+        /// Shortcut API token (optional if using workspace)
+        #[arg(short, long)]
+        token: Option<String>,
+    },
+    /// Print the full details of a single story
+    Story {
+        /// Story ID to display (e.g., 42, sc-42 or a story URL; detected from the current branch if omitted)
+        story_id: Option<StoryRef>,
+
+        /// Output format: text, json or markdown (overrides `--output`)
+        #[arg(long, value_parser = validate_story_format)]
+        format: Option<OutputFormat>,
+
+        /// Open the story in the web browser instead of printing it
+        #[arg(long)]
+        web: bool,
+
+        /// Shortcut API token (optional if using workspace)
+        #[arg(short, long)]
+        token: Option<String>,
+    },
+    /// Show the story detected from the current git branch or worktree directory
+    Current {
+        /// Only print the story ID, without contacting Shortcut
+        #[arg(long)]
+        id_only: bool,
+
+        /// Shortcut API token (optional if using workspace)
+        #[arg(short, long)]
+        token: Option<String>,
+    },
+    /// Manage the git worktrees of your stories
+    Worktree {
+        #[command(subcommand)]
+        command: WorktreeCommand,
+    },
+    /// Generate release notes from the stories referenced by the commits in a git range
+    Changelog {
+        /// Commit range to scan, e.g. v1.2.0..HEAD
+        range: String,
+
+        /// Shortcut API token (optional if using workspace)
+        #[arg(short, long)]
+        token: Option<String>,
+    },
+    /// Manage the git hook that tags commit messages with the story ID
+    Hooks {
+        #[command(subcommand)]
+        command: HooksCommand,
+    },
+    /// Print a pull request description built from a story
+    PrBody {
+        /// Story ID (e.g., 42, sc-42 or a story URL; detected from the current branch if omitted)
+        story_id: Option<StoryRef>,
+
+        /// Only print the pull request title
+        #[arg(long)]
+        title: bool,
+
+        /// Write the description to a file instead of stdout
+        #[arg(short, long)]
+        file: Option<PathBuf>,
+
+        /// Template to render instead of the repository's .sc-cli/pr_template.md
+        #[arg(long)]
+        template: Option<PathBuf>,
+
+        /// Shortcut API token (optional if using workspace)
+        #[arg(short, long)]
+        token: Option<String>,
+    },
+    /// Track the time spent on stories in a local log
+    Timer {
+        #[command(subcommand)]
+        command: TimerCommand,
+    },
+    /// Print the time logged per story or epic (text, markdown or csv with --output)
+    Timesheet {
+        /// Only count the current week (see the `week` settings of the workspace)
+        #[arg(long)]
+        week: bool,
+
+        /// Group the logged time by story or by epic
+        #[arg(long, value_enum, default_value = "story")]
+        by: timer::TimesheetGroup,
+
         /// Shortcut API token (optional if using workspace)
+        #[arg(short, long)]
+        token: Option<String>,
+    },
+    /// Chart the scope, completed and remaining work of an epic or iteration (text, csv or json with --output)
+    Burndown {
+        /// Epic ID or name
+        #[arg(
+            long,
+            conflicts_with = "iteration",
+            required_unless_present = "iteration"
+        )]
+        epic: Option<String>,
+
+        /// Iteration ID, or `current` for the iteration in progress
         #[arg(long)]
+        iteration: Option<String>,
+
+        /// Count story points or stories
+        #[arg(long, value_enum, default_value = "points")]
+        unit: burndown::BurndownUnit,
+
+        /// Shortcut API token (optional if using workspace)
+        #[arg(short, long)]
+        token: Option<String>,
+    },
+    /// Report the stories completed per owner, type and epic (text, markdown or csv with --output)
+    Report {
+        /// Report on whole weeks (see the `week` settings of the workspace)
+        #[arg(long, required = true)]
+        week: bool,
+
+        /// Number of weeks to cover, ending with the current one
+        #[arg(long, value_name = "N", default_value_t = 1, requires = "week")]
+        last: u32,
+
+        /// Shortcut API token (optional if using workspace)
+        #[arg(short, long)]
+        token: Option<String>,
+    },
+    /// Print lead time, cycle time and weekly throughput of completed stories (text or json with --output)
+    Stats {
+        /// Search query for the stories to measure (default: stories completed in the last --weeks weeks)
+        query: Option<String>,
+
+        /// Weeks to look back when no query is given
+        #[arg(long, default_value_t = 12)]
+        weeks: u32,
+
+        /// Break the lead and cycle times down by story type, owner or epic
+        #[arg(long, value_enum, default_value = "type")]
+        by: stats::StatsGroup,
+
+        /// Shortcut API token (optional if using workspace)
+        #[arg(short, long)]
+        token: Option<String>,
+    },
+    /// Show who changed what on a story: state moves, owner changes, edits and comments (text or json with --output)
+    History {
+        /// Story ID (e.g., 42, sc-42 or a story URL; detected from the current branch if omitted)
+        story_id: Option<StoryRef>,
+
+        /// Shortcut API token (optional if using workspace)
+        #[arg(short, long)]
+        token: Option<String>,
+    },
+    /// Summarise your story activity since the previous workday (text, markdown or json with --output)
+    Standup {
+        /// Shortcut API token (optional if using workspace)
+        #[arg(short, long)]
         token: Option<String>,
     },
     /// Display the version of sc-cli
@@ -240,9 +642,21 @@ fn main() -> Result<()> {
             token,
             r#type,
         }) => handle_add_command(args.workspace, token, name, r#type, args.debug),
-        Some(Command::Finish { story_id, token }) => {
-            handle_finish_command(args.workspace, token, story_id, args.debug)
-        }
+        Some(Command::Finish {
+            story_id,
+            base,
+            no_cleanup,
+            yes,
+            token,
+        }) => handle_finish_command(FinishCommandArgs {
+            workspace: args.workspace,
+            token,
+            story_id,
+            base,
+            cleanup: !no_cleanup,
+            assume_yes: yes,
+            debug: args.debug,
+        }),
         Some(Command::View {
             username,
             token,
@@ -262,6 +676,7 @@ fn main() -> Result<()> {
             all: all || args.all,
             _owner: owner || args.owner,
             requester: requester || args.requester,
+            output: args.output,
             debug: args.debug,
         }),
         Some(Command::Show {
@@ -273,6 +688,8 @@ fn main() -> Result<()> {
             all,
             owner,
             requester,
+            template,
+            no_pager,
         }) => handle_show_command(ShowCommandArgs {
             workspace: args.workspace,
             username,
@@ -283,6 +700,9 @@ fn main() -> Result<()> {
             all: all || args.all,
             _owner: owner || args.owner,
             requester: requester || args.requester,
+            output: args.output,
+            template,
+            no_pager,
             debug: args.debug,
         }),
         Some(Command::Edit { story_id, token }) => {
@@ -297,8 +717,152 @@ fn main() -> Result<()> {
             story_id,
             default,
             worktree,
+            base,
+            stash,
+            push,
+            repos,
+            token,
+        }) => handle_branch_command(BranchCommandArgs {
+            workspace: args.workspace,
+            token,
+            story_id,
+            use_default: default,
+            use_worktree: worktree,
+            base,
+            stash,
+            push,
+            repos,
+            debug: args.debug,
+        }),
+        Some(Command::Start {
+            story_id,
+            no_assign,
+            no_move,
+            no_branch,
+            worktree,
+            comment,
+            no_comment,
+            dry_run,
+            token,
+        }) => handle_start_command(StartCommandArgs {
+            workspace: args.workspace,
+            token,
+            story_id,
+            no_assign,
+            no_move,
+            no_branch,
+            worktree,
+            comment,
+            no_comment,
+            dry_run,
+            debug: args.debug,
+        }),
+        Some(Command::Move {
+            args: move_args,
+            next,
+            prev,
+            token,
+        }) => handle_move_command(args.workspace, token, move_args, next, prev, args.debug),
+        Some(Command::Story {
+            story_id,
+            format,
+            web,
+            token,
+        }) => {
+            let format = format.unwrap_or(args.output);
+            handle_story_command(args.workspace, token, story_id, format, web, args.debug)
+        }
+        Some(Command::Current { id_only, token }) => {
+            handle_current_command(args.workspace, token, id_only, args.debug)
+        }
+        Some(Command::Worktree { command }) => match command {
+            WorktreeCommand::List { token } => {
+                handle_worktree_list_command(args.workspace, token, args.debug)
+            }
+            WorktreeCommand::Open { story_id } => handle_worktree_open_command(story_id),
+            WorktreeCommand::Prune {
+                done,
+                dry_run,
+                yes,
+                token,
+            } => handle_worktree_prune_command(args.workspace, token, done, dry_run, yes, args.debug),
+        },
+        Some(Command::Changelog { range, token }) => {
+            handle_changelog_command(args.workspace, token, &range, args.output, args.debug)
+        }
+        Some(Command::Hooks { command }) => match command {
+            HooksCommand::Install { force } => handle_hooks_install_command(args.workspace, force),
+            HooksCommand::Uninstall => handle_hooks_uninstall_command(),
+            HooksCommand::CommitMsg { file } => {
+                handle_hooks_commit_msg_command(args.workspace, &file)
+            }
+        },
+        Some(Command::PrBody {
+            story_id,
+            title,
+            file,
+            template,
+            token,
+        }) => handle_pr_body_command(
+            args.workspace,
+            token,
+            story_id,
+            title,
+            file,
+            template,
+            args.debug,
+        ),
+        Some(Command::Timer { command }) => match command {
+            TimerCommand::Start { story_id } => {
+                handle_timer_start_command(args.workspace, story_id)
+            }
+            TimerCommand::Stop { story_id } => handle_timer_stop_command(args.workspace, story_id),
+            TimerCommand::Status { story_id } => {
+                handle_timer_status_command(args.workspace, story_id)
+            }
+        },
+        Some(Command::Timesheet { week, by, token }) => {
+            handle_timesheet_command(args.workspace, token, week, by, args.output, args.debug)
+        }
+        Some(Command::Burndown {
+            epic,
+            iteration,
+            unit,
+            token,
+        }) => handle_burndown_command(
+            args.workspace,
+            token,
+            epic,
+            iteration,
+            unit,
+            args.output,
+            args.debug,
+        ),
+        Some(Command::Report {
+            week: _,
+            last,
+            token,
+        }) => handle_report_command(args.workspace, token, last, args.output, args.debug),
+        Some(Command::Stats {
+            query,
+            weeks,
+            by,
             token,
-        }) => handle_branch_command(args.workspace, token, story_id, default, worktree, args.debug),
+        }) => handle_stats_command(
+            args.workspace,
+            token,
+            query,
+            weeks,
+            by,
+            args.output,
+            args.debug,
+        ),
+        Some(Command::History { story_id, token }) => {
+            handle_history_command(args.workspace, token, story_id, args.output, args.debug)
+        }
+        Some(Command::Standup { token }) => {
+            handle_standup_command(args.workspace, token, args.output, args.debug)
+        }
         Some(Command::Version) => handle_version_command(),
         None => {
             // Default to view command when no subcommand is specified
@@ -312,62 +876,232 @@ fn main() -> Result<()> {
                 all: args.all,
                 _owner: args.owner,
                 requester: args.requester,
+                output: args.output,
                 debug: args.debug,
             })
         }
     }
 }
 
-fn handle_version_command() -> Result<()> {
-    println!("sc-cli {}", env!("CARGO_PKG_VERSION"));
-    Ok(())
-}
-
-fn handle_add_command(
-    workspace: Option<String>,
-    token: Option<String>,
-    name: Vec<String>,
-    story_type: Option<String>,
-    debug: bool,
-) -> Result<()> {
-    // Get token and user info from args or config
-    // Priority: 1. Explicit workspace, 2. Default workspace (if no token), 3. Token from CLI
-    let (token, _username) = if let Some(workspace_name) = workspace {
-        // Use explicitly specified workspace
+/// Resolve the workspace configuration for commands that accept `--token`
+///
+/// Priority: 1. Explicit workspace, 2. Default workspace (if no token), 3. Token from CLI
+fn resolve_workspace(workspace: Option<String>, token: Option<String>) -> Result<WorkspaceConfig> {
+    if let Some(workspace_name) = workspace {
         let (config, _created) =
             Config::load_or_create(&workspace_name).context("Failed to load or create config")?;
         let workspace = config
             .get_workspace(&workspace_name)
             .context(format!("Failed to get workspace '{workspace_name}'"))?;
-        (workspace.api_key.clone(), workspace.user_id.clone())
-    } else if token.is_none() {
-        // No args provided, try to use default workspace
-        match Config::load() {
-            Ok(config) => {
-                if let Some(default_workspace_name) = config.get_default_workspace() {
-                    let workspace =
-                        config
-                            .get_workspace(&default_workspace_name)
-                            .context(format!(
-                                "Failed to get default workspace '{default_workspace_name}'"
-                            ))?;
-                    (workspace.api_key.clone(), workspace.user_id.clone())
-                } else {
-                    anyhow::bail!(
-                        "No default workspace configured. Use --workspace to specify one or provide --token"
-                    );
-                }
-            }
-            Err(_) => {
-                anyhow::bail!(
-                    "No configuration file found. Use --workspace to create one or provide --token"
-                );
-            }
-        }
-    } else {
-        // Use command line arguments
-        let token = token
-            .ok_or_else(|| anyhow::anyhow!("Either --token or --workspace must be provided"))?;
+        return Ok(workspace.clone());
+    }
+
+    if let Some(token) = token {
+        return Ok(WorkspaceConfig {
+            api_key: token,
+            ..Default::default()
+        });
+    }
+
+    let Ok(config) = Config::load() else {
+        anyhow::bail!(
+            "No configuration file found. Use --workspace to create one or provide --token"
+        );
+    };
+    let Some(default_workspace_name) = config.get_default_workspace() else {
+        anyhow::bail!(
+            "No default workspace configured. Use --workspace to specify one or provide --token"
+        );
+    };
+    let workspace = config
+        .get_workspace(&default_workspace_name)
+        .context(format!(
+            "Failed to get default workspace '{default_workspace_name}'"
+        ))?;
+    Ok(workspace.clone())
+}
+
+/// Use the given story reference, or detect it from the current branch or worktree directory
+fn resolve_story_id(story_ref: Option<StoryRef>) -> Result<i64> {
+    match story_ref.or_else(|| git::detect_current_story().map(|(story_ref, _source)| story_ref)) {
+        Some(story_ref) => Ok(story_ref.id()),
+        None => anyhow::bail!(
+            "A story ID is required: none was given and none could be detected from the current git branch or directory"
+        ),
+    }
+}
+
+/// Build a map of member ID to display name, "Name (mention_name)"
+///
+/// Failures are not fatal: callers fall back to showing raw member IDs.
+fn fetch_member_cache(client: &ShortcutClient, debug: bool) -> HashMap<String, String> {
+    let mut member_cache = HashMap::new();
+    match client.get_members() {
+        Ok(members) => {
+            for member in members {
+                let display_name =
+                    format!("{} ({})", member.profile.name, member.profile.mention_name);
+                member_cache.insert(member.id, display_name);
+            }
+            if debug {
+                eprintln!("Cached {} members", member_cache.len());
+            }
+        }
+        Err(e) => {
+            if debug {
+                eprintln!("WARNING: Failed to fetch members: {e}");
+                eprintln!("Owner names will be displayed as IDs");
+            }
+        }
+    }
+    member_cache
+}
+
+/// Call `f` for every story matching `query`, following pagination without
+/// any interaction and skipping stories repeated across pages
+fn for_each_story(
+    client: &ShortcutClient,
+    query: &str,
+    debug: bool,
+    mut f: impl FnMut(&api::Story) -> Result<()>,
+) -> Result<()> {
+    let mut seen = std::collections::HashSet::new();
+    let mut next_page_token = None;
+
+    loop {
+        let search_result = client
+            .search_stories_page(query, next_page_token)
+            .context("Failed to search stories")?;
+
+        let mut added_count = 0;
+        for story in &search_result.stories {
+            if seen.insert(story.id) {
+                f(story)?;
+                added_count += 1;
+            }
+        }
+
+        next_page_token = search_result.next_page_token;
+        if debug {
+            eprintln!("Fetched {added_count} stories, next_token: {next_page_token:?}");
+        }
+
+        // Stop at the last page, or if a page only repeated stories we already saw
+        if next_page_token.is_none() || added_count == 0 {
+            return Ok(());
+        }
+    }
+}
+
+/// Treat a closed stdout (e.g. output piped into `head`) as success
+fn ignore_broken_pipe(result: Result<()>) -> Result<()> {
+    match result {
+        Err(e)
+            if e.downcast_ref::<io::Error>()
+                .is_some_and(|e| e.kind() == io::ErrorKind::BrokenPipe) =>
+        {
+            Ok(())
+        }
+        other => other,
+    }
+}
+
+/// Stream every story matching `query` to stdout in a machine-readable format
+fn export_stories(
+    client: &ShortcutClient,
+    query: &str,
+    format: OutputFormat,
+    workflows: &[api::Workflow],
+    epics: &[api::Epic],
+    member_cache: &HashMap<String, String>,
+    debug: bool,
+) -> Result<()> {
+    ignore_broken_pipe((|| {
+        let mut writer = output::StoryWriter::new(io::stdout().lock(), format)?;
+        for_each_story(client, query, debug, |story| {
+            writer.write(&output::StoryRecord::new(
+                story,
+                workflows,
+                epics,
+                member_cache,
+            ))
+        })?;
+        let count = writer.finish()?;
+        if debug {
+            eprintln!("Exported {count} stories in total");
+        }
+        Ok(())
+    })())
+}
+
+/// Print one line per story matching `query`, rendered from a user template
+/// with the values `context` builds for each story
+fn print_templated_stories(
+    client: &ShortcutClient,
+    query: &str,
+    template: &template::Template,
+    debug: bool,
+    context: impl Fn(&api::Story) -> Result<HashMap<String, String>>,
+) -> Result<()> {
+    use std::io::Write;
+
+    let mut stdout = io::stdout().lock();
+    ignore_broken_pipe(for_each_story(client, query, debug, |story| {
+        writeln!(stdout, "{}", template.render(&context(story)?)?)?;
+        Ok(())
+    }))
+}
+
+fn handle_version_command() -> Result<()> {
+    println!("sc-cli {}", env!("CARGO_PKG_VERSION"));
+    Ok(())
+}
+
+fn handle_add_command(
+    workspace: Option<String>,
+    token: Option<String>,
+    name: Vec<String>,
+    story_type: Option<String>,
+    debug: bool,
+) -> Result<()> {
+    // Get token and user info from args or config
+    // Priority: 1. Explicit workspace, 2. Default workspace (if no token), 3. Token from CLI
+    let (token, _username) = if let Some(workspace_name) = workspace {
+        // Use explicitly specified workspace
+        let (config, _created) =
+            Config::load_or_create(&workspace_name).context("Failed to load or create config")?;
+        let workspace = config
+            .get_workspace(&workspace_name)
+            .context(format!("Failed to get workspace '{workspace_name}'"))?;
+        (workspace.api_key.clone(), workspace.user_id.clone())
+    } else if token.is_none() {
+        // No args provided, try to use default workspace
+        match Config::load() {
+            Ok(config) => {
+                if let Some(default_workspace_name) = config.get_default_workspace() {
+                    let workspace =
+                        config
+                            .get_workspace(&default_workspace_name)
+                            .context(format!(
+                                "Failed to get default workspace '{default_workspace_name}'"
+                            ))?;
+                    (workspace.api_key.clone(), workspace.user_id.clone())
+                } else {
+                    anyhow::bail!(
+                        "No default workspace configured. Use --workspace to specify one or provide --token"
+                    );
+                }
+            }
+            Err(_) => {
+                anyhow::bail!(
+                    "No configuration file found. Use --workspace to create one or provide --token"
+                );
+            }
+        }
+    } else {
+        // Use command line arguments
+        let token = token
+            .ok_or_else(|| anyhow::anyhow!("Either --token or --workspace must be provided"))?;
         // For add command, we don't need username from CLI, we'll get it from the API
         (token, String::new())
     };
@@ -436,63 +1170,14 @@ fn handle_add_command(
     Ok(())
 }
 
-fn handle_finish_command(
-    workspace: Option<String>,
-    token: Option<String>,
-    story_id: String,
-    debug: bool,
-) -> Result<()> {
-    // Parse story ID - accept both "42" and "sc-42" formats
-    let story_id = if story_id.to_lowercase().starts_with("sc-") {
-        story_id[3..]
-            .parse::<i64>()
-            .context("Invalid story ID format. Expected 'sc-N' where N is a number")?
-    } else {
-        story_id
-            .parse::<i64>()
-            .context("Invalid story ID format. Expected a number or 'sc-N' format")?
-    };
-    // Get token from args or config
-    // Priority: 1. Explicit workspace, 2. Default workspace (if no token), 3. Token from CLI
-    let token = if let Some(workspace_name) = workspace {
-        // Use explicitly specified workspace
-        let (config, _created) =
-            Config::load_or_create(&workspace_name).context("Failed to load or create config")?;
-        let workspace = config
-            .get_workspace(&workspace_name)
-            .context(format!("Failed to get workspace '{workspace_name}'"))?;
-        workspace.api_key.clone()
-    } else if token.is_none() {
-        // No args provided, try to use default workspace
-        match Config::load() {
-            Ok(config) => {
-                if let Some(default_workspace_name) = config.get_default_workspace() {
-                    let workspace =
-                        config
-                            .get_workspace(&default_workspace_name)
-                            .context(format!(
-                                "Failed to get default workspace '{default_workspace_name}'"
-                            ))?;
-                    workspace.api_key.clone()
-                } else {
-                    anyhow::bail!(
-                        "No default workspace configured. Use --workspace to specify one or provide --token"
-                    );
-                }
-            }
-            Err(_) => {
-                anyhow::bail!(
-                    "No configuration file found. Use --workspace to create one or provide --token"
-                );
-            }
-        }
-    } else {
-        // Use command line arguments
-        token.ok_or_else(|| anyhow::anyhow!("Either --token or --workspace must be provided"))?
-    };
+fn handle_finish_command(args: FinishCommandArgs) -> Result<()> {
+    let story_id = resolve_story_id(args.story_id)?;
+    let debug = args.debug;
+    let workspace_config = resolve_workspace(args.workspace, args.token)?;
 
     // Initialize API client
-    let client = ShortcutClient::new(token, debug).context("Failed to create Shortcut client")?;
+    let client = ShortcutClient::new(workspace_config.api_key, debug)
+        .context("Failed to create Shortcut client")?;
 
     // Get current member info for debug/confirmation
     let current_member = client
@@ -523,6 +1208,15 @@ fn handle_finish_command(
                     updated_story.workflow_state_id
                 );
             }
+
+            if args.cleanup {
+                let base = args.base.or(workspace_config.git.base_branch);
+                // The story is already finished, so local cleanup failures are only reported
+                if let Err(e) = cleanup_finished_story(&updated_story, base, args.assume_yes, debug)
+                {
+                    eprintln!("⚠️  Skipped local cleanup: {e}");
+                }
+            }
         }
         Err(e) => {
             eprintln!("❌ Failed to mark story as finished: {e}");
@@ -547,77 +1241,291 @@ fn handle_finish_command(
     Ok(())
 }
 
-fn handle_edit_command(
-    workspace: Option<String>,
-    token: Option<String>,
-    story_id: String,
+/// Warn about open pull requests and offer to remove the story's local branch
+/// and worktree once the branch is merged into `base`
+fn cleanup_finished_story(
+    story: &api::Story,
+    base: Option<String>,
+    assume_yes: bool,
     debug: bool,
 ) -> Result<()> {
-    // Parse story ID - accept both "42" and "sc-42" formats
-    let story_id = if story_id.to_lowercase().starts_with("sc-") {
-        story_id[3..]
-            .parse::<i64>()
-            .context("Invalid story ID format. Expected 'sc-N' where N is a number")?
-    } else {
-        story_id
-            .parse::<i64>()
-            .context("Invalid story ID format. Expected a number or 'sc-N' format")?
-    };
-    // Get token from args or config
-    // Priority: 1. Explicit workspace, 2. Default workspace (if no token), 3. Token from CLI
-    let token = if let Some(workspace_name) = workspace {
-        // Use explicitly specified workspace
-        let (config, _created) =
-            Config::load_or_create(&workspace_name).context("Failed to load or create config")?;
-        let workspace = config
-            .get_workspace(&workspace_name)
-            .context(format!("Failed to get workspace '{workspace_name}'"))?;
-        workspace.api_key.clone()
-    } else if token.is_none() {
-        // No args provided, try to use default workspace
-        match Config::load() {
-            Ok(config) => {
-                if let Some(default_workspace_name) = config.get_default_workspace() {
-                    let workspace =
-                        config
-                            .get_workspace(&default_workspace_name)
-                            .context(format!(
-                                "Failed to get default workspace '{default_workspace_name}'"
-                            ))?;
-                    workspace.api_key.clone()
-                } else {
-                    anyhow::bail!(
-                        "No default workspace configured. Use --workspace to specify one or provide --token"
-                    );
-                }
-            }
-            Err(_) => {
-                anyhow::bail!(
-                    "No configuration file found. Use --workspace to create one or provide --token"
-                );
-            }
+    for pr in finish::open_pull_requests(story) {
+        let number = pr.number.map(|n| format!("#{n} ")).unwrap_or_default();
+        println!("⚠️  Pull request {number}is still open: {}", pr.url);
+    }
+
+    let git_context = git::GitContext::detect().context("Failed to detect git context")?;
+    if !git_context.is_git_repo() {
+        if debug {
+            eprintln!("Not in a git repository, skipping local cleanup");
         }
-    } else {
-        // Use command line arguments
-        token.ok_or_else(|| anyhow::anyhow!("Either --token or --workspace must be provided"))?
-    };
+        return Ok(());
+    }
 
-    // Initialize API client
-    let client = ShortcutClient::new(token, debug).context("Failed to create Shortcut client")?;
+    let local_branches = git::list_local_branches()?;
+    let Some(branch) = finish::find_story_branch(story, &local_branches) else {
+        if debug {
+            eprintln!("No local branch found for story #{}", story.id);
+        }
+        return Ok(());
+    };
 
-    if debug {
-        eprintln!("Fetching story #{story_id} for editing...");
+    let base = base.unwrap_or_else(git::default_base_branch);
+    let merged_locally = git::is_branch_merged(&branch, &base).unwrap_or_else(|e| {
+        eprintln!("⚠️  {e}");
+        false
+    });
+    // Squash and rebase merges are only visible through the pull request
+    let merged_by_pr = !merged_locally && finish::merged_by_pull_request(story);
+    if !merged_locally && !merged_by_pr {
+        println!("🌿 Keeping branch '{branch}': it is not merged into '{base}'");
+        return Ok(());
+    }
+    // The pull request only vouches for what was pushed to it; commits made
+    // after that are left for `git branch -d` to protect
+    let force_delete = merged_by_pr
+        && git::is_branch_pushed(Path::new("."), &branch).unwrap_or_else(|e| {
+            eprintln!("⚠️  {e}");
+            false
+        });
+    if merged_by_pr && !force_delete {
+        println!(
+            "⚠️  Branch '{branch}' has commits that are not on its upstream, so its merged pull request may not include them"
+        );
     }
 
-    // Fetch the story to edit
-    let story = client
-        .get_story(story_id)
-        .context(format!("Failed to fetch story #{story_id}"))?;
+    let worktrees = git::list_worktrees().unwrap_or_default();
+    let worktree = finish::find_story_worktree(&branch, &worktrees);
+    let current_branch = git::get_current_branch().ok();
+    let steps = finish::plan_cleanup(
+        &branch,
+        worktree,
+        current_branch.as_deref(),
+        &base,
+        force_delete,
+    );
+
+    let interactive = io::stdin().is_terminal();
+    let mut dir = PathBuf::from(".");
+    for step in &steps {
+        let confirmed = assume_yes
+            || (interactive
+                && Confirm::new()
+                    .with_prompt(step.prompt())
+                    .default(true)
+                    .interact()?);
+        if !confirmed {
+            if !interactive {
+                println!("💡 Branch '{branch}' is merged into '{base}'. Run with --yes to remove it.");
+            }
+            break;
+        }
 
-    if debug {
-        eprintln!("Found story: {} - {}", story.id, story.name);
-        eprintln!("Current type: {}", story.story_type);
-        eprintln!("Description length: {} chars", story.description.len());
+        // Run git from the main worktree when the current one is removed, so
+        // the steps that follow do not run in a deleted directory
+        if let finish::CleanupStep::RemoveWorktree(path) = step
+            && let Some(main) = worktrees.first()
+            && std::env::current_dir().is_ok_and(|cwd| cwd.starts_with(path))
+        {
+            dir = main.path.clone();
+        }
+
+        match step.run(&dir) {
+            Ok(message) => println!("🧹 {message}"),
+            Err(e) => {
+                eprintln!("❌ {e}");
+                break;
+            }
+        }
+    }
+    if dir != Path::new(".") {
+        println!(
+            "💡 The current worktree was removed. Run: cd {}",
+            dir.display()
+        );
+    }
+
+    Ok(())
+}
+
+/// Where `sc-cli move` should send each story
+enum MoveTarget {
+    State(String),
+    Step(workflow::StateStep),
+}
+
+fn handle_move_command(
+    workspace: Option<String>,
+    token: Option<String>,
+    mut move_args: Vec<String>,
+    next: bool,
+    prev: bool,
+    debug: bool,
+) -> Result<()> {
+    // Without --next/--prev the last positional argument is the target state
+    let target = if next {
+        MoveTarget::Step(workflow::StateStep::Next)
+    } else if prev {
+        MoveTarget::Step(workflow::StateStep::Prev)
+    } else if move_args.len() >= 2
+        || move_args
+            .first()
+            .is_some_and(|arg| StoryRef::parse(arg).is_err())
+    {
+        MoveTarget::State(move_args.pop().unwrap_or_default())
+    } else {
+        anyhow::bail!(
+            "Missing target state. Usage: sc-cli move <STORY_ID>... <STATE> (or use --next/--prev)"
+        );
+    };
+
+    let mut story_ids = move_args
+        .iter()
+        .map(|id| StoryRef::parse(id).map(StoryRef::id))
+        .collect::<Result<Vec<_>>>()?;
+    if story_ids.is_empty() {
+        story_ids.push(resolve_story_id(None)?);
+    }
+
+    let workspace_config = resolve_workspace(workspace, token)?;
+    let client = ShortcutClient::new(workspace_config.api_key, debug)
+        .context("Failed to create Shortcut client")?;
+
+    let workflows = client
+        .get_workflows()
+        .context("Failed to fetch workflows")?;
+
+    let mut failed = 0;
+    for &story_id in &story_ids {
+        if let Err(e) = move_story(&client, &workflows, story_id, &target, debug) {
+            eprintln!("❌ #{story_id}: {e}");
+            failed += 1;
+        }
+    }
+
+    if failed > 0 {
+        anyhow::bail!("Failed to move {failed} of {} stories", story_ids.len());
+    }
+
+    Ok(())
+}
+
+fn move_story(
+    client: &ShortcutClient,
+    workflows: &[api::Workflow],
+    story_id: i64,
+    target: &MoveTarget,
+    debug: bool,
+) -> Result<()> {
+    let story = client
+        .get_story(story_id)
+        .context(format!("Failed to fetch story #{story_id}"))?;
+
+    let target_state = match target {
+        MoveTarget::State(query) => {
+            workflow::resolve_state(workflows, Some(story.workflow_state_id), query)?
+        }
+        MoveTarget::Step(step) => {
+            let edge = match step {
+                workflow::StateStep::Next => "last",
+                workflow::StateStep::Prev => "first",
+            };
+            workflow::step_state(workflows, story.workflow_state_id, *step).ok_or_else(|| {
+                anyhow::anyhow!("Story is already in the {edge} state of its workflow")
+            })?
+        }
+    };
+
+    let current_name = workflow::find_state(workflows, story.workflow_state_id)
+        .map(|s| s.name.clone())
+        .unwrap_or_else(|| story.workflow_state_id.to_string());
+
+    if target_state.id == story.workflow_state_id {
+        println!(
+            "ℹ️  #{} - {} is already in {}",
+            story.id, story.name, current_name
+        );
+        return Ok(());
+    }
+
+    if debug {
+        eprintln!(
+            "Moving story #{story_id} from state {} to {} ({})",
+            story.workflow_state_id, target_state.id, target_state.name
+        );
+    }
+
+    let updated_story = client.update_story_state(story_id, target_state.id)?;
+    println!(
+        "✅ #{} - {}: {} → {}",
+        updated_story.id, updated_story.name, current_name, target_state.name
+    );
+
+    Ok(())
+}
+
+fn handle_edit_command(
+    workspace: Option<String>,
+    token: Option<String>,
+    story_id: Option<StoryRef>,
+    debug: bool,
+) -> Result<()> {
+    let story_id = resolve_story_id(story_id)?;
+    // Get token from args or config
+    // Priority: 1. Explicit workspace, 2. Default workspace (if no token), 3. Token from CLI
+    let token = if let Some(workspace_name) = workspace {
+        // Use explicitly specified workspace
+        let (config, _created) =
+            Config::load_or_create(&workspace_name).context("Failed to load or create config")?;
+        let workspace = config
+            .get_workspace(&workspace_name)
+            .context(format!("Failed to get workspace '{workspace_name}'"))?;
+        workspace.api_key.clone()
+    } else if token.is_none() {
+        // No args provided, try to use default workspace
+        match Config::load() {
+            Ok(config) => {
+                if let Some(default_workspace_name) = config.get_default_workspace() {
+                    let workspace =
+                        config
+                            .get_workspace(&default_workspace_name)
+                            .context(format!(
+                                "Failed to get default workspace '{default_workspace_name}'"
+                            ))?;
+                    workspace.api_key.clone()
+                } else {
+                    anyhow::bail!(
+                        "No default workspace configured. Use --workspace to specify one or provide --token"
+                    );
+                }
+            }
+            Err(_) => {
+                anyhow::bail!(
+                    "No configuration file found. Use --workspace to create one or provide --token"
+                );
+            }
+        }
+    } else {
+        // Use command line arguments
+        token.ok_or_else(|| anyhow::anyhow!("Either --token or --workspace must be provided"))?
+    };
+
+    // Initialize API client
+    let client = ShortcutClient::new(token, debug).context("Failed to create Shortcut client")?;
+
+    if debug {
+        eprintln!("Fetching story #{story_id} for editing...");
+    }
+
+    // Fetch the story to edit
+    let story = client
+        .get_story(story_id)
+        .context(format!("Failed to fetch story #{story_id}"))?;
+
+    if debug {
+        eprintln!("Found story: {} - {}", story.id, story.name);
+        eprintln!("Current type: {}", story.story_type);
+        eprintln!("Description length: {} chars", story.description.len());
     }
 
     // Create a story editor with the current story
@@ -658,16 +1566,1012 @@ fn handle_edit_command(
         );
     }
 
-    // Update the story
-    let updated_story = story_editor
-        .update(&client)
-        .context("Failed to update story")?;
+    // Update the story
+    let updated_story = story_editor
+        .update(&client)
+        .context("Failed to update story")?;
+
+    println!("\n✅ Story updated successfully!");
+    println!("  ID: #{}", updated_story.id);
+    println!("  Name: {}", updated_story.name);
+    println!("  Type: {}", updated_story.story_type);
+    println!("  URL: {}", updated_story.app_url);
+
+    Ok(())
+}
+
+fn handle_story_command(
+    workspace: Option<String>,
+    token: Option<String>,
+    story_id: Option<StoryRef>,
+    format: OutputFormat,
+    web: bool,
+    debug: bool,
+) -> Result<()> {
+    if !matches!(
+        format,
+        OutputFormat::Text | OutputFormat::Json | OutputFormat::Markdown
+    ) {
+        anyhow::bail!("A story can only be printed as text, json or markdown");
+    }
+
+    let story_id = resolve_story_id(story_id)?;
+    let workspace_config = resolve_workspace(workspace, token)?;
+    let client = ShortcutClient::new(workspace_config.api_key, debug)
+        .context("Failed to create Shortcut client")?;
+
+    if debug {
+        eprintln!("Fetching story #{story_id}...");
+    }
+    let story = client
+        .get_story(story_id)
+        .context(format!("Failed to fetch story #{story_id}"))?;
+
+    if web {
+        open::that(&story.app_url).context("Failed to open story in browser")?;
+        println!("🌐 Opened {} in your browser", story.app_url);
+        return Ok(());
+    }
+
+    let workflows = client
+        .get_workflows()
+        .context("Failed to fetch workflows")?;
+
+    // Member and epic names are nice to have; fall back to IDs when unavailable
+    let member_cache = fetch_member_cache(&client, debug);
+    let epics = if story.epic_id.is_some() {
+        client.get_epics().unwrap_or_else(|e| {
+            if debug {
+                eprintln!("WARNING: Failed to fetch epics: {e}");
+            }
+            Vec::new()
+        })
+    } else {
+        Vec::new()
+    };
+
+    let details = story_view::StoryDetails::new(&story, &workflows, &epics, &member_cache);
+    print!("{}", details.render(format)?);
+    if format == OutputFormat::Json {
+        println!();
+    }
+
+    Ok(())
+}
+
+/// Fetch the stories referenced by worktrees, skipping the ones that cannot be loaded
+fn fetch_worktree_stories(
+    client: &ShortcutClient,
+    entries: &[worktree::StoryWorktree],
+    debug: bool,
+) -> HashMap<i64, api::Story> {
+    let mut stories = HashMap::new();
+    for story_id in entries.iter().filter_map(|e| e.story_id) {
+        if stories.contains_key(&story_id) {
+            continue;
+        }
+        match client.get_story(story_id) {
+            Ok(story) => {
+                stories.insert(story_id, story);
+            }
+            Err(e) => {
+                if debug {
+                    eprintln!("Failed to fetch story #{story_id}: {e}");
+                }
+            }
+        }
+    }
+    stories
+}
+
+fn load_story_worktrees() -> Result<Vec<worktree::StoryWorktree>> {
+    let git_context = git::GitContext::detect().context("Failed to detect git context")?;
+    if !git_context.is_git_repo() {
+        anyhow::bail!("Not a git repository. Please run this command from within a git repository.");
+    }
+    Ok(worktree::story_worktrees(&git::list_worktrees()?))
+}
+
+fn handle_worktree_list_command(
+    workspace: Option<String>,
+    token: Option<String>,
+    debug: bool,
+) -> Result<()> {
+    let entries = load_story_worktrees()?;
+
+    let workspace_config = resolve_workspace(workspace, token)?;
+    let client = ShortcutClient::new(workspace_config.api_key, debug)
+        .context("Failed to create Shortcut client")?;
+    let workflows = client
+        .get_workflows()
+        .context("Failed to fetch workflows")?;
+    let member_cache = fetch_member_cache(&client, debug);
+    let stories = fetch_worktree_stories(&client, &entries, debug);
+    let now = chrono::Utc::now();
+
+    let path_width = entries
+        .iter()
+        .map(|e| e.worktree.path.display().to_string().len())
+        .max()
+        .unwrap_or(0);
+    let branch_width = entries.iter().map(|e| e.branch().len()).max().unwrap_or(0);
+
+    println!("\x1b[1;36m🌳 Worktrees\x1b[0m");
+    for entry in &entries {
+        let path = entry.worktree.path.display().to_string();
+        let story_info = match entry.story_id {
+            Some(story_id) => match stories.get(&story_id) {
+                Some(story) => {
+                    let state = workflow::find_state(&workflows, story.workflow_state_id)
+                        .map(|s| s.name.clone())
+                        .unwrap_or_else(|| story.workflow_state_id.to_string());
+                    let owners = if story.owner_ids.is_empty() {
+                        "Unassigned".to_string()
+                    } else {
+                        story
+                            .owner_ids
+                            .iter()
+                            .map(|id| member_cache.get(id).cloned().unwrap_or_else(|| id.clone()))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    };
+                    format!(
+                        "#{} \x1b[35m{}\x1b[0m | 👤 {} | ⏱ {} | {}",
+                        story.id,
+                        state,
+                        owners,
+                        template::format_age(&story.created_at, now),
+                        story.name
+                    )
+                }
+                None => format!("#{story_id} \x1b[31m(story not found)\x1b[0m"),
+            },
+            None => "\x1b[90m(no story)\x1b[0m".to_string(),
+        };
+        let main_marker = if entry.main { " *" } else { "  " };
+        println!(
+            "{main_marker}{path:<path_width$}  \x1b[32m{:<branch_width$}\x1b[0m  {story_info}",
+            entry.branch()
+        );
+    }
+
+    Ok(())
+}
+
+fn handle_worktree_open_command(story_id: Option<StoryRef>) -> Result<()> {
+    let story_id = resolve_story_id(story_id)?;
+    let entries = load_story_worktrees()?;
+    let Some(entry) = entries
+        .iter()
+        .find(|e| !e.main && e.story_id == Some(story_id))
+    else {
+        anyhow::bail!("No worktree found for story #{story_id}");
+    };
+
+    println!("{}", entry.worktree.path.display());
+    Ok(())
+}
+
+fn handle_worktree_prune_command(
+    workspace: Option<String>,
+    token: Option<String>,
+    done: bool,
+    dry_run: bool,
+    assume_yes: bool,
+    debug: bool,
+) -> Result<()> {
+    let entries = load_story_worktrees()?;
+
+    if !dry_run {
+        git::prune_worktrees()?;
+        println!("🧹 Pruned records of deleted worktrees");
+    }
+    if !done {
+        return Ok(());
+    }
+
+    let workspace_config = resolve_workspace(workspace, token)?;
+    let client = ShortcutClient::new(workspace_config.api_key, debug)
+        .context("Failed to create Shortcut client")?;
+    let workflows = client
+        .get_workflows()
+        .context("Failed to fetch workflows")?;
+    let stories = fetch_worktree_stories(&client, &entries, debug);
+    let done_entries = worktree::done_worktrees(&entries, &stories, &workflows);
+
+    if done_entries.is_empty() {
+        println!("No worktrees of finished stories found.");
+        return Ok(());
+    }
+
+    println!("Worktrees of finished stories:");
+    for entry in &done_entries {
+        let name = entry
+            .story_id
+            .and_then(|id| stories.get(&id))
+            .map(|story| story.name.as_str())
+            .unwrap_or_default();
+        println!(
+            "  {} ({}) - #{} {}",
+            entry.worktree.path.display(),
+            entry.branch(),
+            entry.story_id.unwrap_or_default(),
+            name
+        );
+    }
+
+    if dry_run {
+        return Ok(());
+    }
+
+    if !assume_yes {
+        if !io::stdin().is_terminal() {
+            anyhow::bail!("Refusing to remove worktrees without confirmation. Run with --yes.");
+        }
+        let confirmed = Confirm::new()
+            .with_prompt(format!("Remove {} worktree(s)?", done_entries.len()))
+            .default(false)
+            .interact()?;
+        if !confirmed {
+            return Ok(());
+        }
+    }
+
+    let main = git::main_worktree()?;
+    let cwd = std::env::current_dir().ok();
+    let mut failed = 0;
+    for entry in &done_entries {
+        // Removing the worktree the command runs in would delete the shell's directory
+        if cwd
+            .as_ref()
+            .is_some_and(|cwd| cwd.starts_with(&entry.worktree.path))
+        {
+            println!(
+                "⏭️  Keeping {}: it is the current directory",
+                entry.worktree.path.display()
+            );
+            continue;
+        }
+        match git::remove_worktree(&main.path, &entry.worktree.path) {
+            Ok(()) => println!("🧹 Removed {}", entry.worktree.path.display()),
+            Err(e) => {
+                // Usually uncommitted changes; leave those for the user to check
+                eprintln!("❌ {e}");
+                failed += 1;
+            }
+        }
+    }
+
+    if failed > 0 {
+        anyhow::bail!("Failed to remove {failed} of {} worktrees", done_entries.len());
+    }
+
+    Ok(())
+}
+
+fn handle_changelog_command(
+    workspace: Option<String>,
+    token: Option<String>,
+    range: &str,
+    format: OutputFormat,
+    debug: bool,
+) -> Result<()> {
+    if !matches!(
+        format,
+        OutputFormat::Text | OutputFormat::Markdown | OutputFormat::Json
+    ) {
+        anyhow::bail!("The changelog can only be printed as markdown (the default) or json");
+    }
+
+    let git_context = git::GitContext::detect().context("Failed to detect git context")?;
+    if !git_context.is_git_repo() {
+        anyhow::bail!("Not a git repository. Please run this command from within a git repository.");
+    }
+
+    let entries = git::log_range(range)?;
+    let scanned = changelog::scan_commits(&entries, |merge| {
+        git::merged_commits(merge).unwrap_or_else(|e| {
+            if debug {
+                eprintln!("Failed to list the commits of merge {}: {e}", merge.short_hash());
+            }
+            Vec::new()
+        })
+    });
+
+    let workspace_config = resolve_workspace(workspace, token)?;
+    let client = ShortcutClient::new(workspace_config.api_key, debug)
+        .context("Failed to create Shortcut client")?;
+
+    let ids: Vec<i64> = scanned.by_story.keys().copied().collect();
+    let (stories, missing) = changelog::fetch_stories(&client, &ids);
+    if debug && !missing.is_empty() {
+        eprintln!("Failed to fetch stories: {missing:?}");
+    }
+    let epics = if stories.values().any(|story| story.epic_id.is_some()) {
+        client.get_epics().unwrap_or_else(|e| {
+            if debug {
+                eprintln!("Failed to fetch epics: {e}");
+            }
+            Vec::new()
+        })
+    } else {
+        Vec::new()
+    };
+
+    let changelog = changelog::Changelog::new(range, &scanned, &stories, &epics);
+    if format == OutputFormat::Json {
+        println!("{}", serde_json::to_string_pretty(&changelog)?);
+    } else {
+        print!("{}", changelog.to_markdown());
+    }
+    Ok(())
+}
+
+fn handle_pr_body_command(
+    workspace: Option<String>,
+    token: Option<String>,
+    story_id: Option<StoryRef>,
+    title_only: bool,
+    file: Option<PathBuf>,
+    template_path: Option<PathBuf>,
+    debug: bool,
+) -> Result<()> {
+    let story_id = resolve_story_id(story_id)?;
+    let workspace_config = resolve_workspace(workspace, token)?;
+    let naming = git::naming::BranchNaming::new(&workspace_config.git, &workspace_config.user_id)
+        .context("Invalid branch naming settings in the workspace config")?;
+    let client = ShortcutClient::new(workspace_config.api_key, debug)
+        .context("Failed to create Shortcut client")?;
+
+    if debug {
+        eprintln!("Fetching story #{story_id}...");
+    }
+    let story = client
+        .get_story(story_id)
+        .context(format!("Failed to fetch story #{story_id}"))?;
+    let body = pr::PrBody::new(&story);
+
+    // A template given on the command line must exist; the repository one is optional
+    let template_path = template_path.or_else(|| {
+        git::get_toplevel_dir()
+            .ok()
+            .map(|dir| dir.join(pr::TEMPLATE_PATH))
+            .filter(|path| path.is_file())
+    });
+
+    let output = if title_only {
+        format!("{}\n", body.title)
+    } else if let Some(path) = template_path {
+        if debug {
+            eprintln!("Rendering template {}", path.display());
+        }
+        let source = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read template '{}'", path.display()))?;
+        let template = template::Template::parse_file(&source)
+            .with_context(|| format!("Invalid template '{}'", path.display()))?;
+
+        let workflows = client
+            .get_workflows()
+            .context("Failed to fetch workflows")?;
+        let member_cache = fetch_member_cache(&client, debug);
+        let epics = if story.epic_id.is_some() {
+            client.get_epics().unwrap_or_else(|e| {
+                if debug {
+                    eprintln!("WARNING: Failed to fetch epics: {e}");
+                }
+                Vec::new()
+            })
+        } else {
+            Vec::new()
+        };
+
+        let mut context = template::story_context(
+            &story,
+            &workflows,
+            &epics,
+            &member_cache,
+            &naming,
+            chrono::Utc::now(),
+        )?;
+        body.extend_context(&mut context);
+        let mut rendered = template.render(&context)?;
+        if !rendered.ends_with('\n') {
+            rendered.push('\n');
+        }
+        rendered
+    } else {
+        body.to_markdown()
+    };
+
+    match file {
+        Some(path) => {
+            fs::write(&path, output)
+                .with_context(|| format!("Failed to write '{}'", path.display()))?;
+            eprintln!(
+                "✅ Wrote the pull request description to {}",
+                path.display()
+            );
+        }
+        None => print!("{output}"),
+    }
+
+    Ok(())
+}
+
+/// Commit tag format of the workspace, or the default when there is no config
+fn commit_tag_format(workspace: Option<&str>) -> Result<hooks::TagFormat> {
+    let format = Config::load().ok().and_then(|config| {
+        let name = workspace
+            .map(str::to_string)
+            .or_else(|| config.get_default_workspace())?;
+        config.get_workspace(&name).ok()?.git.commit_tag_format.clone()
+    });
+    match format {
+        Some(format) => hooks::TagFormat::parse(&format).context("Invalid git.commit_tag_format"),
+        None => Ok(hooks::TagFormat::default()),
+    }
+}
+
+fn handle_hooks_install_command(workspace: Option<String>, force: bool) -> Result<()> {
+    let git_context = git::GitContext::detect().context("Failed to detect git context")?;
+    if !git_context.is_git_repo() {
+        anyhow::bail!("Not a git repository. Please run this command from within a git repository.");
+    }
+
+    let format = commit_tag_format(workspace.as_deref())?;
+    let executable = std::env::current_exe().context("Failed to find the sc-cli executable")?;
+    let script = hooks::hook_script(&executable, workspace.as_deref());
+    let path = hooks::install(&git::hooks_dir()?, &script, force)?;
+
+    println!("✅ Installed the {} hook at {}", hooks::HOOK_NAME, path.display());
+    if let Some(example) = format.apply("Fix login", 42) {
+        println!("   Commits on sc-42 branches will read: {example}");
+    }
+    Ok(())
+}
+
+fn handle_hooks_uninstall_command() -> Result<()> {
+    let git_context = git::GitContext::detect().context("Failed to detect git context")?;
+    if !git_context.is_git_repo() {
+        anyhow::bail!("Not a git repository. Please run this command from within a git repository.");
+    }
+
+    match hooks::uninstall(&git::hooks_dir()?)? {
+        Some(path) => println!("🗑️  Removed the {} hook at {}", hooks::HOOK_NAME, path.display()),
+        None => println!("ℹ️  No {} hook is installed", hooks::HOOK_NAME),
+    }
+    Ok(())
+}
+
+fn handle_hooks_commit_msg_command(workspace: Option<String>, file: &Path) -> Result<()> {
+    let Some((story_ref, _source)) = git::detect_current_story() else {
+        return Ok(());
+    };
+
+    let format = commit_tag_format(workspace.as_deref())?;
+    let message = fs::read_to_string(file)
+        .with_context(|| format!("Failed to read commit message '{}'", file.display()))?;
+    if let Some(tagged) = format.apply(&message, story_ref.id()) {
+        fs::write(file, tagged)
+            .with_context(|| format!("Failed to write commit message '{}'", file.display()))?;
+    }
+    Ok(())
+}
+
+/// The time log of the given workspace, or of the default one
+fn time_log(workspace: Option<&str>) -> Result<timer::TimeLog> {
+    let name = workspace
+        .map(str::to_string)
+        .or_else(|| Config::load().ok()?.get_default_workspace())
+        .unwrap_or_else(|| "default".to_string());
+    timer::TimeLog::for_workspace(&name)
+}
+
+/// Week settings of the named or default workspace, for commands that work
+/// without a configuration file
+fn week_config(workspace: Option<&str>) -> config::WeekConfig {
+    Config::load()
+        .ok()
+        .and_then(|config| {
+            let name = workspace
+                .map(str::to_string)
+                .or_else(|| config.get_default_workspace())?;
+            Some(config.get_workspace(&name).ok()?.week.clone())
+        })
+        .unwrap_or_default()
+}
+
+fn handle_timer_start_command(workspace: Option<String>, story_id: Option<StoryRef>) -> Result<()> {
+    let story_id = resolve_story_id(story_id)?;
+    let log = time_log(workspace.as_deref())?;
+    if log
+        .running()?
+        .is_some_and(|event| event.story_id == story_id)
+    {
+        println!("⏱  The timer is already running on story #{story_id}");
+        return Ok(());
+    }
+    if let Some(stopped) = log.start(story_id, chrono::Utc::now())? {
+        println!(
+            "⏹  Stopped story #{} after {}",
+            stopped.story_id,
+            timer::format_duration(stopped.duration())
+        );
+    }
+    println!("⏱  Started the timer on story #{story_id}");
+    Ok(())
+}
+
+fn handle_timer_stop_command(workspace: Option<String>, story_id: Option<StoryRef>) -> Result<()> {
+    let log = time_log(workspace.as_deref())?;
+    let Some(running) = log.running()? else {
+        println!("No timer is running");
+        return Ok(());
+    };
+    if let Some(story_ref) = story_id
+        && story_ref.id() != running.story_id
+    {
+        anyhow::bail!(
+            "The timer is running on story #{}, not #{}",
+            running.story_id,
+            story_ref.id()
+        );
+    }
+    if let Some(stopped) = log.stop(chrono::Utc::now())? {
+        println!(
+            "⏹  Stopped story #{} after {}",
+            stopped.story_id,
+            timer::format_duration(stopped.duration())
+        );
+    }
+    Ok(())
+}
+
+fn handle_timer_status_command(
+    workspace: Option<String>,
+    story_id: Option<StoryRef>,
+) -> Result<()> {
+    let log = time_log(workspace.as_deref())?;
+    let events = log.events()?;
+    let now = chrono::Utc::now();
+    match timer::running(&events) {
+        Some(running) => println!(
+            "⏱  Running on story #{} for {} (since {})",
+            running.story_id,
+            timer::format_duration(now - running.at),
+            running.at.with_timezone(&chrono::Local).format("%a %H:%M")
+        ),
+        None => println!("No timer is running"),
+    }
+
+    let story_id = story_id
+        .or_else(|| git::detect_current_story().map(|(story_ref, _source)| story_ref))
+        .map(|story_ref| story_ref.id());
+    if let Some(story_id) = story_id {
+        let entries = timer::entries(&events, now);
+        let total = |from| {
+            timer::totals_by_story(&entries, from, now)
+                .remove(&story_id)
+                .unwrap_or_else(chrono::Duration::zero)
+        };
+        println!(
+            "Logged on story #{story_id}: {} this week, {} in total",
+            timer::format_duration(total(week_config(workspace.as_deref()).start_of_week(now))),
+            timer::format_duration(total(chrono::DateTime::<chrono::Utc>::MIN_UTC))
+        );
+    }
+    Ok(())
+}
+
+fn handle_timesheet_command(
+    workspace: Option<String>,
+    token: Option<String>,
+    week: bool,
+    group: timer::TimesheetGroup,
+    format: OutputFormat,
+    debug: bool,
+) -> Result<()> {
+    if !matches!(
+        format,
+        OutputFormat::Text | OutputFormat::Markdown | OutputFormat::Csv
+    ) {
+        anyhow::bail!("The timesheet can only be printed as text, markdown or csv");
+    }
+
+    let log = time_log(workspace.as_deref())?;
+    let now = chrono::Utc::now();
+    let from = if week {
+        week_config(workspace.as_deref()).start_of_week(now)
+    } else {
+        chrono::DateTime::<chrono::Utc>::MIN_UTC
+    };
+    let totals = timer::totals_by_story(&timer::entries(&log.events()?, now), from, now);
+    if totals.is_empty() {
+        println!("No time logged{}", if week { " this week" } else { "" });
+        return Ok(());
+    }
+
+    let workspace_config = resolve_workspace(workspace, token)?;
+    let client = ShortcutClient::new(workspace_config.api_key, debug)
+        .context("Failed to create Shortcut client")?;
+    let ids: Vec<i64> = totals.keys().copied().collect();
+    let (stories, missing) = changelog::fetch_stories(&client, &ids);
+    if debug && !missing.is_empty() {
+        eprintln!("Failed to fetch stories: {missing:?}");
+    }
+    let epics = if stories.values().any(|story| story.epic_id.is_some()) {
+        client.get_epics().unwrap_or_else(|e| {
+            if debug {
+                eprintln!("Failed to fetch epics: {e}");
+            }
+            Vec::new()
+        })
+    } else {
+        Vec::new()
+    };
+
+    let rows = timer::timesheet_rows(&totals, &stories, &epics, group);
+    let total = rows
+        .iter()
+        .fold(chrono::Duration::zero(), |total, row| total + row.duration);
+    let id = |row: &timer::TimesheetRow| row.id.map(|id| id.to_string()).unwrap_or_default();
+    let (header, mut cells): (Vec<&str>, Vec<Vec<String>>) = match group {
+        timer::TimesheetGroup::Story => (
+            vec!["Story", "Name", "Epic", "Hours"],
+            rows.iter()
+                .map(|row| {
+                    vec![
+                        id(row),
+                        row.name.clone(),
+                        row.epic.clone(),
+                        timer::format_hours(row.duration),
+                    ]
+                })
+                .collect(),
+        ),
+        timer::TimesheetGroup::Epic => (
+            vec!["Epic", "Name", "Hours"],
+            rows.iter()
+                .map(|row| vec![id(row), row.name.clone(), timer::format_hours(row.duration)])
+                .collect(),
+        ),
+    };
+    // Spreadsheets add up the rows themselves
+    if format != OutputFormat::Csv {
+        let mut total_row = vec![String::new(); header.len()];
+        total_row[1] = "Total".to_string();
+        total_row[header.len() - 1] = timer::format_hours(total);
+        cells.push(total_row);
+    }
+
+    if format != OutputFormat::Csv {
+        let period = if week {
+            format!(
+                "week of {}",
+                from.with_timezone(&chrono::Local).format("%Y-%m-%d")
+            )
+        } else {
+            "all logged time".to_string()
+        };
+        let title = format!("Timesheet, {period}");
+        if format == OutputFormat::Markdown {
+            println!("# {title}\n");
+        } else {
+            println!("{title}\n");
+        }
+    }
+    output::write_table(&mut io::stdout().lock(), format, &header, &cells)
+}
+
+fn handle_burndown_command(
+    workspace: Option<String>,
+    token: Option<String>,
+    epic: Option<String>,
+    iteration: Option<String>,
+    unit: burndown::BurndownUnit,
+    format: OutputFormat,
+    debug: bool,
+) -> Result<()> {
+    if !matches!(
+        format,
+        OutputFormat::Text | OutputFormat::Csv | OutputFormat::Json
+    ) {
+        anyhow::bail!("The burndown can only be printed as text, csv or json");
+    }
+
+    let workspace_config = resolve_workspace(workspace, token)?;
+    let client = ShortcutClient::new(workspace_config.api_key, debug)
+        .context("Failed to create Shortcut client")?;
+    let today = chrono::Local::now().date_naive();
+    let workflows = client
+        .get_workflows()
+        .context("Failed to fetch workflows")?;
+    // When stories joined the scope and got done comes from their history
+    let story_days = |stories: &[api::Story], scope| {
+        let histories = fetch_histories(&client, stories.iter().map(|story| story.id), debug);
+        burndown::story_days(stories, &histories, &workflows, scope)
+    };
+    let burndown = if let Some(epic) = epic {
+        let epics = client.get_epics().context("Failed to fetch epics")?;
+        let epic = burndown::find_epic(&epics, &epic)?;
+        let stories = client.get_epic_stories(epic.id)?;
+        let days = story_days(&stories, burndown::BurndownScope::Epic(epic.id));
+        burndown::Burndown::for_epic(&epic.name, &stories, &days, unit, today)
+    } else {
+        let iterations = client
+            .get_iterations()
+            .context("Failed to fetch iterations")?;
+        let iteration =
+            burndown::find_iteration(&iterations, iteration.as_deref().unwrap_or("current"))?;
+        let stories = client.get_iteration_stories(iteration.id)?;
+        let days = story_days(&stories, burndown::BurndownScope::Iteration(iteration.id));
+        burndown::Burndown::for_iteration(iteration, &stories, &days, unit, today)
+    };
+
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&burndown)?),
+        OutputFormat::Csv => {
+            let rows: Vec<Vec<String>> = burndown
+                .days
+                .iter()
+                .map(|day| {
+                    vec![
+                        day.date.to_string(),
+                        day.scope.to_string(),
+                        day.completed.to_string(),
+                        day.remaining.to_string(),
+                    ]
+                })
+                .collect();
+            output::write_table(
+                &mut io::stdout().lock(),
+                format,
+                &["Date", "Scope", "Completed", "Remaining"],
+                &rows,
+            )?;
+        }
+        _ => {
+            let width = crossterm::terminal::size().map_or(80, |(width, _)| width.min(120));
+            println!("{}\n", burndown.title);
+            print!("{}", burndown.render_text(width, 20));
+            println!("\n{}", burndown.summary());
+        }
+    }
+    Ok(())
+}
+
+fn handle_report_command(
+    workspace: Option<String>,
+    token: Option<String>,
+    weeks: u32,
+    format: OutputFormat,
+    debug: bool,
+) -> Result<()> {
+    if !matches!(
+        format,
+        OutputFormat::Text | OutputFormat::Markdown | OutputFormat::Csv
+    ) {
+        anyhow::bail!("The report can only be printed as text, markdown or csv");
+    }
+
+    let workspace_config = resolve_workspace(workspace, token)?;
+    let client = ShortcutClient::new(workspace_config.api_key, debug)
+        .context("Failed to create Shortcut client")?;
+    let period = report::weeks(&workspace_config.week, chrono::Utc::now(), weeks);
+    let stories = client
+        .search_stories(&report::query(&period.0, &period.1), None)
+        .context("Failed to search stories")?;
+    let members = fetch_member_cache(&client, debug);
+    let epics = if stories.iter().any(|story| story.epic_id.is_some()) {
+        client.get_epics().unwrap_or_else(|e| {
+            if debug {
+                eprintln!("Failed to fetch epics: {e}");
+            }
+            Vec::new()
+        })
+    } else {
+        Vec::new()
+    };
+
+    let report = report::Report::new(&stories, &members, &epics, &workspace_config.week, period);
+    report.write(&mut io::stdout().lock(), format)
+}
+
+/// The history of each story by ID. Stories whose history can't be fetched
+/// are left out, so callers fall back to the story's own dates.
+fn fetch_histories(
+    client: &ShortcutClient,
+    story_ids: impl IntoIterator<Item = i64>,
+    debug: bool,
+) -> HashMap<i64, Vec<api::History>> {
+    let ids: Vec<i64> = story_ids.into_iter().collect();
+    let (histories, missing) = history::fetch_histories(client, &ids);
+    if debug && !missing.is_empty() {
+        eprintln!("Failed to fetch story histories: {missing:?}");
+    }
+    histories
+}
+
+fn handle_stats_command(
+    workspace: Option<String>,
+    token: Option<String>,
+    query: Option<String>,
+    weeks: u32,
+    group: stats::StatsGroup,
+    format: OutputFormat,
+    debug: bool,
+) -> Result<()> {
+    if !matches!(format, OutputFormat::Text | OutputFormat::Json) {
+        anyhow::bail!("The stats can only be printed as text or json");
+    }
+
+    let workspace_config = resolve_workspace(workspace, token)?;
+    let client = ShortcutClient::new(workspace_config.api_key, debug)
+        .context("Failed to create Shortcut client")?;
+    let query = query.unwrap_or_else(|| stats::default_query(chrono::Utc::now(), weeks));
+    let stories = client
+        .search_stories(&query, None)
+        .context("Failed to search stories")?;
+    let members = if group == stats::StatsGroup::Owner {
+        fetch_member_cache(&client, debug)
+    } else {
+        HashMap::new()
+    };
+    let epics = if group == stats::StatsGroup::Epic {
+        client.get_epics().unwrap_or_else(|e| {
+            if debug {
+                eprintln!("Failed to fetch epics: {e}");
+            }
+            Vec::new()
+        })
+    } else {
+        Vec::new()
+    };
+
+    let workflows = client
+        .get_workflows()
+        .context("Failed to fetch workflows")?;
+    // Cycle times come from when each story moved between states
+    let histories = fetch_histories(
+        &client,
+        stories
+            .iter()
+            .filter(|story| story.completed_at.is_some())
+            .map(|story| story.id),
+        debug,
+    );
+
+    let stats = stats::Stats::new(
+        &stories,
+        &histories,
+        &workflows,
+        group,
+        &members,
+        &epics,
+        &workspace_config.week,
+    );
+    if format == OutputFormat::Json {
+        println!("{}", serde_json::to_string_pretty(&stats)?);
+        Ok(())
+    } else {
+        stats.write_text(&mut io::stdout().lock(), group)
+    }
+}
+
+fn handle_standup_command(
+    workspace: Option<String>,
+    token: Option<String>,
+    format: OutputFormat,
+    debug: bool,
+) -> Result<()> {
+    if !matches!(
+        format,
+        OutputFormat::Text | OutputFormat::Markdown | OutputFormat::Json
+    ) {
+        anyhow::bail!("The standup can only be printed as text, markdown or json");
+    }
+
+    let workspace_config = resolve_workspace(workspace, token)?;
+    let client = ShortcutClient::new(workspace_config.api_key, debug)
+        .context("Failed to create Shortcut client")?;
+    let member = client
+        .get_current_member()
+        .context("Failed to get current member")?;
+    let since = standup::since(&chrono::Local::now());
+
+    let found = client
+        .search_stories(&standup::query(&member.mention_name, &since), None)
+        .context("Failed to search stories")?;
+    // Search results leave out comments and commits, so load the full stories
+    let ids: Vec<i64> = found.iter().map(|story| story.id).collect();
+    let (stories, missing) = changelog::fetch_stories(&client, &ids);
+    if debug && !missing.is_empty() {
+        eprintln!("Failed to fetch stories: {missing:?}");
+    }
+    let stories: Vec<_> = found
+        .into_iter()
+        .map(|story| stories.get(&story.id).cloned().unwrap_or(story))
+        .collect();
+    let workflows = client
+        .get_workflows()
+        .context("Failed to fetch workflows")?;
+
+    let standup = standup::Standup::new(&stories, &workflows, &member.id, since);
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&standup)?),
+        OutputFormat::Markdown => print!("{}", standup.to_markdown()),
+        _ => print!("{}", standup.to_text()),
+    }
+    Ok(())
+}
+
+fn handle_history_command(
+    workspace: Option<String>,
+    token: Option<String>,
+    story_id: Option<StoryRef>,
+    format: OutputFormat,
+    debug: bool,
+) -> Result<()> {
+    if !matches!(format, OutputFormat::Text | OutputFormat::Json) {
+        anyhow::bail!("The history can only be printed as text or json");
+    }
+
+    let story_id = resolve_story_id(story_id)?;
+    let workspace_config = resolve_workspace(workspace, token)?;
+    let client = ShortcutClient::new(workspace_config.api_key, debug)
+        .context("Failed to create Shortcut client")?;
+    let history = client
+        .get_story_history(story_id)
+        .context("Failed to fetch story history")?;
+    let members = fetch_member_cache(&client, debug);
+
+    let events = history::timeline(&history, &members);
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&events)?),
+        _ if events.is_empty() => println!("No changes recorded for story #{story_id}"),
+        _ => print!("{}", history::to_text(&events, chrono::Utc::now())),
+    }
+    Ok(())
+}
+
+fn handle_current_command(
+    workspace: Option<String>,
+    token: Option<String>,
+    id_only: bool,
+    debug: bool,
+) -> Result<()> {
+    let Some((story_ref, source)) = git::detect_current_story() else {
+        anyhow::bail!(
+            "No story detected. Branch or directory names must contain 'sc-<id>' (e.g. sc-42-fix-login)"
+        );
+    };
+
+    let story_id = story_ref.id();
+    if id_only {
+        println!("{story_id}");
+        return Ok(());
+    }
+
+    println!("🔎 Detected {story_ref} from {source}");
+
+    let workspace_config = resolve_workspace(workspace, token)?;
+    let client = ShortcutClient::new(workspace_config.api_key, debug)
+        .context("Failed to create Shortcut client")?;
+    let story = client
+        .get_story(story_id)
+        .context(format!("Failed to fetch story #{story_id}"))?;
+    let workflows = client
+        .get_workflows()
+        .context("Failed to fetch workflows")?;
+    let state_name = workflow::find_state(&workflows, story.workflow_state_id)
+        .map(|s| s.name.clone())
+        .unwrap_or_else(|| story.workflow_state_id.to_string());
 
-    println!("\n✅ Story updated successfully!");
-    println!("  ID: #{}", updated_story.id);
-    println!("  Name: {}", updated_story.name);
-    println!("  Type: {}", updated_story.story_type);
-    println!("  URL: {}", updated_story.app_url);
+    println!("\x1b[1;36m⚡ #{} - {}\x1b[0m", story.id, story.name);
+    println!(
+        "   📌 \x1b[35m{}\x1b[0m | \x1b[36m🔗 {}\x1b[0m",
+        state_name, story.app_url
+    );
 
     Ok(())
 }
@@ -675,16 +2579,11 @@ fn handle_edit_command(
 fn handle_comment_command(
     workspace: Option<String>,
     token: Option<String>,
-    story_id: String,
+    story_id: Option<StoryRef>,
     message: Option<String>,
     debug: bool,
 ) -> Result<()> {
-    // Parse story ID (remove "sc-" prefix if present)
-    let story_id: i64 = story_id
-        .strip_prefix("sc-")
-        .unwrap_or(&story_id)
-        .parse()
-        .context(format!("Invalid story ID: {story_id}"))?;
+    let story_id = resolve_story_id(story_id)?;
 
     // Get API token from command line or config
     let token = if let Some(t) = token {
@@ -768,59 +2667,26 @@ fn handle_comment_command(
     Ok(())
 }
 
-fn handle_branch_command(
-    workspace: Option<String>,
-    token: Option<String>,
-    story_id_str: String,
-    use_default: bool,
-    use_worktree: bool,
-    debug: bool,
-) -> Result<()> {
-    // Get token from args or config
-    let token = if let Some(workspace_name) = workspace {
-        // Use explicitly specified workspace
-        let (config, _created) =
-            Config::load_or_create(&workspace_name).context("Failed to load or create config")?;
-        let ws = config
-            .get_workspace(&workspace_name)
-            .context(format!("Failed to get workspace '{workspace_name}'"))?;
-        ws.api_key.clone()
-    } else if token.is_none() {
-        // No args provided, try to use default workspace
-        match Config::load() {
-            Ok(config) => {
-                if let Some(default_workspace_name) = config.get_default_workspace() {
-                    let ws = config
-                        .get_workspace(&default_workspace_name)
-                        .context(format!(
-                            "Failed to get default workspace '{default_workspace_name}'"
-                        ))?;
-                    ws.api_key.clone()
-                } else {
-                    anyhow::bail!(
-                        "No default workspace configured. Use --workspace to specify one or provide --token"
-                    );
-                }
-            }
-            Err(_) => {
-                anyhow::bail!(
-                    "No configuration file found. Use --workspace to create one or provide --token"
-                );
-            }
-        }
-    } else {
-        // Use command line arguments
-        token.ok_or_else(|| anyhow::anyhow!("Either --token or --workspace must be provided"))?
-    };
-
-    let client = ShortcutClient::new(token, debug).context("Failed to create Shortcut client")?;
-
-    // Parse story ID (handle both "42" and "sc-42" formats)
-    let story_id: i64 = story_id_str
-        .trim_start_matches("sc-")
-        .trim_start_matches("SC-")
-        .parse()
-        .context(format!("Invalid story ID: {story_id_str}"))?;
+fn handle_branch_command(args: BranchCommandArgs) -> Result<()> {
+    let BranchCommandArgs {
+        use_default,
+        use_worktree,
+        debug,
+        ..
+    } = args;
+    let story_id = resolve_story_id(args.story_id)?;
+    let workspace_config = resolve_workspace(args.workspace, args.token)?;
+    let naming = git::naming::BranchNaming::new(&workspace_config.git, &workspace_config.user_id)
+        .context("Invalid branch naming settings in the workspace config")?;
+    let repos = workspace_config.git.repo_paths(&args.repos)?;
+
+    let base = args.base.or(workspace_config.git.base_branch);
+    let remote = workspace_config
+        .git
+        .remote
+        .unwrap_or_else(|| git::DEFAULT_REMOTE.to_string());
+    let client = ShortcutClient::new(workspace_config.api_key, debug)
+        .context("Failed to create Shortcut client")?;
 
     // Fetch the story to get the suggested branch name
     let story = client
@@ -832,23 +2698,7 @@ fn handle_branch_command(
     }
 
     // Generate the suggested branch name
-    let suggested_branch = story.formatted_vcs_branch_name.clone().unwrap_or_else(|| {
-        format!(
-            "sc-{}-{}",
-            story.id,
-            story
-                .name
-                .to_lowercase()
-                .chars()
-                .map(|c| if c.is_alphanumeric() { c } else { '-' })
-                .collect::<String>()
-                .split('-')
-                .filter(|s| !s.is_empty())
-                .take(5)
-                .collect::<Vec<_>>()
-                .join("-")
-        )
-    });
+    let suggested_branch = naming.branch_name(&story);
 
     // Determine the branch name to use
     let branch_name = if use_default {
@@ -862,6 +2712,43 @@ fn handle_branch_command(
             .context("Failed to read branch name")?
     };
 
+    if !repos.is_empty() {
+        let request = git::operations::GitBranchRequest {
+            branch_name,
+            worktree_path: String::new(),
+            operation: if use_worktree {
+                git::operations::GitOperation::CreateWorktree
+            } else {
+                git::operations::GitOperation::CreateBranch
+            },
+            story_id,
+            base,
+        };
+        let push_remote = args.push.then_some(remote.as_str());
+        let results = branch_in_repos(&story, &naming, &repos, &request, args.stash, push_remote);
+
+        if results.iter().any(|r| r.result.success) {
+            let workflows = client
+                .get_workflows()
+                .context("Failed to fetch workflows")?;
+            if git::operations::move_story_to_in_progress(&client, story_id, &workflows, debug)
+                .is_some()
+            {
+                println!("📋 Story moved to In Progress");
+            }
+        }
+        println!("  View story: {}", story.app_url);
+
+        let failed = results.iter().filter(|r| !r.result.success).count();
+        if failed > 0 {
+            anyhow::bail!(
+                "Failed to create the branch in {failed} of {} repositories",
+                results.len()
+            );
+        }
+        return Ok(());
+    }
+
     // Detect git context
     let git_context = git::GitContext::detect().context("Failed to detect git context")?;
 
@@ -876,24 +2763,53 @@ fn handle_branch_command(
         println!("Detected bare repository, using worktree mode.");
     }
 
+    // Uncommitted changes follow a checkout to the new branch; a worktree leaves them behind
+    let mut stash = false;
+    if !should_use_worktree && git::has_uncommitted_changes(Path::new("."))? {
+        println!("⚠️  You have uncommitted changes, they will be carried over to the new branch.");
+        stash = args.stash
+            || (!use_default
+                && io::stdin().is_terminal()
+                && Confirm::new()
+                    .with_prompt("Stash them first?")
+                    .default(false)
+                    .interact()?);
+    }
+
     // Build the request
     let request = git::operations::GitBranchRequest {
+        worktree_path: naming.worktree_path(&story, &branch_name),
         branch_name: branch_name.clone(),
-        worktree_path: git::generate_worktree_path(&branch_name),
         operation: if should_use_worktree {
             git::operations::GitOperation::CreateWorktree
         } else {
             git::operations::GitOperation::CreateBranch
         },
         story_id,
+        base,
     };
 
     // Execute the git operation
-    let result = git::operations::execute_git_operation(&request);
+    let git = git::GixBackend::default();
+    let result = if stash {
+        git::operations::execute_after_stash(&git, Path::new("."), &request)?
+    } else {
+        git::operations::execute_git_operation(&git, &request)
+    };
 
     if result.success {
+        if stash {
+            println!("📦 Changes stashed, run `git stash pop` to restore them");
+        }
         println!("\n✅ {}", result.message);
 
+        if args.push {
+            match git::push_branch(Path::new("."), &remote, &result.branch_name) {
+                Ok(()) => println!("⬆️  Pushed '{}' to '{remote}'", result.branch_name),
+                Err(e) => println!("⚠️  {e}"),
+            }
+        }
+
         // Move story to In Progress
         let workflows = client
             .get_workflows()
@@ -919,9 +2835,159 @@ fn handle_branch_command(
     Ok(())
 }
 
+/// Create the same branch in several repositories, printing the result of
+/// each and a summary
+fn branch_in_repos(
+    story: &api::Story,
+    naming: &git::naming::BranchNaming,
+    repos: &[(String, PathBuf)],
+    request: &git::operations::GitBranchRequest,
+    stash: bool,
+    push_remote: Option<&str>,
+) -> Vec<git::operations::RepoBranchResult> {
+    let branch_name = &request.branch_name;
+
+    // Uncommitted changes follow a checkout to the new branch unless stashed
+    let mut stashed = vec![false; repos.len()];
+    if stash && request.operation == git::operations::GitOperation::CreateBranch {
+        for ((_, path), stashed) in repos.iter().zip(&mut stashed) {
+            *stashed = git::has_uncommitted_changes(path).unwrap_or(false)
+                && git::stash_changes(path, &format!("sc-cli: before switching to {branch_name}"))
+                    .is_ok();
+        }
+    }
+
+    let results = git::operations::execute_in_repos(repos, request, |path| {
+        naming.for_repo(path).worktree_path(story, branch_name)
+    });
+
+    println!();
+    for ((git::operations::RepoBranchResult { repo, result }, (_, path)), stashed) in
+        results.iter().zip(repos).zip(stashed)
+    {
+        if !result.success {
+            println!("❌ {repo}: {}", result.message);
+            // Nothing changed in this repository, so don't leave its changes stashed
+            if stashed && let Err(e) = git::stash_pop(path) {
+                println!("   ⚠️  {e}");
+            }
+            continue;
+        }
+        if stashed {
+            println!("📦 {repo}: changes stashed, run `git stash pop` there to restore them");
+        }
+        println!("✅ {repo}: {}", result.message);
+        if let Some(worktree_path) = &result.worktree_path {
+            println!("   📁 Worktree at: {worktree_path}");
+        }
+        if let Some(remote) = push_remote {
+            match git::push_branch(path, remote, branch_name) {
+                Ok(()) => println!("   ⬆️  Pushed '{branch_name}' to '{remote}'"),
+                Err(e) => println!("   ⚠️  {e}"),
+            }
+        }
+    }
+    println!(
+        "\n{}",
+        git::operations::repos_summary(branch_name, &results)
+    );
+    results
+}
+
+fn handle_start_command(args: StartCommandArgs) -> Result<()> {
+    let story_id = resolve_story_id(args.story_id)?;
+    let workspace_config = resolve_workspace(args.workspace, args.token)?;
+    let settings = workspace_config.start.clone();
+    let client = ShortcutClient::new(workspace_config.api_key, args.debug)
+        .context("Failed to create Shortcut client")?;
+
+    let story = client
+        .get_story(story_id)
+        .context(format!("Failed to fetch story #{story_id}"))?;
+    let workflows = client
+        .get_workflows()
+        .context("Failed to fetch workflows")?;
+    let member = client
+        .get_current_member()
+        .context("Failed to get current member")?;
+
+    println!("🚀 Starting #{} - {}", story.id, story.name);
+
+    let git = if args.no_branch || !settings.branch {
+        None
+    } else {
+        let git_context = git::GitContext::detect().context("Failed to detect git context")?;
+        if git_context.is_git_repo() {
+            let naming =
+                git::naming::BranchNaming::new(&workspace_config.git, &workspace_config.user_id)
+                    .context("Invalid branch naming settings in the workspace config")?;
+            let branch_name = naming.branch_name(&story);
+            let use_worktree = args.worktree || settings.worktree || git_context.is_bare_repo();
+            Some(git::operations::GitBranchRequest {
+                worktree_path: naming.worktree_path(&story, &branch_name),
+                branch_name,
+                operation: if use_worktree {
+                    git::operations::GitOperation::CreateWorktree
+                } else {
+                    git::operations::GitOperation::CreateBranch
+                },
+                story_id,
+                base: workspace_config.git.base_branch.clone(),
+            })
+        } else {
+            println!("⚠️  Not a git repository, skipping the branch step");
+            None
+        }
+    };
+
+    let options = start::StartOptions {
+        assign: settings.assign && !args.no_assign,
+        move_to_started: settings.move_story && !args.no_move,
+        git,
+        comment: if args.no_comment {
+            None
+        } else {
+            args.comment.or(settings.comment)
+        },
+    };
+
+    let steps = start::plan_start(&story, &workflows, &member, &options);
+    if steps.is_empty() {
+        println!("ℹ️  Nothing to do, the story is already started and assigned to you");
+        return Ok(());
+    }
+
+    if args.dry_run {
+        println!("\nDry run, the following steps would be performed:");
+        for step in &steps {
+            println!("  • {}", step.describe());
+        }
+        return Ok(());
+    }
+
+    for step in &steps {
+        let message = start::run_step(&client, &git::GixBackend::default(), story_id, step)
+            .with_context(|| format!("Step failed: {}", step.describe()))?;
+        println!("✅ {message}");
+
+        if let start::StartStep::Git(request) = step
+            && request.operation == git::operations::GitOperation::CreateWorktree
+        {
+            println!("   Run: cd {}", request.worktree_path);
+        }
+    }
+
+    println!("  View story: {}", story.app_url);
+
+    Ok(())
+}
+
 fn handle_view_command(args: ViewCommandArgs) -> Result<()> {
-    // Get token, username, and fetch_limit from args or config
-    let (token, username, config_limit) = if let Some(workspace_name) = args.workspace {
+    let time_log = time_log(args.workspace.as_deref())?;
+    let week = week_config(args.workspace.as_deref());
+
+    // Get token, username, fetch_limit and git settings from args or config
+    let (token, username, config_limit, git_config) = if let Some(workspace_name) = args.workspace {
         // Use explicitly specified workspace
         let (config, _created) =
             Config::load_or_create(&workspace_name).context("Failed to load or create config")?;
@@ -932,6 +2998,7 @@ fn handle_view_command(args: ViewCommandArgs) -> Result<()> {
             workspace.api_key.clone(),
             workspace.user_id.clone(),
             workspace.fetch_limit,
+            workspace.git.clone(),
         )
     } else if args.token.is_none() && args.username.is_none() {
         // No args provided, try to use default workspace
@@ -948,6 +3015,7 @@ fn handle_view_command(args: ViewCommandArgs) -> Result<()> {
                         workspace.api_key.clone(),
                         workspace.user_id.clone(),
                         workspace.fetch_limit,
+                        workspace.git.clone(),
                     )
                 } else {
                     anyhow::bail!(
@@ -969,12 +3037,16 @@ fn handle_view_command(args: ViewCommandArgs) -> Result<()> {
         let username = args
             .username
             .ok_or_else(|| anyhow::anyhow!("Either username or --workspace must be provided"))?;
-        (token, username, 50) // Default limit when not using workspace
+        (token, username, 50, config::GitConfig::default()) // Default limit when not using workspace
     };
 
     // Use command-line limit if provided, otherwise use workspace config limit
     let limit = args.limit.unwrap_or(config_limit);
 
+    // Validate naming templates before the terminal switches to the TUI
+    let branch_naming = git::naming::BranchNaming::new(&git_config, &username)
+        .context("Invalid branch naming settings in the workspace config")?;
+
     // Initialize API client
     let client =
         ShortcutClient::new(token, args.debug).context("Failed to create Shortcut client")?;
@@ -1026,6 +3098,20 @@ fn handle_view_command(args: ViewCommandArgs) -> Result<()> {
         eprintln!("Query: {query}");
     }
 
+    // Machine-readable output skips the TUI and streams every matching story
+    if args.output != OutputFormat::Text {
+        let member_cache = fetch_member_cache(&client, args.debug);
+        return export_stories(
+            &client,
+            &query,
+            args.output,
+            &workflows,
+            &epics,
+            &member_cache,
+            args.debug,
+        );
+    }
+
     // Load first page initially, but limit to the specified limit
     let mut stories = Vec::new();
     let mut next_page_token = None;
@@ -1138,11 +3224,29 @@ fn handle_view_command(args: ViewCommandArgs) -> Result<()> {
     setup_terminal()?;
 
     // Create app with stories and workflows
-    let mut app = App::new(stories, workflows.clone(), query.clone(), next_page_token);
+    let mut app = App::new(
+        stories,
+        workflows.clone(),
+        query.clone(),
+        next_page_token,
+        week,
+    );
 
     // Set epics in the app for filtering
     app.set_epics(epics.clone());
 
+    app.branch_naming = branch_naming;
+    app.git_base_branch = git_config.base_branch.clone();
+    app.git_repos = git_config.all_repo_paths();
+
+    app.running_timer = time_log
+        .running()
+        .unwrap_or_else(|e| {
+            eprintln!("WARNING: Failed to read the time log: {e:#}");
+            None
+        })
+        .map(|event| (event.story_id, event.at));
+
     // Populate the member cache in the app
     for (id, name) in member_cache {
         app.add_member_to_cache(id, name);
@@ -1170,7 +3274,7 @@ fn handle_view_command(args: ViewCommandArgs) -> Result<()> {
         }
     }
 
-    let result = run_app(app, client, workflows, args.debug);
+    let result = run_app(app, client, workflows, time_log, args.debug);
 
     // Restore terminal
     restore_terminal()?;
@@ -1196,6 +3300,7 @@ fn run_app(
     mut app: App,
     client: ShortcutClient,
     workflows: Vec<api::Workflow>,
+    time_log: timer::TimeLog,
     debug: bool,
 ) -> Result<()> {
     let backend = CrosstermBackend::new(io::stdout());
@@ -1520,6 +3625,49 @@ fn run_app(
                 continue;
             }
 
+            // Reuse the story's existing branch or worktree
+            let story_id = app.git_popup_state.story_id;
+            let existing = match selected_option {
+                ui::GitBranchOption::SwitchToBranch => {
+                    let branch_name = app
+                        .git_popup_state
+                        .existing_branch
+                        .clone()
+                        .unwrap_or_default();
+                    let state =
+                        switch_branch_result(branch_name, app.git_popup_state.stash, story_id);
+                    if state.success {
+                        app.git_context.current_branch = Some(state.branch_name.clone());
+                    }
+                    Some(state)
+                }
+                ui::GitBranchOption::OpenWorktree => {
+                    let path = app
+                        .git_popup_state
+                        .existing_worktree
+                        .clone()
+                        .unwrap_or_default();
+                    Some(ui::GitResultState {
+                        success: true,
+                        operation_type: ui::GitOperationType::OpenWorktree,
+                        message: format!("The story's worktree is at '{path}'"),
+                        branch_name: String::new(),
+                        worktree_path: Some(path),
+                        story_id,
+                        selected_option: ui::GitResultOption::ExitAndChange,
+                        repo_results: Vec::new(),
+                    })
+                }
+                _ => None,
+            };
+            if let Some(state) = existing {
+                app.git_result_state = state;
+                app.show_git_result_popup = true;
+                app.git_branch_requested = false;
+                app.git_popup_state = ui::GitBranchPopupState::default();
+                continue;
+            }
+
             // Build the git operation request
             let request = git::operations::GitBranchRequest {
                 branch_name: app.git_popup_state.branch_name_textarea.lines().join(""),
@@ -1529,13 +3677,67 @@ fn run_app(
                     ui::GitBranchOption::CreateWorktree => {
                         git::operations::GitOperation::CreateWorktree
                     }
-                    ui::GitBranchOption::Cancel => unreachable!(),
+                    ui::GitBranchOption::SwitchToBranch
+                    | ui::GitBranchOption::OpenWorktree
+                    | ui::GitBranchOption::Cancel => unreachable!(),
                 },
                 story_id: app.git_popup_state.story_id,
+                base: app.git_base_branch.clone(),
             };
 
+            if app.git_popup_state.in_repos {
+                let story = app
+                    .all_stories_unfiltered
+                    .iter()
+                    .find(|s| s.id == request.story_id)
+                    .cloned();
+                let results = git::operations::execute_in_repos(
+                    &app.git_repos,
+                    &request,
+                    |path| match &story {
+                        Some(story) => app
+                            .branch_naming
+                            .for_repo(path)
+                            .worktree_path(story, &request.branch_name),
+                        None => request.worktree_path.clone(),
+                    },
+                );
+
+                if results.iter().any(|r| r.result.success)
+                    && let Some(updated_story) = git::operations::move_story_to_in_progress(
+                        &client,
+                        request.story_id,
+                        &app.workflows,
+                        debug,
+                    )
+                {
+                    update_story_state(&mut app, request.story_id, updated_story);
+                }
+                if results
+                    .iter()
+                    .any(|r| r.result.success && r.result.worktree_path.is_some())
+                {
+                    app.worktree_story_ids.insert(request.story_id);
+                }
+
+                app.git_result_state = ui::GitResultState {
+                    success: results.iter().all(|r| r.result.success),
+                    operation_type: ui::GitOperationType::CreateBranch,
+                    message: git::operations::repos_summary(&request.branch_name, &results),
+                    branch_name: request.branch_name.clone(),
+                    worktree_path: None,
+                    story_id: request.story_id,
+                    selected_option: ui::GitResultOption::Continue,
+                    repo_results: results,
+                };
+                app.show_git_result_popup = true;
+                app.git_branch_requested = false;
+                app.git_popup_state = ui::GitBranchPopupState::default();
+                continue;
+            }
+
             // Execute the git operation
-            let result = git::operations::execute_git_operation(&request);
+            let result = git::operations::execute_git_operation(&git::GixBackend::default(), &request);
 
             // Move story to In Progress if operation was successful
             if result.success
@@ -1549,6 +3751,10 @@ fn run_app(
                 update_story_state(&mut app, result.story_id, updated_story);
             }
 
+            if result.success && result.worktree_path.is_some() {
+                app.worktree_story_ids.insert(result.story_id);
+            }
+
             // Convert to UI result state
             let operation_type = match result.operation {
                 git::operations::GitOperation::CreateBranch => ui::GitOperationType::CreateBranch,
@@ -1567,6 +3773,7 @@ fn run_app(
                 } else {
                     ui::GitResultOption::Continue
                 },
+                repo_results: Vec::new(),
             };
             app.show_git_result_popup = true;
 
@@ -1575,6 +3782,84 @@ fn run_app(
             app.git_popup_state = ui::GitBranchPopupState::default();
         }
 
+        // Check out a branch picked in the detail popup's Git tab
+        if let Some(branch_name) = app.git_checkout.take() {
+            let story_id = app.get_selected_story().map_or(0, |story| story.id);
+            app.git_result_state = switch_branch_result(branch_name, false, story_id);
+            if app.git_result_state.success {
+                app.git_context.current_branch = Some(app.git_result_state.branch_name.clone());
+            }
+            app.show_git_result_popup = true;
+        }
+
+        // Start or stop the timer on the selected story
+        if app.timer_toggle_requested {
+            app.timer_toggle_requested = false;
+            if let Some(story_id) = app.get_selected_story().map(|story| story.id) {
+                let now = chrono::Utc::now();
+                let result = if app.running_timer.is_some_and(|(id, _)| id == story_id) {
+                    time_log.stop(now).map(|_| ())
+                } else {
+                    time_log.start(story_id, now).map(|_| ())
+                };
+                match result.and_then(|()| time_log.running()) {
+                    Ok(running) => {
+                        app.running_timer = running.map(|event| (event.story_id, event.at));
+                    }
+                    Err(e) => eprintln!("Failed to update the time log: {e:#}"),
+                }
+            }
+        }
+
+        // Load the burndown of the selected story's epic
+        if app.burndown_requested {
+            app.burndown_requested = false;
+            if let Some(epic_id) = app.get_selected_story().and_then(|story| story.epic_id) {
+                match client.get_epic_stories(epic_id) {
+                    Ok(stories) => {
+                        let name = app
+                            .epics
+                            .iter()
+                            .find(|epic| epic.id == epic_id)
+                            .map_or_else(|| format!("#{epic_id}"), |epic| epic.name.clone());
+                        // Count stories when nothing is estimated
+                        let unit = if stories.iter().any(|story| story.estimate.is_some()) {
+                            burndown::BurndownUnit::Points
+                        } else {
+                            burndown::BurndownUnit::Stories
+                        };
+                        let histories =
+                            fetch_histories(&client, stories.iter().map(|story| story.id), debug);
+                        let days = burndown::story_days(
+                            &stories,
+                            &histories,
+                            &workflows,
+                            burndown::BurndownScope::Epic(epic_id),
+                        );
+                        let today = chrono::Local::now().date_naive();
+                        app.burndown = Some(burndown::Burndown::for_epic(
+                            &name, &stories, &days, unit, today,
+                        ));
+                    }
+                    Err(e) => eprintln!("Failed to load the epic burndown: {e:#}"),
+                }
+            }
+        }
+
+        // Load the history of the story shown in the detail popup
+        if app.history_requested {
+            app.history_requested = false;
+            if let Some(story_id) = app.get_selected_story().map(|story| story.id) {
+                match client.get_story_history(story_id) {
+                    Ok(history) => {
+                        let events = history::timeline(&history, &app.member_cache);
+                        app.story_history = Some((story_id, events));
+                    }
+                    Err(e) => eprintln!("Failed to load the story history: {e:#}"),
+                }
+            }
+        }
+
         // Check if we need to refresh all stories
         if app.refresh_requested {
             // Reset the refresh flag
@@ -1593,20 +3878,29 @@ fn run_app(
                         workflows.clone(),
                         app.search_query.clone(),
                         search_result.next_page_token,
+                        app.week.clone(),
                     );
 
                     // Preserve member cache, user ID, and epics from the old app
                     let old_member_cache = app.member_cache.clone();
                     let old_user_id = app.current_user_id.clone();
                     let old_epics = app.epics.clone();
+                    let old_branch_naming = app.branch_naming.clone();
+                    let old_git_base_branch = app.git_base_branch.clone();
+                    let old_git_repos = app.git_repos.clone();
+                    let old_running_timer = app.running_timer;
 
                     // Replace the app with fresh data
                     app = new_app;
 
-                    // Restore member cache, user ID, and epics
+                    // Restore member cache, user ID, epics and naming settings
                     app.member_cache = old_member_cache;
                     app.epics = old_epics;
                     app.current_user_id = old_user_id;
+                    app.branch_naming = old_branch_naming;
+                    app.git_base_branch = old_git_base_branch;
+                    app.git_repos = old_git_repos;
+                    app.running_timer = old_running_timer;
 
                     app.is_loading = false;
                 }
@@ -1664,6 +3958,28 @@ fn run_app(
     Ok(())
 }
 
+/// Switch to an existing branch from the TUI, refusing when it would carry
+/// uncommitted changes over unless they are stashed
+fn switch_branch_result(branch_name: String, stash: bool, story_id: i64) -> ui::GitResultState {
+    let result = git::switch_branch(Path::new("."), &branch_name, stash);
+    ui::GitResultState {
+        success: result.is_ok(),
+        operation_type: ui::GitOperationType::Checkout,
+        message: match result {
+            Ok(true) => format!(
+                "Switched to branch '{branch_name}'. Your changes were stashed, run `git stash pop` to restore them"
+            ),
+            Ok(false) => format!("Switched to branch '{branch_name}'"),
+            Err(e) => format!("{e:#}"),
+        },
+        branch_name,
+        worktree_path: None,
+        story_id,
+        selected_option: ui::GitResultOption::Continue,
+        repo_results: Vec::new(),
+    }
+}
+
 fn update_story_state(app: &mut App, story_id: i64, updated_story: api::Story) {
     // Update the story in the unfiltered list
     if let Some(pos) = app
@@ -1768,7 +4084,9 @@ fn update_story_details(app: &mut App, story_id: i64, updated_story: api::Story)
 
 fn handle_show_command(args: ShowCommandArgs) -> Result<()> {
     // Get token, username, and config from args or config (similar to view command)
-    let (api_token, search_username, _config_limit) = if let Some(workspace_name) = args.workspace {
+    let (api_token, search_username, templates, git_config) = if let Some(workspace_name) =
+        args.workspace
+    {
         // Use explicitly specified workspace
         let (config, _created) =
             Config::load_or_create(&workspace_name).context("Failed to load or create config")?;
@@ -1778,7 +4096,8 @@ fn handle_show_command(args: ShowCommandArgs) -> Result<()> {
         (
             workspace_config.api_key.clone(),
             workspace_config.user_id.clone(),
-            workspace_config.fetch_limit,
+            workspace_config.templates.clone(),
+            workspace_config.git.clone(),
         )
     } else if args.token.is_none() && args.username.is_none() {
         // No args provided, try to use default workspace
@@ -1794,7 +4113,8 @@ fn handle_show_command(args: ShowCommandArgs) -> Result<()> {
                     (
                         workspace_config.api_key.clone(),
                         workspace_config.user_id.clone(),
-                        workspace_config.fetch_limit,
+                        workspace_config.templates.clone(),
+                        workspace_config.git.clone(),
                     )
                 } else {
                     anyhow::bail!(
@@ -1816,9 +4136,18 @@ fn handle_show_command(args: ShowCommandArgs) -> Result<()> {
         let search_username = args
             .username
             .ok_or_else(|| anyhow::anyhow!("Either username or --workspace must be provided"))?;
-        (api_token, search_username, 50) // Default limit when not using workspace
+        (
+            api_token,
+            search_username,
+            HashMap::new(),
+            config::GitConfig::default(),
+        )
     };
 
+    // Templates name branches the way `sc-cli branch` would
+    let branch_naming = git::naming::BranchNaming::new(&git_config, &search_username)
+        .context("Invalid branch naming settings in the workspace config")?;
+
     // Initialize API client
     let client =
         ShortcutClient::new(api_token, args.debug).context("Failed to create Shortcut client")?;
@@ -1889,7 +4218,78 @@ fn handle_show_command(args: ShowCommandArgs) -> Result<()> {
         }
     }
 
-    // Start pagination
+    if args.output != OutputFormat::Text || args.template.is_some() {
+        let epics = client.get_epics().unwrap_or_else(|e| {
+            if args.debug {
+                eprintln!("WARNING: Failed to fetch epics: {e}");
+            }
+            Vec::new()
+        });
+        if let Some(template) = &args.template {
+            // A configured template name takes precedence over an inline template
+            let source = templates.get(template).unwrap_or(template);
+            let template = template::Template::parse(source)?;
+            let now = chrono::Utc::now();
+            return print_templated_stories(&client, &query, &template, args.debug, |story| {
+                template::story_context(
+                    story,
+                    &workflows,
+                    &epics,
+                    &member_cache,
+                    &branch_naming,
+                    now,
+                )
+            });
+        }
+        return export_stories(
+            &client,
+            &query,
+            args.output,
+            &workflows,
+            &epics,
+            &member_cache,
+            args.debug,
+        );
+    }
+
+    // Pipes get every story at once, without colors or prompts
+    if !io::stdout().is_terminal() {
+        let mut stdout = io::stdout().lock();
+        return ignore_broken_pipe(
+            print_all_stories(
+                &mut stdout,
+                &client,
+                &query,
+                &workflow_state_map,
+                &member_cache,
+                false,
+                args.debug,
+            )
+            .map(|count| {
+                if count == 0 {
+                    eprintln!("No stories found for query: {query}");
+                }
+            }),
+        );
+    }
+
+    if !args.no_pager {
+        let config_pager = Config::load().ok().and_then(|config| config.pager);
+        if let Some(pager) = resolve_pager(config_pager)
+            && show_stories_in_pager(
+                &pager,
+                &client,
+                &query,
+                &workflow_state_map,
+                &member_cache,
+                args.debug,
+            )?
+        {
+            return Ok(());
+        }
+    }
+
+    // Built-in paginator
     show_stories_paginated(
         &client,
         &query,
@@ -1900,6 +4300,165 @@ fn handle_show_command(args: ShowCommandArgs) -> Result<()> {
     )
 }
 
+/// Write one story in the `show` layout; `color` false strips ANSI escapes for pipes
+fn write_story_block(
+    out: &mut impl io::Write,
+    story: &api::Story,
+    workflow_state_map: &HashMap<i64, String>,
+    member_cache: &HashMap<String, String>,
+    color: bool,
+) -> Result<()> {
+    use std::fmt::Write;
+
+    let mut block = String::new();
+
+    // Story title with bright cyan color and lightning bolt emoji
+    writeln!(block, "\x1b[1;36m⚡ #{} - {}\x1b[0m", story.id, story.name)?;
+
+    if !story.description.is_empty() {
+        let first_line = story.description.lines().next().unwrap_or("");
+        if !first_line.is_empty() {
+            // Description with light gray color and document emoji
+            writeln!(block, "   \x1b[37m📄 {first_line}\x1b[0m")?;
+        }
+    }
+
+    if !story.owner_ids.is_empty() {
+        let owner_names: Vec<String> = story
+            .owner_ids
+            .iter()
+            .map(|id| member_cache.get(id).cloned().unwrap_or_else(|| id.clone()))
+            .collect();
+        // Owners with yellow color and person emoji
+        writeln!(
+            block,
+            "   \x1b[33m👤 Owner(s): {}\x1b[0m",
+            owner_names.join(", ")
+        )?;
+    }
+
+    let state_name = workflow_state_map
+        .get(&story.workflow_state_id)
+        .cloned()
+        .unwrap_or_else(|| story.workflow_state_id.to_string());
+
+    // Get emoji and color based on story type
+    let (type_emoji, type_color) = match story.story_type.as_str() {
+        "feature" => ("✨", "\x1b[32m"), // Green for features
+        "bug" => ("🐞", "\x1b[31m"),     // Red for bugs
+        "chore" => ("⚙️", "\x1b[34m"),   // Blue for chores
+        _ => ("📝", "\x1b[37m"),         // Default gray
+    };
+
+    // Get emoji based on state name
+    let state_emoji = match state_name.to_lowercase().as_str() {
+        name if name.contains("todo") || name.contains("backlog") => "📋",
+        name if name.contains("progress") || name.contains("doing") => "🔄",
+        name if name.contains("review") => "👀",
+        name if name.contains("done") || name.contains("complete") => "✅",
+        _ => "📌",
+    };
+
+    // State, type, and URL with appropriate colors and emojis
+    writeln!(
+        block,
+        "   {} \x1b[35m{}\x1b[0m | {}{} {}\x1b[0m | \x1b[36m🔗 {}\x1b[0m",
+        state_emoji, state_name, type_emoji, type_color, story.story_type, story.app_url
+    )?;
+    writeln!(block)?; // Empty line between stories
+
+    if !color {
+        block = markdown::strip_ansi(&block);
+    }
+    out.write_all(block.as_bytes())?;
+    Ok(())
+}
+
+/// Print every story matching `query` in the `show` layout without pausing,
+/// for pipes and external pagers
+fn print_all_stories(
+    out: &mut impl io::Write,
+    client: &ShortcutClient,
+    query: &str,
+    workflow_state_map: &HashMap<i64, String>,
+    member_cache: &HashMap<String, String>,
+    color: bool,
+    debug: bool,
+) -> Result<usize> {
+    let mut count = 0;
+    for_each_story(client, query, debug, |story| {
+        count += 1;
+        write_story_block(out, story, workflow_state_map, member_cache, color)
+    })?;
+    out.flush()?;
+    Ok(count)
+}
+
+/// Pick the pager for `show`: `pager` from config, then `$PAGER`, then `less -R`
+fn resolve_pager(config_pager: Option<String>) -> Option<String> {
+    config_pager
+        .or_else(|| std::env::var("PAGER").ok())
+        .map(|pager| pager.trim().to_string())
+        .or_else(|| Some("less -R".to_string()))
+        .filter(|pager| !pager.is_empty())
+}
+
+/// Stream stories through an external pager. Returns `Ok(false)` when the pager
+/// could not be started, so the caller can fall back to the built-in paginator.
+fn show_stories_in_pager(
+    pager: &str,
+    client: &ShortcutClient,
+    query: &str,
+    workflow_state_map: &HashMap<i64, String>,
+    member_cache: &HashMap<String, String>,
+    debug: bool,
+) -> Result<bool> {
+    let mut parts = pager.split_whitespace();
+    let Some(program) = parts.next() else {
+        return Ok(false);
+    };
+
+    let mut command = std::process::Command::new(program);
+    command.args(parts).stdin(std::process::Stdio::piped());
+    // Same defaults as git: quit if one screen, keep colors, don't clear the screen
+    if std::env::var_os("LESS").is_none() {
+        command.env("LESS", "FRX");
+    }
+
+    let mut child = match command.spawn() {
+        Ok(child) => child,
+        Err(e) => {
+            if debug {
+                eprintln!("Failed to start pager '{pager}': {e}");
+            }
+            return Ok(false);
+        }
+    };
+
+    let result = {
+        let mut stdin = child.stdin.take().context("Failed to open pager input")?;
+        ignore_broken_pipe(
+            print_all_stories(
+                &mut stdin,
+                client,
+                query,
+                workflow_state_map,
+                member_cache,
+                true,
+                debug,
+            )
+            .map(|count| {
+                if count == 0 {
+                    eprintln!("🔍 No stories found for query: {query}");
+                }
+            }),
+        )
+        // stdin is dropped here so the pager sees EOF
+    };
+    child.wait().context("Failed to wait for pager")?;
+    result.map(|()| true)
+}
+
 fn show_stories_paginated(
     client: &ShortcutClient,
     query: &str,
@@ -1991,55 +4550,13 @@ fn show_stories_paginated(
         }
 
         for story in stories_to_show {
-            // Story title with bright cyan color and lightning bolt emoji
-            println!("\x1b[1;36m⚡ #{} - {}\x1b[0m", story.id, story.name);
-
-            if !story.description.is_empty() {
-                let first_line = story.description.lines().next().unwrap_or("");
-                if !first_line.is_empty() {
-                    // Description with light gray color and document emoji
-                    println!("   \x1b[37m📄 {first_line}\x1b[0m");
-                }
-            }
-
-            if !story.owner_ids.is_empty() {
-                let owner_names: Vec<String> = story
-                    .owner_ids
-                    .iter()
-                    .map(|id| member_cache.get(id).cloned().unwrap_or_else(|| id.clone()))
-                    .collect();
-                // Owners with yellow color and person emoji
-                println!("   \x1b[33m👤 Owner(s): {}\x1b[0m", owner_names.join(", "));
-            }
-
-            let state_name = workflow_state_map
-                .get(&story.workflow_state_id)
-                .cloned()
-                .unwrap_or_else(|| story.workflow_state_id.to_string());
-
-            // Get emoji and color based on story type
-            let (type_emoji, type_color) = match story.story_type.as_str() {
-                "feature" => ("✨", "\x1b[32m"), // Green for features
-                "bug" => ("🐞", "\x1b[31m"),     // Red for bugs
-                "chore" => ("⚙️", "\x1b[34m"),   // Blue for chores
-                _ => ("📝", "\x1b[37m"),         // Default gray
-            };
-
-            // Get emoji based on state name
-            let state_emoji = match state_name.to_lowercase().as_str() {
-                name if name.contains("todo") || name.contains("backlog") => "📋",
-                name if name.contains("progress") || name.contains("doing") => "🔄",
-                name if name.contains("review") => "👀",
-                name if name.contains("done") || name.contains("complete") => "✅",
-                _ => "📌",
-            };
-
-            // State, type, and URL with appropriate colors and emojis
-            println!(
-                "   {} \x1b[35m{}\x1b[0m | {}{} {}\x1b[0m | \x1b[36m🔗 {}\x1b[0m",
-                state_emoji, state_name, type_emoji, type_color, story.story_type, story.app_url
-            );
-            println!(); // Empty line between stories
+            write_story_block(
+                &mut io::stdout(),
+                story,
+                workflow_state_map,
+                member_cache,
+                true,
+            )?;
         }
 
         total_shown += stories_to_show.len();
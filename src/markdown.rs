@@ -0,0 +1,340 @@
+use pulldown_cmark::{Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+
+const RESET: &str = "\x1b[0m";
+const BOLD: &str = "\x1b[1m";
+const ITALIC: &str = "\x1b[3m";
+const UNDERLINE: &str = "\x1b[4m";
+const STRIKE: &str = "\x1b[9m";
+const HEADING: &str = "\x1b[1;36m";
+const CODE: &str = "\x1b[33m";
+const QUOTE: &str = "\x1b[37m";
+const LINK: &str = "\x1b[36m";
+
+/// Render Markdown text for the terminal using ANSI escape codes.
+///
+/// Every output line is prefixed with `indent`, so the result can be nested
+/// under a section header. Only the subset of Markdown that Shortcut's
+/// editor produces is styled; anything else is passed through as plain text.
+pub fn render_ansi(markdown: &str, indent: &str) -> String {
+    let mut renderer = AnsiRenderer::new(indent);
+    let parser = Parser::new_ext(
+        markdown,
+        Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS | Options::ENABLE_TABLES,
+    );
+    for event in parser {
+        renderer.event(event);
+    }
+    renderer.finish()
+}
+
+struct AnsiRenderer<'a> {
+    indent: &'a str,
+    output: String,
+    line: String,
+    /// Active inline styles, re-applied after every reset
+    styles: Vec<&'static str>,
+    /// One entry per open list: `Some(n)` for ordered lists, `None` for bullets
+    lists: Vec<Option<u64>>,
+    quote_depth: usize,
+    in_code_block: bool,
+    link_urls: Vec<String>,
+}
+
+impl<'a> AnsiRenderer<'a> {
+    fn new(indent: &'a str) -> Self {
+        Self {
+            indent,
+            output: String::new(),
+            line: String::new(),
+            styles: Vec::new(),
+            lists: Vec::new(),
+            quote_depth: 0,
+            in_code_block: false,
+            link_urls: Vec::new(),
+        }
+    }
+
+    fn event(&mut self, event: Event) {
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) if self.in_code_block => {
+                for line in text.lines() {
+                    self.line.push_str(&format!("    {CODE}{line}{RESET}"));
+                    self.flush_line();
+                }
+            }
+            Event::Text(text) => self.line.push_str(&text),
+            Event::Code(code) => {
+                self.line.push_str(&format!("{CODE}{code}{RESET}"));
+                self.restore_styles();
+            }
+            Event::Html(html) | Event::InlineHtml(html) => self.line.push_str(&html),
+            Event::SoftBreak => self.line.push(' '),
+            Event::HardBreak => self.flush_line(),
+            Event::Rule => {
+                self.flush_line();
+                self.line.push_str(&"─".repeat(40));
+                self.flush_line();
+                self.blank_line();
+            }
+            Event::TaskListMarker(checked) => {
+                self.line.push_str(if checked { "[x] " } else { "[ ] " });
+            }
+            _ => {}
+        }
+    }
+
+    fn start(&mut self, tag: Tag) {
+        match tag {
+            Tag::Heading { level, .. } => {
+                self.flush_line();
+                self.push_style(HEADING);
+                if !matches!(level, HeadingLevel::H1 | HeadingLevel::H2) {
+                    self.line.push_str("▸ ");
+                }
+            }
+            Tag::BlockQuote(_) => {
+                self.flush_line();
+                self.quote_depth += 1;
+                self.push_style(QUOTE);
+            }
+            Tag::CodeBlock(_) => {
+                self.flush_line();
+                self.in_code_block = true;
+            }
+            Tag::List(start) => {
+                self.flush_line();
+                self.lists.push(start);
+            }
+            Tag::Item => {
+                self.flush_line();
+                let depth = self.lists.len().saturating_sub(1);
+                let bullet = match self.lists.last_mut() {
+                    Some(Some(n)) => {
+                        let bullet = format!("{n}. ");
+                        *n += 1;
+                        bullet
+                    }
+                    _ => "• ".to_string(),
+                };
+                self.line.push_str(&"  ".repeat(depth));
+                self.line.push_str(&bullet);
+            }
+            Tag::Emphasis => self.push_style(ITALIC),
+            Tag::Strong => self.push_style(BOLD),
+            Tag::Strikethrough => self.push_style(STRIKE),
+            Tag::Link { dest_url, .. } => {
+                self.push_style(UNDERLINE);
+                self.link_urls.push(dest_url.to_string());
+            }
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Paragraph => {
+                self.flush_line();
+                if self.lists.is_empty() {
+                    self.blank_line();
+                }
+            }
+            TagEnd::Heading(_) => {
+                self.pop_style();
+                self.flush_line();
+                self.blank_line();
+            }
+            TagEnd::BlockQuote(_) => {
+                self.pop_style();
+                self.flush_line();
+                self.quote_depth = self.quote_depth.saturating_sub(1);
+            }
+            TagEnd::CodeBlock => {
+                self.in_code_block = false;
+                self.blank_line();
+            }
+            TagEnd::List(_) => {
+                self.flush_line();
+                self.lists.pop();
+                if self.lists.is_empty() {
+                    self.blank_line();
+                }
+            }
+            TagEnd::Item => self.flush_line(),
+            TagEnd::Emphasis | TagEnd::Strong | TagEnd::Strikethrough => self.pop_style(),
+            TagEnd::Link => {
+                self.pop_style();
+                if let Some(url) = self.link_urls.pop() {
+                    self.line.push_str(&format!(" {LINK}({url}){RESET}"));
+                    self.restore_styles();
+                }
+            }
+            TagEnd::TableHead | TagEnd::TableRow => self.flush_line(),
+            TagEnd::TableCell => self.line.push_str(" │ "),
+            _ => {}
+        }
+    }
+
+    fn push_style(&mut self, style: &'static str) {
+        self.styles.push(style);
+        self.line.push_str(style);
+    }
+
+    fn pop_style(&mut self) {
+        self.styles.pop();
+        self.line.push_str(RESET);
+        self.restore_styles();
+    }
+
+    fn restore_styles(&mut self) {
+        for style in &self.styles {
+            self.line.push_str(style);
+        }
+    }
+
+    fn flush_line(&mut self) {
+        let line = std::mem::take(&mut self.line);
+        if !has_visible_text(&line) {
+            // Only style codes so far; keep the active styles for the next line
+            self.restore_styles();
+            return;
+        }
+        self.output.push_str(self.indent);
+        if self.quote_depth > 0 {
+            self.output.push_str(&"│ ".repeat(self.quote_depth));
+        }
+        self.output.push_str(&line);
+        if !self.styles.is_empty() {
+            self.output.push_str(RESET);
+        }
+        self.output.push('\n');
+        self.restore_styles();
+    }
+
+    fn blank_line(&mut self) {
+        if !self.output.is_empty() && !self.output.ends_with("\n\n") {
+            self.output.push('\n');
+        }
+    }
+
+    fn finish(mut self) -> String {
+        self.flush_line();
+        while self.output.ends_with("\n\n") {
+            self.output.pop();
+        }
+        self.output
+    }
+}
+
+/// Check whether a line contains anything besides ANSI escape sequences
+fn has_visible_text(line: &str) -> bool {
+    let mut in_escape = false;
+    for c in line.chars() {
+        match c {
+            '\x1b' => in_escape = true,
+            'm' if in_escape => in_escape = false,
+            _ if in_escape => {}
+            _ => return true,
+        }
+    }
+    false
+}
+
+/// Remove ANSI escape sequences, for output that is not going to a terminal
+pub fn strip_ansi(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            for c in chars.by_ref() {
+                if c == 'm' {
+                    break;
+                }
+            }
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_plain_paragraphs() {
+        let output = render_ansi("First line\nsame paragraph\n\nSecond paragraph", "");
+        assert_eq!(
+            strip_ansi(&output),
+            "First line same paragraph\n\nSecond paragraph\n"
+        );
+    }
+
+    #[test]
+    fn test_render_applies_indent() {
+        let output = render_ansi("One\n\nTwo", "   ");
+        assert_eq!(strip_ansi(&output), "   One\n\n   Two\n");
+    }
+
+    #[test]
+    fn test_render_heading_is_styled() {
+        let output = render_ansi("## Acceptance criteria", "");
+        assert!(output.contains(HEADING));
+        assert_eq!(strip_ansi(&output), "Acceptance criteria\n");
+    }
+
+    #[test]
+    fn test_render_lists() {
+        let output = render_ansi("- one\n- two\n  1. nested\n  2. again", "");
+        assert_eq!(
+            strip_ansi(&output),
+            "• one\n• two\n  1. nested\n  2. again\n"
+        );
+    }
+
+    #[test]
+    fn test_render_task_list() {
+        let output = render_ansi("- [x] done\n- [ ] todo", "");
+        assert_eq!(strip_ansi(&output), "• [x] done\n• [ ] todo\n");
+    }
+
+    #[test]
+    fn test_render_inline_styles() {
+        let output = render_ansi("some **bold** and *italic* and `code`", "");
+        assert!(output.contains(BOLD));
+        assert!(output.contains(ITALIC));
+        assert!(output.contains(CODE));
+        assert_eq!(strip_ansi(&output), "some bold and italic and code\n");
+    }
+
+    #[test]
+    fn test_render_link_shows_url() {
+        let output = render_ansi("see [the docs](https://example.com)", "");
+        assert_eq!(strip_ansi(&output), "see the docs (https://example.com)\n");
+    }
+
+    #[test]
+    fn test_render_code_block() {
+        let output = render_ansi("```\nlet x = 1;\nlet y = 2;\n```", "");
+        assert_eq!(strip_ansi(&output), "    let x = 1;\n    let y = 2;\n");
+    }
+
+    #[test]
+    fn test_render_block_quote() {
+        let output = render_ansi("> quoted text", "");
+        assert_eq!(strip_ansi(&output), "│ quoted text\n");
+    }
+
+    #[test]
+    fn test_strip_ansi() {
+        assert_eq!(strip_ansi("\x1b[1;36mtitle\x1b[0m plain"), "title plain");
+        assert_eq!(strip_ansi("no codes"), "no codes");
+    }
+
+    #[test]
+    fn test_render_empty() {
+        assert_eq!(render_ansi("", "  "), "");
+    }
+}
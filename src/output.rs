@@ -0,0 +1,421 @@
+use crate::api::{Epic, Story, Workflow};
+use crate::workflow;
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::io::Write;
+
+/// Output format for story listings
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum OutputFormat {
+    Text,
+    Json,
+    Ndjson,
+    Csv,
+    Markdown,
+}
+
+/// Flat representation of a story for machine-readable output.
+///
+/// Field names follow `api::Story`; the `*_name(s)` fields hold the values
+/// resolved from workflow, member and epic IDs.
+#[derive(Debug, Clone, Serialize)]
+pub struct StoryRecord {
+    pub id: i64,
+    pub name: String,
+    pub story_type: String,
+    pub workflow_state_id: i64,
+    pub state_name: Option<String>,
+    pub owner_ids: Vec<String>,
+    pub owner_names: Vec<String>,
+    pub epic_id: Option<i64>,
+    pub epic_name: Option<String>,
+    pub labels: Vec<String>,
+    pub app_url: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub completed_at: Option<String>,
+    pub moved_at: Option<String>,
+    pub description: String,
+}
+
+impl StoryRecord {
+    pub fn new(
+        story: &Story,
+        workflows: &[Workflow],
+        epics: &[Epic],
+        member_cache: &HashMap<String, String>,
+    ) -> Self {
+        Self {
+            id: story.id,
+            name: story.name.clone(),
+            story_type: story.story_type.clone(),
+            workflow_state_id: story.workflow_state_id,
+            state_name: workflow::find_state(workflows, story.workflow_state_id)
+                .map(|s| s.name.clone()),
+            owner_ids: story.owner_ids.clone(),
+            owner_names: story
+                .owner_ids
+                .iter()
+                .map(|id| member_cache.get(id).cloned().unwrap_or_else(|| id.clone()))
+                .collect(),
+            epic_id: story.epic_id,
+            epic_name: story
+                .epic_id
+                .and_then(|epic_id| epics.iter().find(|e| e.id == epic_id))
+                .map(|e| e.name.clone()),
+            labels: story.labels.iter().map(|l| l.name.clone()).collect(),
+            app_url: story.app_url.clone(),
+            created_at: story.created_at.clone(),
+            updated_at: story.updated_at.clone(),
+            completed_at: story.completed_at.clone(),
+            moved_at: story.moved_at.clone(),
+            description: story.description.clone(),
+        }
+    }
+
+    fn state(&self) -> String {
+        self.state_name
+            .clone()
+            .unwrap_or_else(|| self.workflow_state_id.to_string())
+    }
+}
+
+const CSV_HEADER: [&str; 14] = [
+    "id",
+    "name",
+    "story_type",
+    "workflow_state_id",
+    "state_name",
+    "owner_ids",
+    "owner_names",
+    "epic_id",
+    "epic_name",
+    "labels",
+    "app_url",
+    "created_at",
+    "updated_at",
+    "completed_at",
+];
+
+/// Writes story records one at a time so large result sets can be streamed
+pub struct StoryWriter<W: Write> {
+    out: W,
+    format: OutputFormat,
+    count: usize,
+}
+
+impl<W: Write> StoryWriter<W> {
+    /// Create a writer and emit any header the format needs
+    pub fn new(mut out: W, format: OutputFormat) -> Result<Self> {
+        match format {
+            OutputFormat::Json => write!(out, "[")?,
+            OutputFormat::Csv => writeln!(out, "{}", CSV_HEADER.join(","))?,
+            OutputFormat::Markdown => {
+                writeln!(out, "| ID | Name | Type | State | Owners | Epic | URL |")?;
+                writeln!(out, "|---:|------|------|-------|--------|------|-----|")?;
+            }
+            OutputFormat::Text | OutputFormat::Ndjson => {}
+        }
+        Ok(Self {
+            out,
+            format,
+            count: 0,
+        })
+    }
+
+    pub fn write(&mut self, record: &StoryRecord) -> Result<()> {
+        match self.format {
+            OutputFormat::Text => {
+                let owners = if record.owner_names.is_empty() {
+                    "Unassigned".to_string()
+                } else {
+                    record.owner_names.join(", ")
+                };
+                writeln!(
+                    self.out,
+                    "#{}\t{}\t{}\t{}\t{}",
+                    record.id,
+                    record.state(),
+                    record.story_type,
+                    owners,
+                    record.name
+                )?;
+            }
+            OutputFormat::Json => {
+                let separator = if self.count == 0 { "\n  " } else { ",\n  " };
+                let json = serde_json::to_string(record).context("Failed to serialize story")?;
+                write!(self.out, "{separator}{json}")?;
+            }
+            OutputFormat::Ndjson => {
+                let json = serde_json::to_string(record).context("Failed to serialize story")?;
+                writeln!(self.out, "{json}")?;
+            }
+            OutputFormat::Csv => {
+                let fields = [
+                    record.id.to_string(),
+                    record.name.clone(),
+                    record.story_type.clone(),
+                    record.workflow_state_id.to_string(),
+                    record.state_name.clone().unwrap_or_default(),
+                    record.owner_ids.join(";"),
+                    record.owner_names.join(";"),
+                    record.epic_id.map(|id| id.to_string()).unwrap_or_default(),
+                    record.epic_name.clone().unwrap_or_default(),
+                    record.labels.join(";"),
+                    record.app_url.clone(),
+                    record.created_at.clone(),
+                    record.updated_at.clone(),
+                    record.completed_at.clone().unwrap_or_default(),
+                ];
+                let row: Vec<String> = fields.iter().map(|f| csv_escape(f)).collect();
+                writeln!(self.out, "{}", row.join(","))?;
+            }
+            OutputFormat::Markdown => {
+                writeln!(
+                    self.out,
+                    "| {} | {} | {} | {} | {} | {} | {} |",
+                    record.id,
+                    markdown_escape(&record.name),
+                    record.story_type,
+                    markdown_escape(&record.state()),
+                    markdown_escape(&record.owner_names.join(", ")),
+                    markdown_escape(record.epic_name.as_deref().unwrap_or("")),
+                    record.app_url
+                )?;
+            }
+        }
+        self.count += 1;
+        // Flush per record so consumers of a pipe see results as they arrive
+        self.out.flush()?;
+        Ok(())
+    }
+
+    /// Emit any footer the format needs and return the number of records written
+    pub fn finish(mut self) -> Result<usize> {
+        if self.format == OutputFormat::Json {
+            if self.count == 0 {
+                writeln!(self.out, "]")?;
+            } else {
+                writeln!(self.out, "\n]")?;
+            }
+        }
+        self.out.flush()?;
+        Ok(self.count)
+    }
+}
+
+/// Write a table of already formatted cells as CSV, a Markdown table or, for
+/// any other format, plain text columns padded to their widest cell
+pub fn write_table<W: Write>(
+    out: &mut W,
+    format: OutputFormat,
+    header: &[&str],
+    rows: &[Vec<String>],
+) -> Result<()> {
+    match format {
+        OutputFormat::Csv => {
+            writeln!(out, "{}", header.join(","))?;
+            for row in rows {
+                let row: Vec<String> = row.iter().map(|f| csv_escape(f)).collect();
+                writeln!(out, "{}", row.join(","))?;
+            }
+        }
+        OutputFormat::Markdown => {
+            writeln!(out, "| {} |", header.join(" | "))?;
+            let rule: Vec<String> = header.iter().map(|h| "-".repeat(h.len().max(3))).collect();
+            writeln!(out, "|{}|", rule.join("|"))?;
+            for row in rows {
+                let row: Vec<String> = row.iter().map(|f| markdown_escape(f)).collect();
+                writeln!(out, "| {} |", row.join(" | "))?;
+            }
+        }
+        OutputFormat::Text | OutputFormat::Json | OutputFormat::Ndjson => {
+            let mut widths: Vec<usize> = header.iter().map(|h| h.chars().count()).collect();
+            for row in rows {
+                for (width, cell) in widths.iter_mut().zip(row) {
+                    *width = (*width).max(cell.chars().count());
+                }
+            }
+            let header: Vec<String> = header.iter().map(|h| h.to_string()).collect();
+            for row in std::iter::once(&header).chain(rows) {
+                let cells: Vec<String> = row
+                    .iter()
+                    .zip(&widths)
+                    .map(|(cell, &width)| format!("{cell:<width$}"))
+                    .collect();
+                writeln!(out, "{}", cells.join("  ").trim_end())?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Quote a CSV field when it contains a delimiter, quote or line break
+fn csv_escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Keep a value on a single Markdown table row
+fn markdown_escape(value: &str) -> String {
+    value.replace('|', "\\|").replace(['\r', '\n'], " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::{Label, WorkflowState};
+    use pretty_assertions::assert_eq;
+
+    fn create_test_story(id: i64, name: &str) -> Story {
+        Story {
+            id,
+            name: name.to_string(),
+            description: "Line one\nLine two".to_string(),
+            workflow_state_id: 500,
+            app_url: format!("https://app.shortcut.com/org/story/{id}"),
+            story_type: "feature".to_string(),
+            labels: vec![Label {
+                id: 1,
+                name: "api".to_string(),
+                color: String::new(),
+            }],
+            owner_ids: vec!["user-1".to_string()],
+            position: 1000,
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+            updated_at: "2024-01-02T00:00:00Z".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn create_test_workflows() -> Vec<Workflow> {
+        vec![Workflow {
+            id: 1,
+            name: "Engineering".to_string(),
+            states: vec![WorkflowState {
+                id: 500,
+                name: "In Progress".to_string(),
+                color: String::new(),
+                position: 1,
+                state_type: "started".to_string(),
+            }],
+        }]
+    }
+
+    fn record(id: i64, name: &str) -> StoryRecord {
+        let members = HashMap::from([("user-1".to_string(), "Alice (alice)".to_string())]);
+        StoryRecord::new(
+            &create_test_story(id, name),
+            &create_test_workflows(),
+            &[],
+            &members,
+        )
+    }
+
+    fn render(format: OutputFormat, records: &[StoryRecord]) -> String {
+        let mut buffer = Vec::new();
+        let mut writer = StoryWriter::new(&mut buffer, format).unwrap();
+        for record in records {
+            writer.write(record).unwrap();
+        }
+        assert_eq!(writer.finish().unwrap(), records.len());
+        String::from_utf8(buffer).unwrap()
+    }
+
+    #[test]
+    fn test_record_resolves_names() {
+        let record = record(1, "Story");
+        assert_eq!(record.state_name.as_deref(), Some("In Progress"));
+        assert_eq!(record.owner_names, vec!["Alice (alice)"]);
+        assert_eq!(record.labels, vec!["api"]);
+    }
+
+    #[test]
+    fn test_json_is_a_valid_array() {
+        let output = render(OutputFormat::Json, &[record(1, "One"), record(2, "Two")]);
+        let value: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(value.as_array().unwrap().len(), 2);
+        assert_eq!(value[1]["id"], 2);
+        assert_eq!(value[0]["state_name"], "In Progress");
+        assert_eq!(value[0]["owner_names"][0], "Alice (alice)");
+    }
+
+    #[test]
+    fn test_json_empty() {
+        let output = render(OutputFormat::Json, &[]);
+        let value: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert!(value.as_array().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_ndjson_one_object_per_line() {
+        let output = render(OutputFormat::Ndjson, &[record(1, "One"), record(2, "Two")]);
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 2);
+        for (line, id) in lines.iter().zip([1, 2]) {
+            let value: serde_json::Value = serde_json::from_str(line).unwrap();
+            assert_eq!(value["id"], id);
+        }
+    }
+
+    #[test]
+    fn test_csv_header_and_quoting() {
+        let output = render(OutputFormat::Csv, &[record(1, "Fix \"quotes\", commas")]);
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines[0], CSV_HEADER.join(","));
+        assert!(lines[1].starts_with("1,\"Fix \"\"quotes\"\", commas\",feature,500,In Progress,"));
+    }
+
+    #[test]
+    fn test_markdown_table() {
+        let output = render(OutputFormat::Markdown, &[record(7, "Pipe | in name")]);
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines[2],
+            "| 7 | Pipe \\| in name | feature | In Progress | Alice (alice) |  | https://app.shortcut.com/org/story/7 |"
+        );
+    }
+
+    #[test]
+    fn test_text_is_plain() {
+        let output = render(OutputFormat::Text, &[record(3, "Plain")]);
+        assert_eq!(output, "#3\tIn Progress\tfeature\tAlice (alice)\tPlain\n");
+    }
+
+    #[test]
+    fn test_write_table() {
+        let rows = vec![
+            vec!["42".to_string(), "Fix, login".to_string()],
+            vec!["7".to_string(), "a|b".to_string()],
+        ];
+        let render = |format| {
+            let mut out = Vec::new();
+            write_table(&mut out, format, &["ID", "Name"], &rows).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+        assert_eq!(
+            render(OutputFormat::Csv),
+            "ID,Name\n42,\"Fix, login\"\n7,a|b\n"
+        );
+        assert_eq!(
+            render(OutputFormat::Markdown),
+            "| ID | Name |\n|---|----|\n| 42 | Fix, login |\n| 7 | a\\|b |\n"
+        );
+        assert_eq!(
+            render(OutputFormat::Text),
+            "ID  Name\n42  Fix, login\n7   a|b\n"
+        );
+    }
+
+    #[test]
+    fn test_csv_escape() {
+        assert_eq!(csv_escape("plain"), "plain");
+        assert_eq!(csv_escape("a,b"), "\"a,b\"");
+        assert_eq!(csv_escape("line\nbreak"), "\"line\nbreak\"");
+    }
+}
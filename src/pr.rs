@@ -0,0 +1,259 @@
+use crate::api::Story;
+use std::collections::HashMap;
+
+/// Repository file that replaces the default pull request layout
+pub const TEMPLATE_PATH: &str = ".sc-cli/pr_template.md";
+
+/// Headings that start the acceptance criteria section of a description
+const CRITERIA_HEADINGS: [&str; 2] = ["acceptance criteria", "acceptance criterion"];
+
+/// Pull request title for a story, tagged so Shortcut links the PR to it
+pub fn title(story: &Story) -> String {
+    format!("{} [sc-{}]", story.name.trim(), story.id)
+}
+
+/// Level and text of a heading line. `#` headings rank by their depth; a line
+/// that is entirely bold or ends with a colon ranks below all of them.
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let line = line.trim();
+    let hashes = line.chars().take_while(|&c| c == '#').count();
+    if hashes > 0 && line[hashes..].starts_with(' ') {
+        return Some((hashes, line[hashes..].trim()));
+    }
+    if line.len() > 4 && line.starts_with("**") && line.ends_with("**") {
+        return Some((7, line.trim_matches('*').trim()));
+    }
+    if !line.starts_with(['-', '*', '+']) && line.len() > 1 && line.ends_with(':') {
+        return Some((7, line.trim_end_matches(':').trim()));
+    }
+    None
+}
+
+fn is_criteria_heading(text: &str) -> bool {
+    let text = text.trim_end_matches(':').trim().to_lowercase();
+    CRITERIA_HEADINGS.contains(&text.as_str())
+}
+
+/// Split a story description into the text before and after its acceptance
+/// criteria, and the criteria themselves (without their heading). The section
+/// runs until the next heading of the same or a higher level.
+pub fn split_acceptance_criteria(description: &str) -> (String, String) {
+    let lines: Vec<&str> = description.lines().collect();
+    let Some((start, level)) = lines.iter().enumerate().find_map(|(index, line)| {
+        heading(line)
+            .filter(|(_, text)| is_criteria_heading(text))
+            .map(|(level, _)| (index, level))
+    }) else {
+        return (description.trim().to_string(), String::new());
+    };
+
+    let end = lines[start + 1..]
+        .iter()
+        .position(|line| heading(line).is_some_and(|(next, _)| next <= level && next < 7))
+        .map_or(lines.len(), |offset| start + 1 + offset);
+
+    let rest: Vec<&str> = lines[..start]
+        .iter()
+        .chain(&lines[end..])
+        .copied()
+        .collect();
+    (
+        rest.join("\n").trim().to_string(),
+        lines[start + 1..end].join("\n").trim().to_string(),
+    )
+}
+
+/// Incomplete tasks of the story as a markdown checklist, in story order
+pub fn open_tasks(story: &Story) -> String {
+    let mut tasks: Vec<_> = story.tasks.iter().filter(|task| !task.complete).collect();
+    tasks.sort_by_key(|task| task.position);
+    tasks
+        .iter()
+        .map(|task| format!("- [ ] {}", task.description.trim()))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The sections of a pull request description built from a story
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrBody {
+    pub title: String,
+    pub story_url: String,
+    pub description: String,
+    pub tasks: String,
+    pub acceptance_criteria: String,
+}
+
+impl PrBody {
+    pub fn new(story: &Story) -> Self {
+        let (description, acceptance_criteria) = split_acceptance_criteria(&story.description);
+        Self {
+            title: title(story),
+            story_url: story.app_url.clone(),
+            description,
+            tasks: open_tasks(story),
+            acceptance_criteria,
+        }
+    }
+
+    /// The default layout: title, story link, then the description, open
+    /// tasks and acceptance criteria, leaving out empty sections
+    pub fn to_markdown(&self) -> String {
+        let mut sections = vec![
+            format!("# {}", self.title),
+            format!("Story: {}", self.story_url),
+        ];
+        if !self.description.is_empty() {
+            sections.push(self.description.clone());
+        }
+        if !self.tasks.is_empty() {
+            sections.push(format!("## Tasks\n\n{}", self.tasks));
+        }
+        if !self.acceptance_criteria.is_empty() {
+            sections.push(format!(
+                "## Acceptance Criteria\n\n{}",
+                self.acceptance_criteria
+            ));
+        }
+        sections.join("\n\n") + "\n"
+    }
+
+    /// Add the sections to a story template context. `description` and
+    /// `tasks` replace the raw story fields of the same name.
+    pub fn extend_context(&self, context: &mut HashMap<String, String>) {
+        let fields = [
+            ("title", &self.title),
+            ("story_url", &self.story_url),
+            ("description", &self.description),
+            ("tasks", &self.tasks),
+            ("acceptance_criteria", &self.acceptance_criteria),
+        ];
+        for (key, value) in fields {
+            context.insert(key.to_string(), value.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::Task;
+    use pretty_assertions::assert_eq;
+
+    fn create_test_story(description: &str) -> Story {
+        Story {
+            id: 42,
+            name: "Fix login bug ".to_string(),
+            description: description.to_string(),
+            workflow_state_id: 500,
+            app_url: "https://app.shortcut.com/org/story/42".to_string(),
+            story_type: "bug".to_string(),
+            position: 1000,
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+            updated_at: "2024-01-02T00:00:00Z".to_string(),
+            tasks: vec![
+                Task {
+                    id: 2,
+                    description: "Add a test".to_string(),
+                    complete: false,
+                    position: 2,
+                },
+                Task {
+                    id: 1,
+                    description: "Reproduce".to_string(),
+                    complete: true,
+                    position: 1,
+                },
+                Task {
+                    id: 3,
+                    description: "Fix the redirect".to_string(),
+                    complete: false,
+                    position: 1,
+                },
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_split_markdown_heading() {
+        let description = "Users cannot log in.\n\n## Acceptance Criteria\n\n- Login works\n- No 500s\n\n## Notes\n\nSee logs";
+        let (body, criteria) = split_acceptance_criteria(description);
+        assert_eq!(body, "Users cannot log in.\n\n## Notes\n\nSee logs");
+        assert_eq!(criteria, "- Login works\n- No 500s");
+    }
+
+    #[test]
+    fn test_split_keeps_subheadings_in_section() {
+        let description = "# Acceptance criteria\n### Web\n- works\n# Other\nrest";
+        let (body, criteria) = split_acceptance_criteria(description);
+        assert_eq!(body, "# Other\nrest");
+        assert_eq!(criteria, "### Web\n- works");
+    }
+
+    #[test]
+    fn test_split_bold_and_colon_headings() {
+        let (body, criteria) =
+            split_acceptance_criteria("Intro\n\n**Acceptance Criteria**\n- one\n- two");
+        assert_eq!(body, "Intro");
+        assert_eq!(criteria, "- one\n- two");
+
+        let (body, criteria) = split_acceptance_criteria("Intro\nAcceptance criteria:\n- one");
+        assert_eq!(body, "Intro");
+        assert_eq!(criteria, "- one");
+    }
+
+    #[test]
+    fn test_split_without_criteria() {
+        let (body, criteria) = split_acceptance_criteria("  Just a description\n");
+        assert_eq!(body, "Just a description");
+        assert_eq!(criteria, "");
+    }
+
+    #[test]
+    fn test_open_tasks_in_order() {
+        let story = create_test_story("");
+        assert_eq!(
+            open_tasks(&story),
+            "- [ ] Fix the redirect\n- [ ] Add a test"
+        );
+    }
+
+    #[test]
+    fn test_default_layout() {
+        let story =
+            create_test_story("Users cannot log in.\n\n## Acceptance Criteria\n\n- Login works");
+        assert_eq!(
+            PrBody::new(&story).to_markdown(),
+            "# Fix login bug [sc-42]\n\n\
+             Story: https://app.shortcut.com/org/story/42\n\n\
+             Users cannot log in.\n\n\
+             ## Tasks\n\n- [ ] Fix the redirect\n- [ ] Add a test\n\n\
+             ## Acceptance Criteria\n\n- Login works\n"
+        );
+    }
+
+    #[test]
+    fn test_default_layout_skips_empty_sections() {
+        let mut story = create_test_story("");
+        story.tasks.clear();
+        assert_eq!(
+            PrBody::new(&story).to_markdown(),
+            "# Fix login bug [sc-42]\n\nStory: https://app.shortcut.com/org/story/42\n"
+        );
+    }
+
+    #[test]
+    fn test_extend_context_replaces_story_fields() {
+        let story = create_test_story("Body\n\nAcceptance Criteria:\n- works");
+        let mut context = HashMap::from([
+            ("description".to_string(), story.description.clone()),
+            ("id".to_string(), "42".to_string()),
+        ]);
+        PrBody::new(&story).extend_context(&mut context);
+        assert_eq!(context["description"], "Body");
+        assert_eq!(context["acceptance_criteria"], "- works");
+        assert_eq!(context["title"], "Fix login bug [sc-42]");
+        assert_eq!(context["id"], "42");
+    }
+}
@@ -0,0 +1,367 @@
+use crate::api::{Epic, Story};
+use crate::config::WeekConfig;
+use crate::output::{self, OutputFormat};
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
+
+/// Start and end of the `weeks` weeks ending with the one containing `now`
+pub fn weeks(week: &WeekConfig, now: DateTime<Utc>, weeks: u32) -> (DateTime<Utc>, DateTime<Utc>) {
+    let start = week.start_of_week(now);
+    let from = start - Duration::weeks(i64::from(weeks.max(1)) - 1);
+    (from, start + Duration::weeks(1))
+}
+
+/// Search query for the stories completed between `from` and `to`. Dates are
+/// widened by a day on both ends, `Report::new` drops what falls outside.
+pub fn query(from: &DateTime<Utc>, to: &DateTime<Utc>) -> String {
+    format!(
+        "completed:{}..{} is:story",
+        (*from - Duration::days(1)).format("%Y-%m-%d"),
+        to.format("%Y-%m-%d")
+    )
+}
+
+fn parse_date(date: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(date)
+        .ok()
+        .map(|date| date.with_timezone(&Utc))
+}
+
+/// Time from the start of a story to its completion
+fn cycle_time(story: &Story) -> Option<Duration> {
+    let started = parse_date(story.started_at.as_deref()?)?;
+    let completed = parse_date(story.completed_at.as_deref()?)?;
+    Some(completed - started)
+}
+
+/// Completed stories and points for an owner, story type or epic
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReportRow {
+    pub name: String,
+    pub stories: usize,
+    pub points: i64,
+    /// Cycle times of the stories that have a start date
+    pub cycle_times: Vec<Duration>,
+}
+
+impl ReportRow {
+    fn new(name: &str, stories: &[&Story]) -> Self {
+        let mut cycle_times: Vec<Duration> = stories.iter().filter_map(|s| cycle_time(s)).collect();
+        cycle_times.sort();
+        Self {
+            name: name.to_string(),
+            stories: stories.len(),
+            points: stories.iter().filter_map(|story| story.estimate).sum(),
+            cycle_times,
+        }
+    }
+
+    pub fn median_cycle_time(&self) -> Option<Duration> {
+        let len = self.cycle_times.len();
+        match len {
+            0 => None,
+            _ if len % 2 == 1 => Some(self.cycle_times[len / 2]),
+            _ => Some((self.cycle_times[len / 2 - 1] + self.cycle_times[len / 2]) / 2),
+        }
+    }
+
+    pub fn average_cycle_time(&self) -> Option<Duration> {
+        let total = self.cycle_times.iter().copied().reduce(|a, b| a + b)?;
+        Some(total / i32::try_from(self.cycle_times.len()).ok()?)
+    }
+
+    fn cells(&self, group: &str, format: OutputFormat) -> Vec<String> {
+        let days = |duration: Option<Duration>| {
+            duration.map_or_else(String::new, |d| {
+                format!("{:.1}", d.num_minutes() as f64 / (24.0 * 60.0))
+            })
+        };
+        let mut cells = vec![
+            self.name.clone(),
+            self.stories.to_string(),
+            self.points.to_string(),
+            days(self.median_cycle_time()),
+            days(self.average_cycle_time()),
+        ];
+        if format == OutputFormat::Csv {
+            cells.insert(0, group.to_string());
+        }
+        cells
+    }
+}
+
+/// Stories completed in a period, grouped by owner, story type and epic
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    /// Weeks covered, e.g. `week of 2024-01-15`
+    pub period: String,
+    pub total: ReportRow,
+    pub by_owner: Vec<ReportRow>,
+    pub by_type: Vec<ReportRow>,
+    pub by_epic: Vec<ReportRow>,
+}
+
+impl Report {
+    /// Stories with several owners count once for each of them
+    pub fn new(
+        stories: &[Story],
+        members: &HashMap<String, String>,
+        epics: &[Epic],
+        week: &WeekConfig,
+        (from, to): (DateTime<Utc>, DateTime<Utc>),
+    ) -> Self {
+        let completed: Vec<&Story> = stories
+            .iter()
+            .filter(|story| {
+                story
+                    .completed_at
+                    .as_deref()
+                    .and_then(parse_date)
+                    .is_some_and(|at| at >= from && at < to)
+            })
+            .collect();
+
+        let mut by_owner: BTreeMap<String, Vec<&Story>> = BTreeMap::new();
+        let mut by_type: BTreeMap<String, Vec<&Story>> = BTreeMap::new();
+        let mut by_epic: BTreeMap<String, Vec<&Story>> = BTreeMap::new();
+        for &story in &completed {
+            if story.owner_ids.is_empty() {
+                by_owner
+                    .entry("Unassigned".to_string())
+                    .or_default()
+                    .push(story);
+            }
+            for owner_id in &story.owner_ids {
+                let name = members.get(owner_id).unwrap_or(owner_id);
+                by_owner.entry(name.clone()).or_default().push(story);
+            }
+            by_type
+                .entry(story.story_type.clone())
+                .or_default()
+                .push(story);
+            let epic = story
+                .epic_id
+                .map(|id| {
+                    epics
+                        .iter()
+                        .find(|epic| epic.id == id)
+                        .map_or_else(|| format!("Epic #{id}"), |epic| epic.name.clone())
+                })
+                .unwrap_or_else(|| "No epic".to_string());
+            by_epic.entry(epic).or_default().push(story);
+        }
+
+        let rows = |groups: BTreeMap<String, Vec<&Story>>| {
+            let mut rows: Vec<ReportRow> = groups
+                .iter()
+                .map(|(name, stories)| ReportRow::new(name, stories))
+                .collect();
+            rows.sort_by(|a, b| b.stories.cmp(&a.stories).then(b.points.cmp(&a.points)));
+            rows
+        };
+        let last_week = to - Duration::weeks(1);
+        let period = if last_week <= from {
+            format!("week of {}", week.date(from))
+        } else {
+            format!("weeks of {} to {}", week.date(from), week.date(last_week))
+        };
+        Self {
+            period,
+            total: ReportRow::new("Total", &completed),
+            by_owner: rows(by_owner),
+            by_type: rows(by_type),
+            by_epic: rows(by_epic),
+        }
+    }
+
+    /// Write the report as text or Markdown tables, or as a single CSV table
+    /// with a column naming the grouping
+    pub fn write<W: Write>(&self, out: &mut W, format: OutputFormat) -> Result<()> {
+        let sections = [
+            ("Owner", &self.by_owner),
+            ("Type", &self.by_type),
+            ("Epic", &self.by_epic),
+        ];
+        let columns = [
+            "Stories",
+            "Points",
+            "Median cycle (days)",
+            "Average cycle (days)",
+        ];
+
+        if format == OutputFormat::Csv {
+            let header: Vec<&str> = ["Group", "Name"].iter().chain(&columns).copied().collect();
+            let rows: Vec<Vec<String>> = sections
+                .iter()
+                .flat_map(|(group, rows)| rows.iter().map(|row| row.cells(group, format)))
+                .chain(std::iter::once(self.total.cells("Total", format)))
+                .collect();
+            return output::write_table(out, format, &header, &rows);
+        }
+
+        let title = format!("Completed work, {}", self.period);
+        let summary = format!(
+            "{} stories, {} points",
+            self.total.stories, self.total.points
+        );
+        if format == OutputFormat::Markdown {
+            writeln!(out, "# {title}\n\n{summary}")?;
+        } else {
+            writeln!(out, "{title}\n{summary}")?;
+        }
+        for (group, rows) in sections {
+            if rows.is_empty() {
+                continue;
+            }
+            if format == OutputFormat::Markdown {
+                writeln!(out, "\n## By {}\n", group.to_lowercase())?;
+            } else {
+                writeln!(out, "\nBy {}:", group.to_lowercase())?;
+            }
+            let header: Vec<&str> = std::iter::once(&group).chain(&columns).copied().collect();
+            let cells: Vec<Vec<String>> = rows.iter().map(|row| row.cells(group, format)).collect();
+            output::write_table(out, format, &header, &cells)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn at(date: &str) -> DateTime<Utc> {
+        date.parse().unwrap()
+    }
+
+    fn create_test_story(id: i64, owner: &str, completed_at: &str) -> Story {
+        Story {
+            id,
+            name: format!("Story {id}"),
+            workflow_state_id: 3,
+            story_type: "feature".to_string(),
+            owner_ids: vec![owner.to_string()],
+            position: 1000,
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+            updated_at: completed_at.to_string(),
+            completed_at: Some(completed_at.to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn utc_week() -> WeekConfig {
+        WeekConfig {
+            timezone: crate::config::WeekTimezone::Utc,
+            ..WeekConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_weeks() {
+        let week = utc_week();
+        let now = at("2024-01-17T12:00:00Z");
+        assert_eq!(
+            weeks(&week, now, 1),
+            (at("2024-01-15T00:00:00Z"), at("2024-01-22T00:00:00Z"))
+        );
+        assert_eq!(weeks(&week, now, 3).0, at("2024-01-01T00:00:00Z"));
+        assert_eq!(
+            query(&at("2024-01-15T00:00:00Z"), &at("2024-01-22T00:00:00Z")),
+            "completed:2024-01-14..2024-01-22 is:story"
+        );
+    }
+
+    #[test]
+    fn test_report_groups_and_cycle_times() {
+        let mut first = create_test_story(1, "alice", "2024-01-16T12:00:00Z");
+        first.estimate = Some(3);
+        first.started_at = Some("2024-01-15T12:00:00Z".to_string());
+        first.epic_id = Some(7);
+        let mut second = create_test_story(2, "alice", "2024-01-17T00:00:00Z");
+        second.estimate = Some(5);
+        second.started_at = Some("2024-01-14T00:00:00Z".to_string());
+        second.story_type = "bug".to_string();
+        let mut third = create_test_story(3, "bob", "2024-01-18T00:00:00Z");
+        third.owner_ids.push("alice".to_string());
+        let outside = create_test_story(4, "bob", "2024-01-12T00:00:00Z");
+
+        let members = HashMap::from([("alice".to_string(), "Alice".to_string())]);
+        let epics = vec![Epic {
+            id: 7,
+            name: "Checkout".to_string(),
+            description: String::new(),
+            app_url: String::new(),
+            state: "in progress".to_string(),
+            owner_ids: vec![],
+            created_at: String::new(),
+            updated_at: String::new(),
+        }];
+        let report = Report::new(
+            &[first, second, third, outside],
+            &members,
+            &epics,
+            &utc_week(),
+            (at("2024-01-15T00:00:00Z"), at("2024-01-22T00:00:00Z")),
+        );
+
+        assert_eq!(report.total.stories, 3);
+        assert_eq!(report.total.points, 8);
+        assert_eq!(report.total.median_cycle_time(), Some(Duration::hours(48)));
+        assert_eq!(report.total.average_cycle_time(), Some(Duration::hours(48)));
+
+        let names = |rows: &[ReportRow]| {
+            rows.iter()
+                .map(|row| (row.name.clone(), row.stories))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            names(&report.by_owner),
+            vec![("Alice".to_string(), 3), ("bob".to_string(), 1)]
+        );
+        assert_eq!(
+            names(&report.by_type),
+            vec![("feature".to_string(), 2), ("bug".to_string(), 1)]
+        );
+        assert_eq!(
+            names(&report.by_epic),
+            vec![("No epic".to_string(), 2), ("Checkout".to_string(), 1)]
+        );
+    }
+
+    #[test]
+    fn test_write_report() {
+        let mut story = create_test_story(1, "alice", "2024-01-16T12:00:00Z");
+        story.estimate = Some(2);
+        story.started_at = Some("2024-01-15T00:00:00Z".to_string());
+        let report = Report::new(
+            &[story],
+            &HashMap::new(),
+            &[],
+            &utc_week(),
+            (at("2024-01-15T00:00:00Z"), at("2024-01-22T00:00:00Z")),
+        );
+
+        let mut out = Vec::new();
+        report.write(&mut out, OutputFormat::Csv).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "Group,Name,Stories,Points,Median cycle (days),Average cycle (days)\n\
+             Owner,alice,1,2,1.5,1.5\n\
+             Type,feature,1,2,1.5,1.5\n\
+             Epic,No epic,1,2,1.5,1.5\n\
+             Total,Total,1,2,1.5,1.5\n"
+        );
+
+        let mut out = Vec::new();
+        report.write(&mut out, OutputFormat::Markdown).unwrap();
+        let markdown = String::from_utf8(out).unwrap();
+        assert!(
+            markdown.starts_with("# Completed work, week of 2024-01-15\n\n1 stories, 2 points\n")
+        );
+        assert!(markdown.contains("## By owner\n\n| Owner | Stories |"));
+        assert!(markdown.contains("| alice | 1 | 2 | 1.5 | 1.5 |"));
+    }
+}
@@ -0,0 +1,316 @@
+use crate::api::{Story, Workflow};
+use crate::workflow;
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, TimeZone, Utc, Weekday};
+use serde::Serialize;
+use std::fmt::Write;
+
+/// The workday before `today`: Friday on Mondays and weekends
+pub fn previous_workday(today: NaiveDate) -> NaiveDate {
+    let days_back = match today.weekday() {
+        Weekday::Mon => 3,
+        Weekday::Sun => 2,
+        _ => 1,
+    };
+    today - Duration::days(days_back)
+}
+
+/// Midnight, local time, at the start of the previous workday
+pub fn since(now: &DateTime<Local>) -> DateTime<Utc> {
+    let midnight = previous_workday(now.date_naive())
+        .and_hms_opt(0, 0, 0)
+        .unwrap_or_default();
+    Local
+        .from_local_datetime(&midnight)
+        .earliest()
+        .map_or_else(|| midnight.and_utc(), |start| start.with_timezone(&Utc))
+}
+
+/// Search query for the stories a member owns that changed since `since`
+pub fn query(mention_name: &str, since: &DateTime<Utc>) -> String {
+    format!(
+        "owner:{mention_name} updated:{}..* is:story",
+        since.with_timezone(&Local).format("%Y-%m-%d")
+    )
+}
+
+fn is_after(date: &str, since: &DateTime<Utc>) -> bool {
+    DateTime::parse_from_rfc3339(date).is_ok_and(|date| date >= *since)
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StandupItem {
+    pub id: i64,
+    pub name: String,
+    pub state: String,
+    pub app_url: String,
+    /// Comments the member wrote since the previous workday
+    pub comments: Vec<String>,
+    /// Subjects of the member's commits since the previous workday
+    pub commits: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Standup {
+    pub since: DateTime<Utc>,
+    pub done: Vec<StandupItem>,
+    pub in_progress: Vec<StandupItem>,
+    pub blocked: Vec<StandupItem>,
+}
+
+impl Standup {
+    /// Sort the member's stories that were moved or updated since `since`.
+    /// Blocked stories go to Blocked whatever their state; stories that have
+    /// not been started yet are left out.
+    pub fn new(
+        stories: &[Story],
+        workflows: &[Workflow],
+        member_id: &str,
+        since: DateTime<Utc>,
+    ) -> Self {
+        let mut standup = Self {
+            since,
+            done: Vec::new(),
+            in_progress: Vec::new(),
+            blocked: Vec::new(),
+        };
+
+        let mut stories: Vec<&Story> = stories
+            .iter()
+            .filter(|story| {
+                is_after(&story.updated_at, &since)
+                    || story
+                        .moved_at
+                        .as_deref()
+                        .is_some_and(|at| is_after(at, &since))
+            })
+            .collect();
+        stories.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+
+        for story in stories {
+            let state = workflow::find_state(workflows, story.workflow_state_id);
+            let item = StandupItem {
+                id: story.id,
+                name: story.name.clone(),
+                state: state.map(|s| s.name.clone()).unwrap_or_default(),
+                app_url: story.app_url.clone(),
+                comments: story
+                    .comments
+                    .iter()
+                    .filter(|c| c.author_id == member_id && is_after(&c.created_at, &since))
+                    .map(|c| c.text.trim().to_string())
+                    .collect(),
+                commits: story
+                    .commits
+                    .iter()
+                    .filter(|c| {
+                        c.author_id.as_deref() == Some(member_id)
+                            && c.created_at
+                                .as_deref()
+                                .is_some_and(|at| is_after(at, &since))
+                    })
+                    .map(|c| c.message.lines().next().unwrap_or_default().to_string())
+                    .collect(),
+            };
+
+            match state.map(|s| s.state_type.as_str()) {
+                _ if story.blocked => standup.blocked.push(item),
+                Some("done") => standup.done.push(item),
+                Some("unstarted") => {}
+                _ => standup.in_progress.push(item),
+            }
+        }
+        standup
+    }
+
+    fn sections(&self) -> [(&str, &[StandupItem]); 3] {
+        [
+            ("Done", &self.done),
+            ("In progress", &self.in_progress),
+            ("Blocked", &self.blocked),
+        ]
+    }
+
+    fn title(&self) -> String {
+        format!(
+            "Standup: since {}",
+            self.since.with_timezone(&Local).format("%A %Y-%m-%d")
+        )
+    }
+
+    /// Markdown for pasting into chat, leaving out empty sections
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!("## {}\n", self.title());
+        for (title, items) in self.sections() {
+            if items.is_empty() {
+                continue;
+            }
+            let _ = write!(out, "\n**{title}**\n");
+            for item in items {
+                let _ = writeln!(out, "- [sc-{}]({}) {}", item.id, item.app_url, item.name);
+                for comment in &item.comments {
+                    let _ = writeln!(out, "  - 💬 {}", comment.replace('\n', " "));
+                }
+                for commit in &item.commits {
+                    let _ = writeln!(out, "  - `{commit}`");
+                }
+            }
+        }
+        if self.is_empty() {
+            out.push_str("\nNo story activity.\n");
+        }
+        out
+    }
+
+    /// Plain text for the terminal, with the state of each story
+    pub fn to_text(&self) -> String {
+        let mut out = format!("{}\n", self.title());
+        for (title, items) in self.sections() {
+            if items.is_empty() {
+                continue;
+            }
+            let _ = write!(out, "\n{title}:\n");
+            for item in items {
+                let _ = writeln!(out, "  #{} {} [{}]", item.id, item.name, item.state);
+                for comment in &item.comments {
+                    let _ = writeln!(out, "      💬 {}", comment.replace('\n', " "));
+                }
+                for commit in &item.commits {
+                    let _ = writeln!(out, "      • {commit}");
+                }
+            }
+        }
+        if self.is_empty() {
+            out.push_str("\nNo story activity.\n");
+        }
+        out
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.done.is_empty() && self.in_progress.is_empty() && self.blocked.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::{Comment, Commit, WorkflowState};
+    use pretty_assertions::assert_eq;
+
+    fn date(date: &str) -> NaiveDate {
+        date.parse().unwrap()
+    }
+
+    fn create_test_story(id: i64, state_id: i64, updated_at: &str) -> Story {
+        Story {
+            id,
+            name: format!("Story {id}"),
+            workflow_state_id: state_id,
+            app_url: format!("https://app.shortcut.com/org/story/{id}"),
+            story_type: "feature".to_string(),
+            owner_ids: vec!["me".to_string()],
+            position: 1000,
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+            updated_at: updated_at.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn create_test_workflows() -> Vec<Workflow> {
+        let state = |id: i64, name: &str, state_type: &str| WorkflowState {
+            id,
+            name: name.to_string(),
+            color: String::new(),
+            position: id,
+            state_type: state_type.to_string(),
+        };
+        vec![Workflow {
+            id: 1,
+            name: "Engineering".to_string(),
+            states: vec![
+                state(1, "Backlog", "unstarted"),
+                state(2, "In Progress", "started"),
+                state(3, "Done", "done"),
+            ],
+        }]
+    }
+
+    fn comment(author_id: &str, text: &str, created_at: &str) -> Comment {
+        Comment {
+            id: 1,
+            text: text.to_string(),
+            author_id: author_id.to_string(),
+            created_at: created_at.to_string(),
+            updated_at: created_at.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_previous_workday() {
+        assert_eq!(previous_workday(date("2024-01-09")), date("2024-01-08"));
+        // Monday and the weekend look back to Friday
+        assert_eq!(previous_workday(date("2024-01-08")), date("2024-01-05"));
+        assert_eq!(previous_workday(date("2024-01-07")), date("2024-01-05"));
+        assert_eq!(previous_workday(date("2024-01-06")), date("2024-01-05"));
+    }
+
+    #[test]
+    fn test_stories_are_grouped_by_state() {
+        let since: DateTime<Utc> = "2024-01-08T00:00:00Z".parse().unwrap();
+        let mut done = create_test_story(1, 3, "2024-01-08T17:00:00Z");
+        done.comments = vec![
+            comment("me", "Shipped\nto prod", "2024-01-08T16:00:00Z"),
+            comment("someone", "Thanks!", "2024-01-08T16:30:00Z"),
+            comment("me", "Old note", "2024-01-05T10:00:00Z"),
+        ];
+        done.commits = vec![Commit {
+            id: 1,
+            hash: "abc1234".to_string(),
+            message: "Fix login redirect\n\nDetails".to_string(),
+            url: String::new(),
+            author_id: Some("me".to_string()),
+            created_at: Some("2024-01-08T15:00:00Z".to_string()),
+        }];
+        let in_progress = create_test_story(2, 2, "2024-01-08T09:00:00Z");
+        let mut blocked = create_test_story(3, 2, "2024-01-01T09:00:00Z");
+        blocked.blocked = true;
+        blocked.moved_at = Some("2024-01-08T11:00:00Z".to_string());
+        let stale = create_test_story(4, 2, "2024-01-05T09:00:00Z");
+        let unstarted = create_test_story(5, 1, "2024-01-08T09:00:00Z");
+
+        let standup = Standup::new(
+            &[done, in_progress, blocked, stale, unstarted],
+            &create_test_workflows(),
+            "me",
+            since,
+        );
+
+        let ids = |items: &[StandupItem]| items.iter().map(|i| i.id).collect::<Vec<_>>();
+        assert_eq!(ids(&standup.done), vec![1]);
+        assert_eq!(ids(&standup.in_progress), vec![2]);
+        assert_eq!(ids(&standup.blocked), vec![3]);
+        assert_eq!(standup.done[0].comments, vec!["Shipped\nto prod"]);
+        assert_eq!(standup.done[0].commits, vec!["Fix login redirect"]);
+    }
+
+    #[test]
+    fn test_markdown_and_text() {
+        let since: DateTime<Utc> = "2024-01-08T00:00:00Z".parse().unwrap();
+        let mut story = create_test_story(1, 2, "2024-01-08T17:00:00Z");
+        story.comments = vec![comment("me", "Halfway there", "2024-01-08T16:00:00Z")];
+        let standup = Standup::new(&[story], &create_test_workflows(), "me", since);
+
+        let markdown = standup.to_markdown();
+        assert!(markdown.contains(
+            "**In progress**\n- [sc-1](https://app.shortcut.com/org/story/1) Story 1\n  - 💬 Halfway there\n"
+        ));
+        assert!(!markdown.contains("**Done**"));
+
+        let text = standup.to_text();
+        assert!(
+            text.contains("In progress:\n  #1 Story 1 [In Progress]\n      💬 Halfway there\n")
+        );
+
+        let empty = Standup::new(&[], &create_test_workflows(), "me", since);
+        assert!(empty.to_markdown().ends_with("No story activity.\n"));
+    }
+}
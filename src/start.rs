@@ -0,0 +1,424 @@
+use crate::api::{CurrentMember, ShortcutApi, Story, Workflow};
+use crate::git::GitBackend;
+use crate::git::operations::{self, GitBranchRequest, GitOperation};
+use crate::workflow;
+use anyhow::Result;
+
+/// Which steps `sc-cli start` should perform
+#[derive(Debug, Clone, PartialEq)]
+pub struct StartOptions {
+    pub assign: bool,
+    pub move_to_started: bool,
+    /// Branch or worktree to create/check out; `None` skips the git step
+    pub git: Option<GitBranchRequest>,
+    pub comment: Option<String>,
+}
+
+/// A single action of the start workflow
+#[derive(Debug, Clone, PartialEq)]
+pub enum StartStep {
+    Git(GitBranchRequest),
+    Assign {
+        owner_ids: Vec<String>,
+        member_name: String,
+    },
+    Move {
+        state_id: i64,
+        state_name: String,
+    },
+    Comment(String),
+}
+
+impl StartStep {
+    /// Human readable description, used for `--dry-run`
+    pub fn describe(&self) -> String {
+        match self {
+            StartStep::Git(request) => match request.operation {
+                GitOperation::CreateBranch => {
+                    format!("Create or check out branch '{}'", request.branch_name)
+                }
+                GitOperation::CreateWorktree => format!(
+                    "Create worktree for '{}' at '{}'",
+                    request.branch_name, request.worktree_path
+                ),
+            },
+            StartStep::Assign { member_name, .. } => format!("Assign the story to {member_name}"),
+            StartStep::Move { state_name, .. } => format!("Move the story to '{state_name}'"),
+            StartStep::Comment(text) => format!("Comment: \"{text}\""),
+        }
+    }
+}
+
+/// Work out the steps needed to start a story, skipping the ones that are
+/// already done (the member already owns it, it is already started).
+///
+/// The git step comes first so a failing checkout leaves the story untouched.
+pub fn plan_start(
+    story: &Story,
+    workflows: &[Workflow],
+    member: &CurrentMember,
+    options: &StartOptions,
+) -> Vec<StartStep> {
+    let mut steps = Vec::new();
+
+    if let Some(request) = &options.git {
+        steps.push(StartStep::Git(request.clone()));
+    }
+
+    if options.assign && !story.owner_ids.contains(&member.id) {
+        let mut owner_ids = story.owner_ids.clone();
+        owner_ids.push(member.id.clone());
+        steps.push(StartStep::Assign {
+            owner_ids,
+            member_name: format!("{} ({})", member.name, member.mention_name),
+        });
+    }
+
+    if options.move_to_started {
+        let already_started = workflow::find_state(workflows, story.workflow_state_id)
+            .is_some_and(|s| s.state_type == "started");
+        if !already_started
+            && let Some(state) =
+                workflow::first_state_of_type(workflows, story.workflow_state_id, "started")
+        {
+            steps.push(StartStep::Move {
+                state_id: state.id,
+                state_name: state.name.clone(),
+            });
+        }
+    }
+
+    if let Some(text) = &options.comment {
+        steps.push(StartStep::Comment(text.clone()));
+    }
+
+    steps
+}
+
+/// Perform one step and return a message describing what was done
+pub fn run_step<C: ShortcutApi, G: GitBackend>(
+    client: &C,
+    git: &G,
+    story_id: i64,
+    step: &StartStep,
+) -> Result<String> {
+    match step {
+        StartStep::Git(request) => {
+            let result = operations::execute_checkout_or_create(git, request);
+            if !result.success {
+                anyhow::bail!(result.message);
+            }
+            Ok(result.message)
+        }
+        StartStep::Assign {
+            owner_ids,
+            member_name,
+        } => {
+            client.update_story(story_id, owner_ids.clone())?;
+            Ok(format!("Assigned to {member_name}"))
+        }
+        StartStep::Move {
+            state_id,
+            state_name,
+        } => {
+            client.update_story_state(story_id, *state_id)?;
+            Ok(format!("Moved to '{state_name}'"))
+        }
+        StartStep::Comment(text) => {
+            client.add_comment(story_id, text)?;
+            Ok("Comment posted".to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::{Epic, History, Iteration, Member, SearchStoriesResult, WorkflowState};
+    use crate::git::GixBackend;
+    use std::cell::RefCell;
+
+    /// Records the write calls made against the API
+    #[derive(Default)]
+    struct RecordingApi {
+        calls: RefCell<Vec<String>>,
+        fail_updates: bool,
+    }
+
+    impl ShortcutApi for RecordingApi {
+        fn search_stories(&self, _query: &str, _limit: Option<usize>) -> Result<Vec<Story>> {
+            unimplemented!()
+        }
+
+        fn search_stories_page(
+            &self,
+            _query: &str,
+            _next_token: Option<String>,
+        ) -> Result<SearchStoriesResult> {
+            unimplemented!()
+        }
+
+        fn get_workflows(&self) -> Result<Vec<Workflow>> {
+            Ok(create_test_workflows())
+        }
+
+        fn get_story(&self, _story_id: i64) -> Result<Story> {
+            Ok(create_test_story())
+        }
+
+        fn update_story_state(&self, story_id: i64, workflow_state_id: i64) -> Result<Story> {
+            if self.fail_updates {
+                anyhow::bail!("API error");
+            }
+            self.calls
+                .borrow_mut()
+                .push(format!("state {story_id} {workflow_state_id}"));
+            Ok(create_test_story())
+        }
+
+        fn get_current_member(&self) -> Result<CurrentMember> {
+            Ok(create_test_member())
+        }
+
+        fn update_story(&self, story_id: i64, owner_ids: Vec<String>) -> Result<Story> {
+            self.calls
+                .borrow_mut()
+                .push(format!("owners {story_id} {}", owner_ids.join(",")));
+            Ok(create_test_story())
+        }
+
+        fn update_story_details(
+            &self,
+            _story_id: i64,
+            _name: String,
+            _description: String,
+            _story_type: String,
+            _epic_id: Option<i64>,
+        ) -> Result<Story> {
+            unimplemented!()
+        }
+
+        fn get_members(&self) -> Result<Vec<Member>> {
+            Ok(vec![])
+        }
+
+        fn create_story(
+            &self,
+            _name: String,
+            _description: String,
+            _story_type: String,
+            _requested_by_id: String,
+            _workflow_state_id: i64,
+            _epic_id: Option<i64>,
+        ) -> Result<Story> {
+            unimplemented!()
+        }
+
+        fn get_epics(&self) -> Result<Vec<Epic>> {
+            Ok(vec![])
+        }
+
+        fn create_epic(&self, _name: String, _description: String) -> Result<Epic> {
+            unimplemented!()
+        }
+
+        fn add_comment(&self, story_id: i64, text: &str) -> Result<()> {
+            self.calls
+                .borrow_mut()
+                .push(format!("comment {story_id} {text}"));
+            Ok(())
+        }
+
+        fn get_epic_stories(&self, _epic_id: i64) -> Result<Vec<Story>> {
+            Ok(vec![])
+        }
+
+        fn get_iterations(&self) -> Result<Vec<Iteration>> {
+            Ok(vec![])
+        }
+
+        fn get_iteration_stories(&self, _iteration_id: i64) -> Result<Vec<Story>> {
+            Ok(vec![])
+        }
+
+        fn get_story_history(&self, _story_id: i64) -> Result<Vec<History>> {
+            Ok(vec![])
+        }
+    }
+
+    fn create_test_story() -> Story {
+        Story {
+            id: 42,
+            name: "Fix login".to_string(),
+            workflow_state_id: 100,
+            app_url: "https://app.shortcut.com/org/story/42".to_string(),
+            story_type: "bug".to_string(),
+            owner_ids: vec!["other".to_string()],
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+            updated_at: "2024-01-01T00:00:00Z".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn create_test_member() -> CurrentMember {
+        CurrentMember {
+            id: "me".to_string(),
+            name: "Test User".to_string(),
+            mention_name: "testuser".to_string(),
+        }
+    }
+
+    fn create_test_workflows() -> Vec<Workflow> {
+        let state = |id, name: &str, position, state_type: &str| WorkflowState {
+            id,
+            name: name.to_string(),
+            color: String::new(),
+            position,
+            state_type: state_type.to_string(),
+        };
+        vec![Workflow {
+            id: 1,
+            name: "Engineering".to_string(),
+            states: vec![
+                state(100, "Backlog", 0, "unstarted"),
+                state(102, "In Review", 2, "started"),
+                state(101, "In Development", 1, "started"),
+                state(103, "Done", 3, "done"),
+            ],
+        }]
+    }
+
+    fn all_steps() -> StartOptions {
+        StartOptions {
+            assign: true,
+            move_to_started: true,
+            git: Some(GitBranchRequest {
+                branch_name: "sc-42-fix-login".to_string(),
+                worktree_path: "../sc-42-fix-login".to_string(),
+                operation: GitOperation::CreateBranch,
+                story_id: 42,
+                base: None,
+            }),
+            comment: Some("Started".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_plan_start_all_steps_in_order() {
+        let steps = plan_start(
+            &create_test_story(),
+            &create_test_workflows(),
+            &create_test_member(),
+            &all_steps(),
+        );
+
+        assert_eq!(steps.len(), 4);
+        assert!(matches!(steps[0], StartStep::Git(_)));
+        assert_eq!(
+            steps[1],
+            StartStep::Assign {
+                owner_ids: vec!["other".to_string(), "me".to_string()],
+                member_name: "Test User (testuser)".to_string(),
+            }
+        );
+        assert_eq!(
+            steps[2],
+            StartStep::Move {
+                state_id: 101,
+                state_name: "In Development".to_string(),
+            }
+        );
+        assert_eq!(steps[3], StartStep::Comment("Started".to_string()));
+    }
+
+    #[test]
+    fn test_plan_start_skips_disabled_steps() {
+        let options = StartOptions {
+            assign: false,
+            move_to_started: false,
+            git: None,
+            comment: None,
+        };
+        let steps = plan_start(
+            &create_test_story(),
+            &create_test_workflows(),
+            &create_test_member(),
+            &options,
+        );
+        assert!(steps.is_empty());
+    }
+
+    #[test]
+    fn test_plan_start_skips_completed_steps() {
+        let mut story = create_test_story();
+        story.owner_ids.push("me".to_string());
+        story.workflow_state_id = 102;
+
+        let mut options = all_steps();
+        options.git = None;
+        options.comment = None;
+
+        let steps = plan_start(
+            &story,
+            &create_test_workflows(),
+            &create_test_member(),
+            &options,
+        );
+        assert!(steps.is_empty());
+    }
+
+    #[test]
+    fn test_describe_steps() {
+        let steps = plan_start(
+            &create_test_story(),
+            &create_test_workflows(),
+            &create_test_member(),
+            &all_steps(),
+        );
+        let descriptions: Vec<String> = steps.iter().map(|s| s.describe()).collect();
+        assert_eq!(
+            descriptions,
+            vec![
+                "Create or check out branch 'sc-42-fix-login'",
+                "Assign the story to Test User (testuser)",
+                "Move the story to 'In Development'",
+                "Comment: \"Started\"",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_run_api_steps() {
+        let api = RecordingApi::default();
+        let mut options = all_steps();
+        options.git = None;
+        let steps = plan_start(
+            &create_test_story(),
+            &create_test_workflows(),
+            &create_test_member(),
+            &options,
+        );
+
+        for step in &steps {
+            run_step(&api, &GixBackend::default(), 42, step).unwrap();
+        }
+
+        assert_eq!(
+            *api.calls.borrow(),
+            vec!["owners 42 other,me", "state 42 101", "comment 42 Started"]
+        );
+    }
+
+    #[test]
+    fn test_run_step_propagates_api_errors() {
+        let api = RecordingApi {
+            fail_updates: true,
+            ..Default::default()
+        };
+        let step = StartStep::Move {
+            state_id: 101,
+            state_name: "In Development".to_string(),
+        };
+        assert!(run_step(&api, &GixBackend::default(), 42, &step).is_err());
+    }
+}
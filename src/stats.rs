@@ -0,0 +1,533 @@
+use crate::api::{Epic, History, Story, Workflow};
+use crate::config::WeekConfig;
+use crate::history;
+use crate::output::{self, OutputFormat};
+use anyhow::Result;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
+
+/// Widest histogram bar, in characters
+const BAR_WIDTH: usize = 40;
+
+/// Upper bounds, in days, of the cycle time histogram buckets
+const BUCKETS: [i64; 6] = [1, 2, 4, 7, 14, 30];
+
+/// Search query for the stories completed in the last `weeks` weeks
+pub fn default_query(now: DateTime<Utc>, weeks: u32) -> String {
+    format!(
+        "completed:{}..* is:story",
+        (now - Duration::weeks(weeks.into())).format("%Y-%m-%d")
+    )
+}
+
+/// How the stats are broken down
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum StatsGroup {
+    Type,
+    Owner,
+    Epic,
+}
+
+fn parse_date(date: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(date)
+        .ok()
+        .map(|date| date.with_timezone(&Utc))
+}
+
+fn days(duration: Duration) -> f64 {
+    (duration.num_minutes() as f64 / (24.0 * 60.0) * 10.0).round() / 10.0
+}
+
+/// Lead and cycle time of a completed story. The cycle time runs from the
+/// first move into a started state to the last move into a done state, so a
+/// story reopened after completion counts until it is done again. Both come
+/// from the story's history, falling back to `started_at` and `completed_at`
+/// when the history doesn't record the move.
+#[derive(Debug, Clone, PartialEq)]
+struct StoryTimes {
+    completed: DateTime<Utc>,
+    lead: Duration,
+    cycle: Option<Duration>,
+}
+
+impl StoryTimes {
+    /// `state_types` maps workflow state IDs to their type
+    fn new(story: &Story, history: &[History], state_types: &HashMap<i64, &str>) -> Option<Self> {
+        let completed_at = parse_date(story.completed_at.as_deref()?)?;
+        let created = parse_date(&story.created_at)?;
+        let entered = |state_type: &'static str| {
+            history::story_changes(history)
+                .into_iter()
+                .filter(move |(_, changes)| {
+                    changes
+                        .workflow_state_id
+                        .as_ref()
+                        .and_then(|state| state.new)
+                        .is_some_and(|id| state_types.get(&id) == Some(&state_type))
+                })
+                .map(|(changed_at, _)| changed_at)
+        };
+        let started = entered("started")
+            .next()
+            .or_else(|| story.started_at.as_deref().and_then(parse_date));
+        let completed = entered("done").next_back().unwrap_or(completed_at);
+        Some(Self {
+            completed,
+            lead: completed - created,
+            cycle: started.map(|started| completed - started),
+        })
+    }
+}
+
+/// Nearest-rank percentile of already sorted durations
+fn percentile(sorted: &[Duration], p: usize) -> Option<Duration> {
+    let rank = (sorted.len() * p).div_ceil(100).max(1);
+    sorted.get(rank - 1).copied()
+}
+
+/// Percentiles of a set of durations, in days
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Percentiles {
+    pub stories: usize,
+    pub p50: f64,
+    pub p75: f64,
+    pub p85: f64,
+    pub p95: f64,
+}
+
+impl Percentiles {
+    fn new(mut durations: Vec<Duration>) -> Option<Self> {
+        durations.sort();
+        let at = |p| percentile(&durations, p).map(days);
+        Some(Self {
+            stories: durations.len(),
+            p50: at(50)?,
+            p75: at(75)?,
+            p85: at(85)?,
+            p95: at(95)?,
+        })
+    }
+}
+
+/// Lead and cycle time percentiles of a group of stories
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GroupStats {
+    pub name: String,
+    pub stories: usize,
+    pub lead_time: Option<Percentiles>,
+    pub cycle_time: Option<Percentiles>,
+}
+
+impl GroupStats {
+    fn new(name: &str, times: &[&StoryTimes]) -> Self {
+        Self {
+            name: name.to_string(),
+            stories: times.len(),
+            lead_time: Percentiles::new(times.iter().map(|t| t.lead).collect()),
+            cycle_time: Percentiles::new(times.iter().filter_map(|t| t.cycle).collect()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HistogramBucket {
+    pub label: String,
+    pub stories: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WeekThroughput {
+    pub week: NaiveDate,
+    pub stories: usize,
+}
+
+/// Lead time, cycle time and throughput of completed stories
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Stats {
+    pub overall: GroupStats,
+    pub groups: Vec<GroupStats>,
+    /// Stories per cycle time bucket
+    pub histogram: Vec<HistogramBucket>,
+    /// Stories completed per week, including weeks without any
+    pub throughput: Vec<WeekThroughput>,
+}
+
+impl Stats {
+    /// Stories that are not completed are left out. A story with several
+    /// owners counts in the group of each of them. `histories` holds the
+    /// history of each story by ID, and `workflows` tell which states are
+    /// started and done.
+    pub fn new(
+        stories: &[Story],
+        histories: &HashMap<i64, Vec<History>>,
+        workflows: &[Workflow],
+        group: StatsGroup,
+        members: &HashMap<String, String>,
+        epics: &[Epic],
+        week: &WeekConfig,
+    ) -> Self {
+        let state_types: HashMap<i64, &str> = workflows
+            .iter()
+            .flat_map(|workflow| &workflow.states)
+            .map(|state| (state.id, state.state_type.as_str()))
+            .collect();
+        let completed: Vec<(&Story, StoryTimes)> = stories
+            .iter()
+            .filter_map(|story| {
+                let history = histories.get(&story.id).map_or(&[][..], Vec::as_slice);
+                Some((story, StoryTimes::new(story, history, &state_types)?))
+            })
+            .collect();
+        let all: Vec<&StoryTimes> = completed.iter().map(|(_, times)| times).collect();
+
+        let mut groups: BTreeMap<String, Vec<&StoryTimes>> = BTreeMap::new();
+        for (story, times) in &completed {
+            for name in group_names(story, group, members, epics) {
+                groups.entry(name).or_default().push(times);
+            }
+        }
+        let mut groups: Vec<GroupStats> = groups
+            .iter()
+            .map(|(name, times)| GroupStats::new(name, times))
+            .collect();
+        groups.sort_by_key(|group| std::cmp::Reverse(group.stories));
+
+        let mut histogram: Vec<HistogramBucket> = BUCKETS
+            .iter()
+            .scan(0, |lower, &upper| {
+                let label = format!("{}-{upper}d", *lower);
+                *lower = upper;
+                Some(HistogramBucket { label, stories: 0 })
+            })
+            .chain(std::iter::once(HistogramBucket {
+                label: format!("{}d+", BUCKETS[BUCKETS.len() - 1]),
+                stories: 0,
+            }))
+            .collect();
+        for cycle in all.iter().filter_map(|times| times.cycle) {
+            let bucket = BUCKETS
+                .iter()
+                .position(|&upper| cycle < Duration::days(upper))
+                .unwrap_or(BUCKETS.len());
+            histogram[bucket].stories += 1;
+        }
+
+        let mut weeks: BTreeMap<NaiveDate, usize> = BTreeMap::new();
+        for times in &all {
+            *weeks
+                .entry(week.date(week.start_of_week(times.completed)))
+                .or_default() += 1;
+        }
+        let mut throughput = Vec::new();
+        if let (Some(&first), Some(&last)) = (weeks.keys().next(), weeks.keys().last()) {
+            let mut day = first;
+            while day <= last {
+                throughput.push(WeekThroughput {
+                    week: day,
+                    stories: weeks.get(&day).copied().unwrap_or_default(),
+                });
+                day += Duration::weeks(1);
+            }
+        }
+
+        Self {
+            overall: GroupStats::new("All", &all),
+            groups,
+            histogram,
+            throughput,
+        }
+    }
+
+    /// Percentile tables, the cycle time histogram and the weekly throughput
+    pub fn write_text<W: Write>(&self, out: &mut W, group: StatsGroup) -> Result<()> {
+        writeln!(out, "{} completed stories", self.overall.stories)?;
+        if self.overall.stories == 0 {
+            return Ok(());
+        }
+
+        writeln!(out, "\nDays          Stories  p50   p75   p85   p95")?;
+        for (name, percentiles) in [
+            ("Lead time", &self.overall.lead_time),
+            ("Cycle time", &self.overall.cycle_time),
+        ] {
+            match percentiles {
+                Some(p) => writeln!(
+                    out,
+                    "{name:<12}  {:<7}  {:<4}  {:<4}  {:<4}  {}",
+                    p.stories, p.p50, p.p75, p.p85, p.p95
+                )?,
+                None => writeln!(out, "{name:<12}  0")?,
+            }
+        }
+
+        let max = self.histogram.iter().map(|b| b.stories).max();
+        writeln!(out, "\nCycle time")?;
+        for bucket in &self.histogram {
+            writeln!(
+                out,
+                "  {:<7} {} {}",
+                bucket.label,
+                bar(bucket.stories, max),
+                bucket.stories
+            )?;
+        }
+
+        let max = self.throughput.iter().map(|w| w.stories).max();
+        writeln!(out, "\nThroughput per week")?;
+        for week in &self.throughput {
+            writeln!(
+                out,
+                "  {} {} {}",
+                week.week,
+                bar(week.stories, max),
+                week.stories
+            )?;
+        }
+
+        let label = match group {
+            StatsGroup::Type => "Type",
+            StatsGroup::Owner => "Owner",
+            StatsGroup::Epic => "Epic",
+        };
+        let p = |percentiles: &Option<Percentiles>, f: fn(&Percentiles) -> f64| {
+            percentiles
+                .as_ref()
+                .map(|p| f(p).to_string())
+                .unwrap_or_default()
+        };
+        let rows: Vec<Vec<String>> = self
+            .groups
+            .iter()
+            .map(|g| {
+                vec![
+                    g.name.clone(),
+                    g.stories.to_string(),
+                    p(&g.lead_time, |p| p.p50),
+                    p(&g.lead_time, |p| p.p85),
+                    p(&g.cycle_time, |p| p.p50),
+                    p(&g.cycle_time, |p| p.p85),
+                ]
+            })
+            .collect();
+        writeln!(out, "\nBy {}", label.to_lowercase())?;
+        output::write_table(
+            out,
+            OutputFormat::Text,
+            &[
+                label,
+                "Stories",
+                "Lead p50",
+                "Lead p85",
+                "Cycle p50",
+                "Cycle p85",
+            ],
+            &rows,
+        )
+    }
+}
+
+fn bar(count: usize, max: Option<usize>) -> String {
+    let width = match max {
+        Some(max) if max > 0 => (count * BAR_WIDTH).div_ceil(max),
+        _ => 0,
+    };
+    "█".repeat(width)
+}
+
+fn group_names(
+    story: &Story,
+    group: StatsGroup,
+    members: &HashMap<String, String>,
+    epics: &[Epic],
+) -> Vec<String> {
+    match group {
+        StatsGroup::Type => vec![story.story_type.clone()],
+        StatsGroup::Owner if story.owner_ids.is_empty() => vec!["Unassigned".to_string()],
+        StatsGroup::Owner => story
+            .owner_ids
+            .iter()
+            .map(|id| members.get(id).unwrap_or(id).clone())
+            .collect(),
+        StatsGroup::Epic => vec![match story.epic_id {
+            Some(id) => epics
+                .iter()
+                .find(|epic| epic.id == id)
+                .map_or_else(|| format!("Epic #{id}"), |epic| epic.name.clone()),
+            None => "No epic".to_string(),
+        }],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::WorkflowState;
+    use crate::config::WeekTimezone;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    fn create_test_story(id: i64, created_at: &str, completed_at: Option<&str>) -> Story {
+        Story {
+            id,
+            name: format!("Story {id}"),
+            workflow_state_id: 3,
+            story_type: "feature".to_string(),
+            owner_ids: vec!["alice".to_string()],
+            position: 1000,
+            created_at: created_at.to_string(),
+            updated_at: created_at.to_string(),
+            completed_at: completed_at.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    fn utc_week() -> WeekConfig {
+        WeekConfig {
+            timezone: WeekTimezone::Utc,
+            ..WeekConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_percentile() {
+        let sorted: Vec<Duration> = (1..=10).map(Duration::days).collect();
+        assert_eq!(percentile(&sorted, 50), Some(Duration::days(5)));
+        assert_eq!(percentile(&sorted, 85), Some(Duration::days(9)));
+        assert_eq!(percentile(&sorted, 95), Some(Duration::days(10)));
+        assert_eq!(percentile(&sorted[..1], 50), Some(Duration::days(1)));
+        assert_eq!(percentile(&[], 50), None);
+    }
+
+    #[test]
+    fn test_stats() {
+        let mut first = create_test_story(1, "2024-01-01T00:00:00Z", Some("2024-01-03T00:00:00Z"));
+        first.started_at = Some("2024-01-02T12:00:00Z".to_string());
+        let mut second = create_test_story(2, "2024-01-01T00:00:00Z", Some("2024-01-23T00:00:00Z"));
+        second.started_at = Some("2024-01-13T00:00:00Z".to_string());
+        second.story_type = "bug".to_string();
+        let third = create_test_story(3, "2024-01-20T00:00:00Z", Some("2024-01-24T00:00:00Z"));
+        let open = create_test_story(4, "2024-01-20T00:00:00Z", None);
+
+        let stats = Stats::new(
+            &[first, second, third, open],
+            &HashMap::new(),
+            &[],
+            StatsGroup::Type,
+            &HashMap::new(),
+            &[],
+            &utc_week(),
+        );
+
+        assert_eq!(stats.overall.stories, 3);
+        let lead = stats.overall.lead_time.unwrap();
+        assert_eq!((lead.p50, lead.p95), (4.0, 22.0));
+        let cycle = stats.overall.cycle_time.unwrap();
+        assert_eq!((cycle.stories, cycle.p50, cycle.p95), (2, 0.5, 10.0));
+
+        let histogram: Vec<usize> = stats.histogram.iter().map(|b| b.stories).collect();
+        assert_eq!(histogram, vec![1, 0, 0, 0, 1, 0, 0]);
+        assert_eq!(stats.histogram[4].label, "7-14d");
+        assert_eq!(stats.histogram[6].label, "30d+");
+
+        let throughput: Vec<(String, usize)> = stats
+            .throughput
+            .iter()
+            .map(|w| (w.week.to_string(), w.stories))
+            .collect();
+        assert_eq!(
+            throughput,
+            vec![
+                ("2024-01-01".to_string(), 1),
+                ("2024-01-08".to_string(), 0),
+                ("2024-01-15".to_string(), 0),
+                ("2024-01-22".to_string(), 2),
+            ]
+        );
+
+        let groups: Vec<(&str, usize)> = stats
+            .groups
+            .iter()
+            .map(|g| (g.name.as_str(), g.stories))
+            .collect();
+        assert_eq!(groups, vec![("feature", 2), ("bug", 1)]);
+    }
+
+    #[test]
+    fn test_cycle_time_from_history() {
+        // Reopened after the first completion, and `completed_at` was set a
+        // day after the last move to done
+        let story = create_test_story(1, "2024-01-01T00:00:00Z", Some("2024-01-09T00:00:00Z"));
+        let moved = |changed_at: &str, old: i64, new: i64| {
+            json!({
+                "id": changed_at,
+                "changed_at": changed_at,
+                "actions": [{
+                    "id": 1,
+                    "entity_type": "story",
+                    "action": "update",
+                    "changes": {"workflow_state_id": {"old": old, "new": new}}
+                }]
+            })
+        };
+        let history: Vec<History> = serde_json::from_value(json!([
+            moved("2024-01-02T00:00:00Z", 1, 2),
+            moved("2024-01-04T00:00:00Z", 2, 3),
+            moved("2024-01-05T00:00:00Z", 3, 2),
+            moved("2024-01-08T00:00:00Z", 2, 3),
+        ]))
+        .unwrap();
+        let state = |id, state_type: &str| WorkflowState {
+            id,
+            name: state_type.to_string(),
+            color: String::new(),
+            position: id,
+            state_type: state_type.to_string(),
+        };
+        let workflows = [Workflow {
+            id: 1,
+            name: "Engineering".to_string(),
+            states: vec![state(1, "unstarted"), state(2, "started"), state(3, "done")],
+        }];
+
+        let stats = Stats::new(
+            &[story],
+            &HashMap::from([(1, history)]),
+            &workflows,
+            StatsGroup::Type,
+            &HashMap::new(),
+            &[],
+            &utc_week(),
+        );
+
+        assert_eq!(stats.overall.lead_time.unwrap().p50, 7.0);
+        assert_eq!(stats.overall.cycle_time.unwrap().p50, 6.0);
+    }
+
+    #[test]
+    fn test_write_text() {
+        let mut story = create_test_story(1, "2024-01-01T00:00:00Z", Some("2024-01-03T00:00:00Z"));
+        story.started_at = Some("2024-01-02T00:00:00Z".to_string());
+        let members = HashMap::from([("alice".to_string(), "Alice".to_string())]);
+        let stats = Stats::new(
+            &[story],
+            &HashMap::new(),
+            &[],
+            StatsGroup::Owner,
+            &members,
+            &[],
+            &utc_week(),
+        );
+
+        let mut out = Vec::new();
+        stats.write_text(&mut out, StatsGroup::Owner).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.starts_with("1 completed stories\n"));
+        assert!(text.contains("Lead time     1        2     2     2     2\n"));
+        assert!(text.contains(&format!("  1-2d    {} 1\n", "█".repeat(BAR_WIDTH))));
+        assert!(text.contains("  2024-01-01 "));
+        assert!(text.contains("By owner\nOwner  Stories  Lead p50"));
+        assert!(text.contains("\nAlice  1        2"));
+    }
+}
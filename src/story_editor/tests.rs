@@ -10,19 +10,10 @@ fn test_story_editor_from_story() {
         workflow_state_id: 1,
         app_url: "https://example.com".to_string(),
         story_type: "feature".to_string(),
-        labels: vec![],
-        owner_ids: vec![],
         position: 1,
         created_at: "2023-01-01T00:00:00Z".to_string(),
         updated_at: "2023-01-01T00:00:00Z".to_string(),
-        completed_at: None,
-        moved_at: None,
-        comments: vec![],
-        formatted_vcs_branch_name: None,
-        epic_id: None,
-        branches: vec![],
-        pull_requests: vec![],
-        commits: vec![],
+        ..Default::default()
     };
 
     let editor = StoryEditor::from_story(&story);
@@ -0,0 +1,246 @@
+use anyhow::{Context, Result};
+use std::fmt;
+use std::str::FromStr;
+
+/// A reference to a Shortcut story as typed by a user.
+///
+/// Accepted forms:
+/// - a plain number: `42`
+/// - an `sc-` prefix in any case: `sc-42`, `SC-42`
+/// - a hash prefix: `#42`
+/// - a story URL: `https://app.shortcut.com/org/story/42/fix-login`
+/// - a branch or directory name containing `sc-42`: `edo/sc-42/fix-login`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StoryRef(i64);
+
+impl StoryRef {
+    pub fn id(self) -> i64 {
+        self.0
+    }
+
+    pub fn parse(input: &str) -> Result<Self> {
+        let trimmed = input.trim();
+        let digits = strip_prefix_ignore_case(trimmed, "sc-")
+            .or_else(|| trimmed.strip_prefix('#'))
+            .unwrap_or(trimmed);
+
+        if !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()) {
+            return digits.parse::<i64>().map(Self).context(format!(
+                "Invalid story ID '{input}'. The number is too large"
+            ));
+        }
+
+        if is_url(trimmed) {
+            return id_from_url(trimmed).map(Self).with_context(|| {
+                format!("Invalid story ID '{input}'. The URL does not point to a story")
+            });
+        }
+
+        if let Some(story_ref) = Self::from_name(trimmed) {
+            return Ok(story_ref);
+        }
+
+        anyhow::bail!(
+            "Invalid story ID '{input}'. Expected a number, 'sc-N', '#N', a story URL or a branch name containing 'sc-N'"
+        )
+    }
+
+    /// Find a story reference in a branch or directory name such as
+    /// `sc-42-fix-login`, `edo/sc-42/fix-login` or `feature/SC-42`. The `sc-`
+    /// marker must start a word so names like `misc-42` are not mistaken for a
+    /// story.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let lower = name.to_ascii_lowercase();
+        let bytes = lower.as_bytes();
+        let mut search_from = 0;
+
+        while let Some(offset) = lower[search_from..].find("sc-") {
+            let start = search_from + offset;
+            let at_word_start = start == 0 || !bytes[start - 1].is_ascii_alphanumeric();
+            let digits_len = bytes[start + 3..]
+                .iter()
+                .take_while(|b| b.is_ascii_digit())
+                .count();
+            let end = start + 3 + digits_len;
+            let at_word_end = bytes.get(end).is_none_or(|b| !b.is_ascii_alphanumeric());
+
+            if at_word_start
+                && at_word_end
+                && let Ok(id) = lower[start + 3..end].parse::<i64>()
+            {
+                return Some(Self(id));
+            }
+            search_from = start + 3;
+        }
+
+        None
+    }
+}
+
+impl FromStr for StoryRef {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for StoryRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "sc-{}", self.0)
+    }
+}
+
+fn strip_prefix_ignore_case<'a>(text: &'a str, prefix: &str) -> Option<&'a str> {
+    let head = text.get(..prefix.len())?;
+    head.eq_ignore_ascii_case(prefix)
+        .then(|| &text[prefix.len()..])
+}
+
+fn is_url(text: &str) -> bool {
+    let lower = text.to_ascii_lowercase();
+    lower.starts_with("http://")
+        || lower.starts_with("https://")
+        || lower.starts_with("app.shortcut.com/")
+}
+
+/// Extract the ID following the `story` path segment of a URL, ignoring any
+/// query string or fragment
+fn id_from_url(url: &str) -> Option<i64> {
+    let without_scheme = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let path = without_scheme.split(['?', '#']).next().unwrap_or_default();
+    let mut segments = path.split('/');
+    while let Some(segment) = segments.next() {
+        if segment.eq_ignore_ascii_case("story") {
+            return segments.next()?.parse().ok();
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn parse(input: &str) -> Option<i64> {
+        StoryRef::parse(input).ok().map(StoryRef::id)
+    }
+
+    #[test]
+    fn test_parse_plain_and_prefixed_ids() {
+        assert_eq!(parse("42"), Some(42));
+        assert_eq!(parse("  42\n"), Some(42));
+        assert_eq!(parse("sc-42"), Some(42));
+        assert_eq!(parse("SC-42"), Some(42));
+        assert_eq!(parse("#42"), Some(42));
+    }
+
+    #[test]
+    fn test_parse_urls() {
+        assert_eq!(
+            parse("https://app.shortcut.com/acme/story/42/fix-the-login"),
+            Some(42)
+        );
+        assert_eq!(parse("https://app.shortcut.com/acme/story/42"), Some(42));
+        assert_eq!(
+            parse("app.shortcut.com/acme/story/42?comment=7#activity"),
+            Some(42)
+        );
+        assert_eq!(parse("https://app.shortcut.com/acme/epic/42"), None);
+        assert_eq!(parse("https://app.shortcut.com/acme/story/abc"), None);
+    }
+
+    #[test]
+    fn test_parse_branch_names() {
+        assert_eq!(parse("sc-42-fix-login"), Some(42));
+        assert_eq!(parse("edo/sc-42/fix-login"), Some(42));
+        assert_eq!(parse("feature/SC-1234"), Some(1234));
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_input() {
+        for input in ["", "abc", "sc-", "#", "sc-42abc", "misc-42", "42a", "-5"] {
+            assert!(StoryRef::parse(input).is_err(), "accepted '{input}'");
+        }
+    }
+
+    #[test]
+    fn test_parse_error_message() {
+        let err = StoryRef::parse("not-a-number").unwrap_err();
+        assert!(err.to_string().contains("Invalid story ID 'not-a-number'"));
+
+        let err = StoryRef::parse("99999999999999999999").unwrap_err();
+        assert!(err.to_string().contains("too large"));
+    }
+
+    #[test]
+    fn test_from_name() {
+        assert_eq!(StoryRef::from_name("sc-42-fix-login"), Some(StoryRef(42)));
+        assert_eq!(
+            StoryRef::from_name("edo-sc-7-story-name"),
+            Some(StoryRef(7))
+        );
+        assert_eq!(StoryRef::from_name("misc-1/sc-99-real"), Some(StoryRef(99)));
+        assert_eq!(StoryRef::from_name("main"), None);
+        assert_eq!(StoryRef::from_name("misc-42-cleanup"), None);
+        assert_eq!(StoryRef::from_name("sc-abc"), None);
+        assert_eq!(StoryRef::from_name("sc-42abc"), None);
+        assert_eq!(StoryRef::from_name("sc-"), None);
+    }
+
+    #[test]
+    fn test_display_and_from_str() {
+        assert_eq!(StoryRef(42).to_string(), "sc-42");
+        assert_eq!("#42".parse::<StoryRef>().unwrap(), StoryRef(42));
+    }
+
+    proptest! {
+        #[test]
+        fn prop_accepts_every_id_form(id in 0i64..=i64::MAX, upper in any::<bool>()) {
+            let prefix = if upper { "SC-" } else { "sc-" };
+            prop_assert_eq!(parse(&id.to_string()), Some(id));
+            prop_assert_eq!(parse(&format!("{prefix}{id}")), Some(id));
+            prop_assert_eq!(parse(&format!("#{id}")), Some(id));
+        }
+
+        #[test]
+        fn prop_accepts_story_urls(
+            id in 0i64..=i64::MAX,
+            org in "[a-z0-9-]{1,12}",
+            slug in "(/[a-z0-9-]{0,30})?",
+            suffix in "(\\?[a-z=0-9&]{0,10})?(#[a-z]{0,8})?",
+        ) {
+            let url = format!("https://app.shortcut.com/{org}/story/{id}{slug}{suffix}");
+            prop_assert_eq!(parse(&url), Some(id));
+        }
+
+        #[test]
+        fn prop_accepts_branch_names(
+            id in 0i64..=i64::MAX,
+            owner in "([a-z]{1,8}/)?",
+            slug in "([-/][a-z0-9-]{0,30})?",
+        ) {
+            let branch = format!("{owner}sc-{id}{slug}");
+            prop_assert_eq!(parse(&branch), Some(id));
+            prop_assert_eq!(StoryRef::from_name(&branch), Some(StoryRef(id)));
+        }
+
+        #[test]
+        fn prop_display_round_trips(id in 0i64..=i64::MAX) {
+            let story_ref = StoryRef(id);
+            prop_assert_eq!(StoryRef::parse(&story_ref.to_string()).unwrap(), story_ref);
+        }
+
+        #[test]
+        fn prop_never_panics(input in "\\PC*") {
+            let _ = StoryRef::parse(&input);
+            let _ = StoryRef::from_name(&input);
+        }
+
+        #[test]
+        fn prop_ignores_surrounding_whitespace(id in 0i64..=i64::MAX, pad in "[ \t\n]{0,3}") {
+            prop_assert_eq!(parse(&format!("{pad}sc-{id}{pad}")), Some(id));
+        }
+    }
+}
@@ -0,0 +1,496 @@
+use crate::api::{Epic, PullRequest, Story, Workflow};
+use crate::markdown;
+use crate::output::OutputFormat;
+use crate::workflow;
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// A story together with the names resolved from its IDs
+#[derive(Debug, Serialize)]
+pub struct StoryDetails<'a> {
+    #[serde(flatten)]
+    pub story: &'a Story,
+    pub state_name: Option<String>,
+    pub owner_names: Vec<String>,
+    pub epic_name: Option<String>,
+    #[serde(skip)]
+    member_cache: &'a HashMap<String, String>,
+}
+
+impl<'a> StoryDetails<'a> {
+    pub fn new(
+        story: &'a Story,
+        workflows: &[Workflow],
+        epics: &[Epic],
+        member_cache: &'a HashMap<String, String>,
+    ) -> Self {
+        let state_name =
+            workflow::find_state(workflows, story.workflow_state_id).map(|s| s.name.clone());
+        let owner_names = story
+            .owner_ids
+            .iter()
+            .map(|id| member_cache.get(id).cloned().unwrap_or_else(|| id.clone()))
+            .collect();
+        let epic_name = story
+            .epic_id
+            .and_then(|epic_id| epics.iter().find(|e| e.id == epic_id))
+            .map(|e| e.name.clone());
+
+        Self {
+            story,
+            state_name,
+            owner_names,
+            epic_name,
+            member_cache,
+        }
+    }
+
+    fn state(&self) -> String {
+        self.state_name
+            .clone()
+            .unwrap_or_else(|| self.story.workflow_state_id.to_string())
+    }
+
+    fn member_name(&self, member_id: &str) -> String {
+        self.member_cache
+            .get(member_id)
+            .cloned()
+            .unwrap_or_else(|| member_id.to_string())
+    }
+
+    /// Render the story as JSON or markdown, or as text for any other format
+    pub fn render(&self, format: OutputFormat) -> Result<String> {
+        match format {
+            OutputFormat::Json => self.render_json(),
+            OutputFormat::Markdown => Ok(self.render_markdown()),
+            _ => Ok(self.render_text()),
+        }
+    }
+
+    /// Render the story as pretty-printed JSON
+    pub fn render_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).context("Failed to serialize story")
+    }
+
+    /// Render the story for the terminal with ANSI colors
+    pub fn render_text(&self) -> String {
+        let story = self.story;
+        let mut out = String::new();
+
+        let (type_emoji, type_color) = match story.story_type.as_str() {
+            "feature" => ("✨", "\x1b[32m"),
+            "bug" => ("🐞", "\x1b[31m"),
+            "chore" => ("⚙️", "\x1b[34m"),
+            _ => ("📝", "\x1b[37m"),
+        };
+
+        let _ = writeln!(out, "\x1b[1;36m⚡ #{} - {}\x1b[0m", story.id, story.name);
+        let _ = writeln!(
+            out,
+            "   📌 \x1b[35m{}\x1b[0m | {}{} {}\x1b[0m",
+            self.state(),
+            type_emoji,
+            type_color,
+            story.story_type
+        );
+        let owners = if self.owner_names.is_empty() {
+            "Unassigned".to_string()
+        } else {
+            self.owner_names.join(", ")
+        };
+        let _ = writeln!(out, "   \x1b[33m👤 Owner(s): {owners}\x1b[0m");
+        if let Some(epic_name) = &self.epic_name {
+            let _ = writeln!(out, "   \x1b[35m🏔️  Epic: {epic_name}\x1b[0m");
+        }
+        if !story.labels.is_empty() {
+            let labels: Vec<&str> = story.labels.iter().map(|l| l.name.as_str()).collect();
+            let _ = writeln!(out, "   🏷️  Labels: {}", labels.join(", "));
+        }
+        let _ = writeln!(out, "   \x1b[36m🔗 {}\x1b[0m", story.app_url);
+
+        let _ = writeln!(out, "\n\x1b[1m📄 Description\x1b[0m");
+        if story.description.trim().is_empty() {
+            let _ = writeln!(out, "   \x1b[37mNo description available\x1b[0m");
+        } else {
+            out.push_str(&markdown::render_ansi(&story.description, "   "));
+        }
+
+        if !story.branches.is_empty() {
+            let _ = writeln!(out, "\n\x1b[1m🌿 Branches\x1b[0m");
+            for branch in &story.branches {
+                let _ = writeln!(
+                    out,
+                    "   \x1b[32m{}\x1b[0m - \x1b[36m{}\x1b[0m",
+                    branch.name, branch.url
+                );
+            }
+        }
+
+        if !story.pull_requests.is_empty() {
+            let _ = writeln!(out, "\n\x1b[1m🔀 Pull Requests\x1b[0m");
+            for pr in &story.pull_requests {
+                let color = match pr_status(pr) {
+                    "merged" => "\x1b[35m",
+                    "closed" => "\x1b[31m",
+                    "draft" => "\x1b[33m",
+                    _ => "\x1b[32m",
+                };
+                let _ = writeln!(out, "   {} [{}{}\x1b[0m]", pr.title, color, pr_status(pr));
+                let _ = writeln!(out, "     \x1b[36m{}\x1b[0m", pr.url);
+            }
+        }
+
+        if !story.commits.is_empty() {
+            let _ = writeln!(out, "\n\x1b[1m📝 Commits\x1b[0m");
+            for commit in &story.commits {
+                let _ = writeln!(
+                    out,
+                    "   \x1b[33m{}\x1b[0m - {}",
+                    short_hash(&commit.hash),
+                    first_line(&commit.message)
+                );
+            }
+        }
+
+        if !story.comments.is_empty() {
+            let _ = writeln!(
+                out,
+                "\n\x1b[1m💬 Comments ({})\x1b[0m",
+                story.comments.len()
+            );
+            for comment in &story.comments {
+                let _ = writeln!(
+                    out,
+                    "\n   \x1b[1;36m{}\x1b[0m - \x1b[37m{}\x1b[0m",
+                    self.member_name(&comment.author_id),
+                    format_timestamp(&comment.created_at)
+                );
+                out.push_str(&markdown::render_ansi(&comment.text, "     "));
+            }
+        }
+
+        out
+    }
+
+    /// Render the story as a Markdown document
+    pub fn render_markdown(&self) -> String {
+        let story = self.story;
+        let mut out = String::new();
+
+        let _ = writeln!(out, "# [sc-{}] {}\n", story.id, story.name);
+        let _ = writeln!(out, "- **State:** {}", self.state());
+        let _ = writeln!(out, "- **Type:** {}", story.story_type);
+        let owners = if self.owner_names.is_empty() {
+            "Unassigned".to_string()
+        } else {
+            self.owner_names.join(", ")
+        };
+        let _ = writeln!(out, "- **Owners:** {owners}");
+        if let Some(epic_name) = &self.epic_name {
+            let _ = writeln!(out, "- **Epic:** {epic_name}");
+        }
+        if !story.labels.is_empty() {
+            let labels: Vec<&str> = story.labels.iter().map(|l| l.name.as_str()).collect();
+            let _ = writeln!(out, "- **Labels:** {}", labels.join(", "));
+        }
+        let _ = writeln!(out, "- **URL:** {}", story.app_url);
+
+        let _ = writeln!(out, "\n## Description\n");
+        if story.description.trim().is_empty() {
+            let _ = writeln!(out, "_No description available_");
+        } else {
+            let _ = writeln!(out, "{}", story.description.trim_end());
+        }
+
+        if !story.branches.is_empty() {
+            let _ = writeln!(out, "\n## Branches\n");
+            for branch in &story.branches {
+                let _ = writeln!(out, "- [{}]({})", branch.name, branch.url);
+            }
+        }
+
+        if !story.pull_requests.is_empty() {
+            let _ = writeln!(out, "\n## Pull Requests\n");
+            for pr in &story.pull_requests {
+                let _ = writeln!(out, "- [{}]({}) ({})", pr.title, pr.url, pr_status(pr));
+            }
+        }
+
+        if !story.commits.is_empty() {
+            let _ = writeln!(out, "\n## Commits\n");
+            for commit in &story.commits {
+                let _ = writeln!(
+                    out,
+                    "- [`{}`]({}) {}",
+                    short_hash(&commit.hash),
+                    commit.url,
+                    first_line(&commit.message)
+                );
+            }
+        }
+
+        if !story.comments.is_empty() {
+            let _ = writeln!(out, "\n## Comments");
+            for comment in &story.comments {
+                let _ = writeln!(
+                    out,
+                    "\n**{}** - {}\n",
+                    self.member_name(&comment.author_id),
+                    format_timestamp(&comment.created_at)
+                );
+                let _ = writeln!(out, "{}", comment.text.trim_end());
+            }
+        }
+
+        out
+    }
+}
+
+fn pr_status(pr: &PullRequest) -> &'static str {
+    if pr.merged {
+        "merged"
+    } else if pr.closed {
+        "closed"
+    } else if pr.draft {
+        "draft"
+    } else {
+        "open"
+    }
+}
+
+fn short_hash(hash: &str) -> &str {
+    hash.get(..7).unwrap_or(hash)
+}
+
+fn first_line(message: &str) -> &str {
+    message.lines().next().unwrap_or(message)
+}
+
+/// Format an RFC 3339 timestamp as local "YYYY-MM-DD HH:MM", falling back to the raw value
+fn format_timestamp(timestamp: &str) -> String {
+    chrono::DateTime::parse_from_rfc3339(timestamp)
+        .map(|dt| {
+            dt.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_else(|_| timestamp.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::{Branch, Comment, Commit, Label, WorkflowState};
+
+    fn create_test_story() -> Story {
+        Story {
+            id: 42,
+            name: "Fix login bug".to_string(),
+            description: "Users **cannot** log in.\n\n- step one\n- step two".to_string(),
+            workflow_state_id: 500,
+            app_url: "https://app.shortcut.com/org/story/42".to_string(),
+            story_type: "bug".to_string(),
+            labels: vec![Label {
+                id: 1,
+                name: "backend".to_string(),
+                color: "#ff0000".to_string(),
+            }],
+            owner_ids: vec!["user-1".to_string(), "user-unknown".to_string()],
+            position: 1000,
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+            updated_at: "2024-01-02T00:00:00Z".to_string(),
+            comments: vec![Comment {
+                id: 7,
+                text: "Looks good to me".to_string(),
+                author_id: "user-1".to_string(),
+                created_at: "2024-01-02T10:00:00Z".to_string(),
+                updated_at: "2024-01-02T10:00:00Z".to_string(),
+            }],
+            epic_id: Some(9),
+            branches: vec![Branch {
+                id: 1,
+                name: "sc-42-fix-login".to_string(),
+                url: "https://github.com/org/repo/tree/sc-42-fix-login".to_string(),
+                repository_id: None,
+                created_at: None,
+                updated_at: None,
+            }],
+            pull_requests: vec![PullRequest {
+                id: 1,
+                title: "Fix login".to_string(),
+                url: "https://github.com/org/repo/pull/3".to_string(),
+                number: Some(3),
+                draft: false,
+                closed: false,
+                merged: true,
+                created_at: None,
+                updated_at: None,
+            }],
+            commits: vec![Commit {
+                id: 1,
+                hash: "abcdef1234567890".to_string(),
+                message: "Fix login\n\nLonger explanation".to_string(),
+                url: "https://github.com/org/repo/commit/abcdef1".to_string(),
+                author_id: None,
+                created_at: None,
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn create_test_workflows() -> Vec<Workflow> {
+        vec![Workflow {
+            id: 1,
+            name: "Engineering".to_string(),
+            states: vec![WorkflowState {
+                id: 500,
+                name: "In Review".to_string(),
+                color: String::new(),
+                position: 1,
+                state_type: "started".to_string(),
+            }],
+        }]
+    }
+
+    fn create_test_epics() -> Vec<Epic> {
+        vec![Epic {
+            id: 9,
+            name: "Authentication".to_string(),
+            description: String::new(),
+            app_url: String::new(),
+            state: "in progress".to_string(),
+            owner_ids: vec![],
+            created_at: String::new(),
+            updated_at: String::new(),
+        }]
+    }
+
+    fn create_member_cache() -> HashMap<String, String> {
+        HashMap::from([("user-1".to_string(), "Alice (alice)".to_string())])
+    }
+
+    #[test]
+    fn test_details_resolve_names() {
+        let story = create_test_story();
+        let members = create_member_cache();
+        let details = StoryDetails::new(
+            &story,
+            &create_test_workflows(),
+            &create_test_epics(),
+            &members,
+        );
+
+        assert_eq!(details.state_name.as_deref(), Some("In Review"));
+        assert_eq!(details.owner_names, vec!["Alice (alice)", "user-unknown"]);
+        assert_eq!(details.epic_name.as_deref(), Some("Authentication"));
+    }
+
+    #[test]
+    fn test_details_unknown_state_and_epic() {
+        let story = create_test_story();
+        let members = HashMap::new();
+        let details = StoryDetails::new(&story, &[], &[], &members);
+
+        assert!(details.state_name.is_none());
+        assert!(details.epic_name.is_none());
+        assert!(details.render_text().contains("500"));
+    }
+
+    #[test]
+    fn test_render_json_flattens_story() {
+        let story = create_test_story();
+        let members = create_member_cache();
+        let details = StoryDetails::new(
+            &story,
+            &create_test_workflows(),
+            &create_test_epics(),
+            &members,
+        );
+
+        let value: serde_json::Value =
+            serde_json::from_str(&details.render(OutputFormat::Json).unwrap()).unwrap();
+        assert_eq!(value["id"], 42);
+        assert_eq!(value["name"], "Fix login bug");
+        assert_eq!(value["state_name"], "In Review");
+        assert_eq!(value["epic_name"], "Authentication");
+        assert_eq!(value["owner_names"][0], "Alice (alice)");
+        assert_eq!(value["pull_requests"][0]["merged"], true);
+    }
+
+    #[test]
+    fn test_render_markdown() {
+        let story = create_test_story();
+        let members = create_member_cache();
+        let details = StoryDetails::new(
+            &story,
+            &create_test_workflows(),
+            &create_test_epics(),
+            &members,
+        );
+
+        let output = details.render(OutputFormat::Markdown).unwrap();
+        assert!(output.starts_with("# [sc-42] Fix login bug\n"));
+        assert!(output.contains("- **State:** In Review"));
+        assert!(output.contains("- **Epic:** Authentication"));
+        assert!(output.contains("- **Labels:** backend"));
+        assert!(output.contains("Users **cannot** log in."));
+        assert!(output.contains("- [Fix login](https://github.com/org/repo/pull/3) (merged)"));
+        assert!(
+            output
+                .contains("- [`abcdef1`](https://github.com/org/repo/commit/abcdef1) Fix login\n")
+        );
+        assert!(output.contains("**Alice (alice)**"));
+        assert!(!output.contains("\x1b["));
+    }
+
+    #[test]
+    fn test_render_text_sections() {
+        let story = create_test_story();
+        let members = create_member_cache();
+        let details = StoryDetails::new(
+            &story,
+            &create_test_workflows(),
+            &create_test_epics(),
+            &members,
+        );
+
+        let output = details.render(OutputFormat::Text).unwrap();
+        assert!(output.contains("#42 - Fix login bug"));
+        assert!(output.contains("Owner(s): Alice (alice), user-unknown"));
+        assert!(output.contains("Epic: Authentication"));
+        assert!(output.contains("Labels: backend"));
+        assert!(output.contains("sc-42-fix-login"));
+        assert!(output.contains("abcdef1"));
+        assert!(output.contains("Comments (1)"));
+        // Description Markdown is rendered, not printed raw
+        assert!(!output.contains("**cannot**"));
+        assert!(output.contains("• step one"));
+    }
+
+    #[test]
+    fn test_render_text_empty_story() {
+        let mut story = create_test_story();
+        story.description = String::new();
+        story.owner_ids.clear();
+        story.comments.clear();
+        story.branches.clear();
+        story.pull_requests.clear();
+        story.commits.clear();
+        let members = HashMap::new();
+        let details = StoryDetails::new(&story, &create_test_workflows(), &[], &members);
+
+        let output = details.render_text();
+        assert!(output.contains("No description available"));
+        assert!(output.contains("Unassigned"));
+        assert!(!output.contains("Comments"));
+        assert!(!output.contains("Pull Requests"));
+    }
+
+    #[test]
+    fn test_short_hash() {
+        assert_eq!(short_hash("abcdef1234"), "abcdef1");
+        assert_eq!(short_hash("abc"), "abc");
+    }
+}
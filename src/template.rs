@@ -0,0 +1,333 @@
+use crate::api::{Epic, Story, Workflow};
+use crate::git::naming::BranchNaming;
+use crate::output::StoryRecord;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+
+/// Values derived from a story in addition to its own fields
+const DERIVED_FIELDS: [&str; 5] = ["state", "owners", "epic", "age", "branch"];
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Literal(String),
+    Field(String),
+}
+
+/// A user-defined line template such as `{{id}}\t{{state}}\t{{name}}`.
+///
+/// Placeholders are story field names wrapped in double braces. The escapes
+/// `\t`, `\n` and `\\` are expanded in the literal text so templates can be
+/// passed from a shell without quoting tricks; templates read from a file
+/// are taken as written.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Template {
+    segments: Vec<Segment>,
+}
+
+impl Template {
+    pub fn parse(source: &str) -> Result<Self> {
+        Self::parse_with(source, unescape)
+    }
+
+    /// Parse a template read from a file, leaving backslashes alone
+    pub fn parse_file(source: &str) -> Result<Self> {
+        Self::parse_with(source, str::to_string)
+    }
+
+    fn parse_with(source: &str, literal: fn(&str) -> String) -> Result<Self> {
+        let mut segments = Vec::new();
+        let mut rest = source;
+
+        while let Some(start) = rest.find("{{") {
+            if start > 0 {
+                segments.push(Segment::Literal(literal(&rest[..start])));
+            }
+            let after_open = &rest[start + 2..];
+            let end = after_open
+                .find("}}")
+                .with_context(|| format!("Unclosed '{{{{' in template: {source}"))?;
+            let field = after_open[..end].trim();
+            if field.is_empty() {
+                anyhow::bail!("Empty placeholder '{{{{}}}}' in template: {source}");
+            }
+            segments.push(Segment::Field(field.to_string()));
+            rest = &after_open[end + 2..];
+        }
+        if !rest.is_empty() {
+            segments.push(Segment::Literal(literal(rest)));
+        }
+
+        Ok(Self { segments })
+    }
+
+    /// Render the template, failing on placeholders missing from the context
+    pub fn render(&self, context: &HashMap<String, String>) -> Result<String> {
+        let mut output = String::new();
+        for segment in &self.segments {
+            match segment {
+                Segment::Literal(text) => output.push_str(text),
+                Segment::Field(name) => match context.get(name) {
+                    Some(value) => output.push_str(value),
+                    None => {
+                        let mut available: Vec<&str> = context.keys().map(|k| k.as_str()).collect();
+                        available.sort_unstable();
+                        anyhow::bail!(
+                            "Unknown template field '{}'. Available fields: {}",
+                            name,
+                            available.join(", ")
+                        );
+                    }
+                },
+            }
+        }
+        Ok(output)
+    }
+}
+
+fn unescape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => result.push('\t'),
+            Some('n') => result.push('\n'),
+            Some('\\') => result.push('\\'),
+            Some(other) => {
+                result.push('\\');
+                result.push(other);
+            }
+            None => result.push('\\'),
+        }
+    }
+    result
+}
+
+/// Build the template context for a story: every `Story` field plus the
+/// `*_name(s)` values from `StoryRecord` and the derived fields `state`,
+/// `owners`, `epic`, `age` and `branch`, the branch named by `naming`
+pub fn story_context(
+    story: &Story,
+    workflows: &[Workflow],
+    epics: &[Epic],
+    member_cache: &HashMap<String, String>,
+    naming: &BranchNaming,
+    now: DateTime<Utc>,
+) -> Result<HashMap<String, String>> {
+    let mut context = HashMap::new();
+
+    let value = serde_json::to_value(story).context("Failed to serialize story")?;
+    if let serde_json::Value::Object(fields) = value {
+        for (key, value) in fields {
+            context.insert(key, value_to_string(&value));
+        }
+    }
+
+    let record = StoryRecord::new(story, workflows, epics, member_cache);
+    let state = record
+        .state_name
+        .clone()
+        .unwrap_or_else(|| story.workflow_state_id.to_string());
+    let owners = record.owner_names.join(", ");
+    let epic = record.epic_name.clone().unwrap_or_default();
+
+    context.insert("state_name".to_string(), state.clone());
+    context.insert("owner_names".to_string(), owners.clone());
+    context.insert("epic_name".to_string(), epic.clone());
+
+    let derived = [
+        state,
+        owners,
+        epic,
+        format_age(&story.created_at, now),
+        naming.branch_name(story),
+    ];
+    for (key, value) in DERIVED_FIELDS.iter().zip(derived) {
+        context.insert(key.to_string(), value);
+    }
+
+    Ok(context)
+}
+
+/// Flatten a JSON value for display: lists are joined with ", " and objects
+/// with a `name` (labels, branches) are shown by name
+fn value_to_string(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Null => String::new(),
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Array(items) => items
+            .iter()
+            .map(|item| match item.get("name") {
+                Some(name) => value_to_string(name),
+                None => value_to_string(item),
+            })
+            .collect::<Vec<_>>()
+            .join(", "),
+        other => other.to_string(),
+    }
+}
+
+/// Compact age of a timestamp relative to `now`, e.g. "45m", "5h", "12d"
+pub fn format_age(timestamp: &str, now: DateTime<Utc>) -> String {
+    let Ok(created) = DateTime::parse_from_rfc3339(timestamp) else {
+        return String::new();
+    };
+    let elapsed = now.signed_duration_since(created.with_timezone(&Utc));
+    if elapsed.num_days() > 0 {
+        format!("{}d", elapsed.num_days())
+    } else if elapsed.num_hours() > 0 {
+        format!("{}h", elapsed.num_hours())
+    } else {
+        format!("{}m", elapsed.num_minutes().max(0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::{Label, WorkflowState};
+    use chrono::TimeZone;
+
+    fn create_test_story() -> Story {
+        Story {
+            id: 42,
+            name: "Fix login bug".to_string(),
+            workflow_state_id: 500,
+            app_url: "https://app.shortcut.com/org/story/42".to_string(),
+            story_type: "bug".to_string(),
+            labels: vec![
+                Label {
+                    id: 1,
+                    name: "backend".to_string(),
+                    color: String::new(),
+                },
+                Label {
+                    id: 2,
+                    name: "urgent".to_string(),
+                    color: String::new(),
+                },
+            ],
+            owner_ids: vec!["user-1".to_string()],
+            position: 1000,
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+            updated_at: "2024-01-02T00:00:00Z".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn create_context() -> HashMap<String, String> {
+        let workflows = vec![Workflow {
+            id: 1,
+            name: "Engineering".to_string(),
+            states: vec![WorkflowState {
+                id: 500,
+                name: "In Progress".to_string(),
+                color: String::new(),
+                position: 1,
+                state_type: "started".to_string(),
+            }],
+        }];
+        let members = HashMap::from([("user-1".to_string(), "Alice (alice)".to_string())]);
+        let now = Utc.with_ymd_and_hms(2024, 1, 11, 0, 0, 0).unwrap();
+        story_context(
+            &create_test_story(),
+            &workflows,
+            &[],
+            &members,
+            &BranchNaming::default(),
+            now,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_render_with_escapes() {
+        let template = Template::parse(r"{{id}}\t{{state}}\t{{owners}}\t{{name}}").unwrap();
+        assert_eq!(
+            template.render(&create_context()).unwrap(),
+            "42\tIn Progress\tAlice (alice)\tFix login bug"
+        );
+    }
+
+    #[test]
+    fn test_file_template_keeps_backslashes() {
+        let template = Template::parse_file(r"`C:\temp\n` {{id}} \\").unwrap();
+        assert_eq!(
+            template.render(&create_context()).unwrap(),
+            r"`C:\temp\n` 42 \\"
+        );
+    }
+
+    #[test]
+    fn test_render_derived_fields() {
+        let template = Template::parse("{{ age }} {{branch}} [{{epic}}]").unwrap();
+        assert_eq!(
+            template.render(&create_context()).unwrap(),
+            "10d sc-42-fix-login-bug []"
+        );
+    }
+
+    #[test]
+    fn test_branch_follows_configured_naming() {
+        let config = crate::config::GitConfig {
+            branch_template: Some("{type}/{id}-{slug}".to_string()),
+            ..Default::default()
+        };
+        let naming = BranchNaming::new(&config, "alice").unwrap();
+        let now = Utc.with_ymd_and_hms(2024, 1, 11, 0, 0, 0).unwrap();
+        let context = story_context(
+            &create_test_story(),
+            &[],
+            &[],
+            &HashMap::new(),
+            &naming,
+            now,
+        )
+        .unwrap();
+
+        assert_eq!(context["branch"], "bug/42-fix-login-bug");
+    }
+
+    #[test]
+    fn test_render_story_fields() {
+        let template =
+            Template::parse("{{story_type}}|{{labels}}|{{completed_at}}|{{position}}").unwrap();
+        assert_eq!(
+            template.render(&create_context()).unwrap(),
+            "bug|backend, urgent||1000"
+        );
+    }
+
+    #[test]
+    fn test_render_unknown_field() {
+        let template = Template::parse("{{nope}}").unwrap();
+        let err = template.render(&create_context()).unwrap_err();
+        assert!(err.to_string().contains("Unknown template field 'nope'"));
+        assert!(err.to_string().contains("owners"));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(Template::parse("{{id").is_err());
+        assert!(Template::parse("{{ }}").is_err());
+    }
+
+    #[test]
+    fn test_parse_plain_text() {
+        let template = Template::parse("no placeholders").unwrap();
+        assert_eq!(template.render(&HashMap::new()).unwrap(), "no placeholders");
+    }
+
+    #[test]
+    fn test_format_age() {
+        let now = Utc.with_ymd_and_hms(2024, 1, 1, 12, 30, 0).unwrap();
+        assert_eq!(format_age("2024-01-01T12:00:00Z", now), "30m");
+        assert_eq!(format_age("2024-01-01T07:00:00Z", now), "5h");
+        assert_eq!(format_age("2023-12-20T12:00:00Z", now), "12d");
+        assert_eq!(format_age("not a date", now), "");
+    }
+}
@@ -0,0 +1,428 @@
+use crate::api::{Epic, Story};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimerAction {
+    Start,
+    Stop,
+}
+
+/// One line of the time log
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimeEvent {
+    pub action: TimerAction,
+    pub story_id: i64,
+    pub at: DateTime<Utc>,
+}
+
+/// A span of time spent on a story
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeEntry {
+    pub story_id: i64,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+impl TimeEntry {
+    pub fn duration(&self) -> Duration {
+        self.end - self.start
+    }
+}
+
+/// Append-only log of timer starts and stops, one JSON object per line.
+/// Nothing is ever rewritten, so the log doubles as an audit trail.
+#[derive(Debug, Clone)]
+pub struct TimeLog {
+    path: PathBuf,
+}
+
+impl TimeLog {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// The log of a workspace, kept next to the configuration in
+    /// ~/.config/sc-cli/timelog/<workspace>.jsonl
+    pub fn for_workspace(workspace: &str) -> Result<Self> {
+        let home_dir = dirs::home_dir().context("Failed to find the home directory")?;
+        Ok(Self::new(
+            home_dir
+                .join(".config")
+                .join("sc-cli")
+                .join("timelog")
+                .join(format!("{workspace}.jsonl")),
+        ))
+    }
+
+    pub fn events(&self) -> Result<Vec<TimeEvent>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        let content = fs::read_to_string(&self.path)
+            .with_context(|| format!("Failed to read '{}'", self.path.display()))?;
+        content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| {
+                serde_json::from_str(line).with_context(|| {
+                    format!(
+                        "Invalid entry on line {} of '{}'",
+                        index + 1,
+                        self.path.display()
+                    )
+                })
+            })
+            .collect()
+    }
+
+    fn append(&self, event: &TimeEvent) -> Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create '{}'", dir.display()))?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .with_context(|| format!("Failed to open '{}'", self.path.display()))?;
+        writeln!(file, "{}", serde_json::to_string(event)?)
+            .with_context(|| format!("Failed to write '{}'", self.path.display()))
+    }
+
+    /// The start of the timer that is still running, if any
+    pub fn running(&self) -> Result<Option<TimeEvent>> {
+        Ok(running(&self.events()?).cloned())
+    }
+
+    /// Start timing a story. A timer running on another story is stopped
+    /// first and returned; restarting the running story changes nothing.
+    pub fn start(&self, story_id: i64, now: DateTime<Utc>) -> Result<Option<TimeEntry>> {
+        let stopped = match self.running()? {
+            Some(event) if event.story_id == story_id => return Ok(None),
+            Some(_) => self.stop(now)?,
+            None => None,
+        };
+        self.append(&TimeEvent {
+            action: TimerAction::Start,
+            story_id,
+            at: now,
+        })?;
+        Ok(stopped)
+    }
+
+    /// Stop the running timer, returning the time it recorded
+    pub fn stop(&self, now: DateTime<Utc>) -> Result<Option<TimeEntry>> {
+        let Some(event) = self.running()? else {
+            return Ok(None);
+        };
+        self.append(&TimeEvent {
+            action: TimerAction::Stop,
+            story_id: event.story_id,
+            at: now,
+        })?;
+        Ok(Some(TimeEntry {
+            story_id: event.story_id,
+            start: event.at,
+            end: now,
+        }))
+    }
+}
+
+/// The start event of the running timer: the last start not followed by a stop
+pub fn running(events: &[TimeEvent]) -> Option<&TimeEvent> {
+    events
+        .last()
+        .filter(|event| event.action == TimerAction::Start)
+}
+
+/// Pair starts with the stops that follow them. A start without a stop ends
+/// at the next start, or at `now` when the timer is still running.
+pub fn entries(events: &[TimeEvent], now: DateTime<Utc>) -> Vec<TimeEntry> {
+    let mut entries = Vec::new();
+    let mut open: Option<&TimeEvent> = None;
+    for event in events {
+        if let Some(start) = open.take() {
+            entries.push(TimeEntry {
+                story_id: start.story_id,
+                start: start.at,
+                end: event.at,
+            });
+        }
+        if event.action == TimerAction::Start {
+            open = Some(event);
+        }
+    }
+    if let Some(start) = open {
+        entries.push(TimeEntry {
+            story_id: start.story_id,
+            start: start.at,
+            end: now.max(start.at),
+        });
+    }
+    entries
+}
+
+/// Total time per story of the entries overlapping `from..to`, counting only
+/// the part inside the range
+pub fn totals_by_story(
+    entries: &[TimeEntry],
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> BTreeMap<i64, Duration> {
+    let mut totals = BTreeMap::new();
+    for entry in entries {
+        let start = entry.start.max(from);
+        let end = entry.end.min(to);
+        if end > start {
+            *totals.entry(entry.story_id).or_insert_with(Duration::zero) += end - start;
+        }
+    }
+    totals
+}
+
+/// Hours and minutes, e.g. `2h 05m`
+pub fn format_duration(duration: Duration) -> String {
+    let minutes = duration.num_minutes().max(0);
+    format!("{}h {:02}m", minutes / 60, minutes % 60)
+}
+
+/// Decimal hours for timesheets, e.g. `1.75`
+pub fn format_hours(duration: Duration) -> String {
+    format!("{:.2}", duration.num_seconds() as f64 / 3600.0)
+}
+
+/// How timesheet rows are grouped
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum TimesheetGroup {
+    Story,
+    Epic,
+}
+
+/// A timesheet row: one story, or one epic with all its stories
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimesheetRow {
+    pub id: Option<i64>,
+    pub name: String,
+    pub epic: String,
+    pub duration: Duration,
+}
+
+/// Build the timesheet rows from the totals per story, largest first. Stories
+/// that could not be fetched keep their ID as the name; stories without an
+/// epic are grouped under "No epic".
+pub fn timesheet_rows(
+    totals: &BTreeMap<i64, Duration>,
+    stories: &HashMap<i64, Story>,
+    epics: &[Epic],
+    group: TimesheetGroup,
+) -> Vec<TimesheetRow> {
+    let epic_of = |story_id: i64| {
+        stories
+            .get(&story_id)
+            .and_then(|story| story.epic_id)
+            .map(|epic_id| {
+                let name = epics
+                    .iter()
+                    .find(|epic| epic.id == epic_id)
+                    .map_or_else(|| format!("Epic {epic_id}"), |epic| epic.name.clone());
+                (epic_id, name)
+            })
+    };
+
+    let mut rows: Vec<TimesheetRow> = match group {
+        TimesheetGroup::Story => totals
+            .iter()
+            .map(|(&story_id, &duration)| TimesheetRow {
+                id: Some(story_id),
+                name: stories
+                    .get(&story_id)
+                    .map_or_else(|| format!("Story {story_id}"), |story| story.name.clone()),
+                epic: epic_of(story_id).map(|(_, name)| name).unwrap_or_default(),
+                duration,
+            })
+            .collect(),
+        TimesheetGroup::Epic => {
+            let mut by_epic: BTreeMap<Option<i64>, TimesheetRow> = BTreeMap::new();
+            for (&story_id, &duration) in totals {
+                let epic = epic_of(story_id);
+                let row = by_epic
+                    .entry(epic.as_ref().map(|(id, _)| *id))
+                    .or_insert_with(|| TimesheetRow {
+                        id: epic.as_ref().map(|(id, _)| *id),
+                        name: epic.map_or_else(|| "No epic".to_string(), |(_, name)| name),
+                        epic: String::new(),
+                        duration: Duration::zero(),
+                    });
+                row.duration += duration;
+            }
+            by_epic.into_values().collect()
+        }
+    };
+    rows.sort_by(|a, b| b.duration.cmp(&a.duration).then(a.id.cmp(&b.id)));
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    fn at(time: &str) -> DateTime<Utc> {
+        format!("2024-01-08T{time}:00Z").parse().unwrap()
+    }
+
+    fn event(action: TimerAction, story_id: i64, time: &str) -> TimeEvent {
+        TimeEvent {
+            action,
+            story_id,
+            at: at(time),
+        }
+    }
+
+    fn create_test_story(id: i64, name: &str, epic_id: Option<i64>) -> Story {
+        Story {
+            id,
+            name: name.to_string(),
+            workflow_state_id: 500,
+            story_type: "feature".to_string(),
+            position: 1000,
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+            updated_at: "2024-01-01T00:00:00Z".to_string(),
+            epic_id,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_start_stop_and_switch() {
+        let dir = TempDir::new().unwrap();
+        let log = TimeLog::new(dir.path().join("timelog").join("work.jsonl"));
+        assert_eq!(log.running().unwrap(), None);
+        assert_eq!(log.stop(at("09:00")).unwrap(), None);
+
+        assert_eq!(log.start(42, at("09:00")).unwrap(), None);
+        // Starting the running story again keeps the original start
+        assert_eq!(log.start(42, at("09:30")).unwrap(), None);
+        assert_eq!(log.running().unwrap().unwrap().at, at("09:00"));
+
+        // Starting another story stops the first one
+        let stopped = log.start(7, at("10:15")).unwrap().unwrap();
+        assert_eq!(stopped.story_id, 42);
+        assert_eq!(stopped.duration(), Duration::minutes(75));
+
+        let stopped = log.stop(at("11:00")).unwrap().unwrap();
+        assert_eq!(stopped.story_id, 7);
+        assert_eq!(log.running().unwrap(), None);
+        assert_eq!(log.events().unwrap().len(), 4);
+    }
+
+    #[test]
+    fn test_invalid_log_line_is_reported() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("work.jsonl");
+        fs::write(&path, "not json\n").unwrap();
+        let error = TimeLog::new(path).events().unwrap_err();
+        assert!(error.to_string().contains("line 1"));
+    }
+
+    #[test]
+    fn test_entries_close_unstopped_starts() {
+        let events = vec![
+            event(TimerAction::Start, 1, "09:00"),
+            event(TimerAction::Stop, 1, "10:00"),
+            event(TimerAction::Start, 2, "10:30"),
+            event(TimerAction::Start, 3, "11:00"),
+        ];
+        let durations: Vec<(i64, i64)> = entries(&events, at("12:00"))
+            .iter()
+            .map(|entry| (entry.story_id, entry.duration().num_minutes()))
+            .collect();
+        assert_eq!(durations, vec![(1, 60), (2, 30), (3, 60)]);
+    }
+
+    #[test]
+    fn test_totals_are_clipped_to_the_range() {
+        let entries = vec![
+            TimeEntry {
+                story_id: 1,
+                start: at("08:00"),
+                end: at("10:00"),
+            },
+            TimeEntry {
+                story_id: 1,
+                start: at("11:00"),
+                end: at("11:30"),
+            },
+            TimeEntry {
+                story_id: 2,
+                start: at("06:00"),
+                end: at("07:00"),
+            },
+        ];
+        let totals = totals_by_story(&entries, at("09:00"), at("23:00"));
+        assert_eq!(totals, BTreeMap::from([(1, Duration::minutes(90))]));
+    }
+
+    #[test]
+    fn test_format_duration_and_hours() {
+        assert_eq!(format_duration(Duration::minutes(125)), "2h 05m");
+        assert_eq!(format_duration(Duration::seconds(59)), "0h 00m");
+        assert_eq!(format_hours(Duration::minutes(105)), "1.75");
+    }
+
+    #[test]
+    fn test_timesheet_rows() {
+        let stories = HashMap::from([
+            (1, create_test_story(1, "Login", Some(10))),
+            (2, create_test_story(2, "Logout", Some(10))),
+            (3, create_test_story(3, "Docs", None)),
+        ]);
+        let epics = vec![Epic {
+            id: 10,
+            name: "Auth".to_string(),
+            description: String::new(),
+            app_url: String::new(),
+            state: "in progress".to_string(),
+            owner_ids: vec![],
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+            updated_at: "2024-01-01T00:00:00Z".to_string(),
+        }];
+        let totals = BTreeMap::from([
+            (1, Duration::minutes(30)),
+            (2, Duration::minutes(45)),
+            (3, Duration::minutes(60)),
+            (4, Duration::minutes(15)),
+        ]);
+
+        let rows = timesheet_rows(&totals, &stories, &epics, TimesheetGroup::Story);
+        let summary: Vec<(Option<i64>, &str, &str)> = rows
+            .iter()
+            .map(|row| (row.id, row.name.as_str(), row.epic.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (Some(3), "Docs", ""),
+                (Some(2), "Logout", "Auth"),
+                (Some(1), "Login", "Auth"),
+                (Some(4), "Story 4", ""),
+            ]
+        );
+
+        let rows = timesheet_rows(&totals, &stories, &epics, TimesheetGroup::Epic);
+        let summary: Vec<(Option<i64>, &str, i64)> = rows
+            .iter()
+            .map(|row| (row.id, row.name.as_str(), row.duration.num_minutes()))
+            .collect();
+        assert_eq!(summary, vec![(None, "No epic", 75), (Some(10), "Auth", 75)]);
+    }
+}
@@ -1,6 +1,12 @@
-use crate::api::{Epic, Story, Workflow};
+use crate::api::{Epic, PullRequest, Story, Workflow};
+use crate::burndown::Burndown;
+use crate::config::WeekConfig;
 use crate::git::GitContext;
-use chrono::{DateTime, Datelike, Duration, Utc, Weekday};
+use crate::git::naming::BranchNaming;
+use crate::git::operations::RepoBranchResult;
+use crate::history::HistoryEvent;
+use crate::worktree;
+use chrono::{DateTime, Utc};
 use crossterm::event::{self, KeyCode, MouseEventKind, MouseButton};
 use ratatui::{
     Frame,
@@ -9,7 +15,8 @@ use ratatui::{
     text::{Line, Span, Text},
     widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Wrap},
 };
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use tui_textarea::TextArea;
 
 fn convert_key_to_ratatui(key: crossterm::event::KeyEvent) -> ratatui::crossterm::event::KeyEvent {
@@ -41,36 +48,9 @@ fn convert_key_to_ratatui(key: crossterm::event::KeyEvent) -> ratatui::crossterm
 mod tests;
 
 /// Helper function to determine if a date string is from the current week
-fn is_current_week(date_str: &str) -> bool {
-    if let Ok(date) = DateTime::parse_from_rfc3339(date_str) {
-        let now = Utc::now();
-        let date_utc = date.with_timezone(&Utc);
-
-        // Get the start of the current week (Monday)
-        let days_since_monday = match now.weekday() {
-            Weekday::Mon => 0,
-            Weekday::Tue => 1,
-            Weekday::Wed => 2,
-            Weekday::Thu => 3,
-            Weekday::Fri => 4,
-            Weekday::Sat => 5,
-            Weekday::Sun => 6,
-        };
-
-        let week_start = now - Duration::days(days_since_monday);
-        let week_start = week_start
-            .date_naive()
-            .and_hms_opt(0, 0, 0)
-            .unwrap()
-            .and_utc();
-
-        // Get the end of the current week (Sunday)
-        let week_end = week_start + Duration::days(7);
-
-        date_utc >= week_start && date_utc < week_end
-    } else {
-        false
-    }
+fn is_current_week(date_str: &str, week: &WeekConfig) -> bool {
+    DateTime::parse_from_rfc3339(date_str)
+        .is_ok_and(|date| week.contains(date.with_timezone(&Utc), Utc::now()))
 }
 
 /// Helper function to check if a workflow state is a "done" state
@@ -85,6 +65,62 @@ fn is_done_state(state_id: i64, workflows: &[Workflow]) -> bool {
     false
 }
 
+/// Tabs of the story detail popup
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DetailTab {
+    Details,
+    Git,
+    History,
+}
+
+impl DetailTab {
+    const ALL: [DetailTab; 3] = [DetailTab::Details, DetailTab::Git, DetailTab::History];
+
+    fn title(self) -> &'static str {
+        match self {
+            DetailTab::Details => "Details",
+            DetailTab::Git => "Git",
+            DetailTab::History => "History",
+        }
+    }
+
+    fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|&tab| tab == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+/// A selectable entry of the detail popup's Git tab, by index into the
+/// story's pull requests, branches or commits
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GitEntry {
+    PullRequest(usize),
+    Branch(usize),
+    Commit(usize),
+}
+
+/// Entries of the Git tab in display order: pull requests, branches, then commits
+pub fn git_entries(story: &Story) -> Vec<GitEntry> {
+    (0..story.pull_requests.len())
+        .map(GitEntry::PullRequest)
+        .chain((0..story.branches.len()).map(GitEntry::Branch))
+        .chain((0..story.commits.len()).map(GitEntry::Commit))
+        .collect()
+}
+
+/// Badge text and color for the state of a pull request
+fn pull_request_status(pr: &PullRequest) -> (&'static str, Color) {
+    if pr.merged {
+        ("merged", Color::Magenta)
+    } else if pr.closed {
+        ("closed", Color::Red)
+    } else if pr.draft {
+        ("draft", Color::Yellow)
+    } else {
+        ("open", Color::Green)
+    }
+}
+
 pub struct App {
     pub show_detail: bool,
     pub show_state_selector: bool,
@@ -100,6 +136,8 @@ pub struct App {
     pub member_cache: HashMap<String, String>, // owner_id -> name
     pub current_user_id: Option<String>,       // ID of current user
     pub detail_scroll_offset: usize,           // Scroll offset for detail popup
+    pub detail_tab: DetailTab,                 // Active tab of the detail popup
+    pub detail_git_index: usize,               // Selected entry in the Git tab
     pub should_quit: bool,
     pub selected_column: usize,
     pub selected_row: usize,
@@ -124,6 +162,12 @@ pub struct App {
     pub git_branch_requested: bool,           // Flag to request git branch creation
     pub show_git_result_popup: bool,          // Flag to show git operation result popup
     pub git_result_state: GitResultState,     // Git result popup state
+    pub worktree_story_ids: HashSet<i64>,     // Stories with a local linked worktree
+    pub branch_naming: BranchNaming,          // Branch and worktree naming settings
+    pub git_base_branch: Option<String>,      // Ref new branches start from
+    pub git_repos: Vec<(String, PathBuf)>,    // Repositories for multi-repo branches
+    pub local_branches: HashSet<String>,      // Local branches, read when the Git tab opens
+    pub git_checkout: Option<String>,         // Branch to check out from the Git tab
     // Refresh state
     pub refresh_requested: bool, // Flag to request refreshing all stories
     // Epic filtering state
@@ -146,6 +190,16 @@ pub struct App {
     pub show_comment_popup: bool,
     pub comment_popup_state: CommentPopupState,
     pub add_comment_requested: bool,
+    // Time tracking state
+    pub running_timer: Option<(i64, DateTime<Utc>)>, // Story and start of the running timer
+    pub timer_toggle_requested: bool, // Flag to start or stop the timer on the selected story
+    pub week: WeekConfig,             // Done stories are shown for the current week only
+    // Burndown popup state
+    pub burndown: Option<Burndown>, // Chart shown in the burndown popup
+    pub burndown_requested: bool,   // Flag to load the burndown of the selected story's epic
+    // History tab state
+    pub story_history: Option<(i64, Vec<HistoryEvent>)>, // Timeline of the story it was loaded for
+    pub history_requested: bool, // Flag to load the history of the selected story
 }
 
 #[derive(Clone)]
@@ -222,15 +276,74 @@ pub struct GitBranchPopupState {
     pub story_id: i64,
     pub editing_branch_name: bool,
     pub editing_worktree_path: bool,
+    pub in_repos: bool, // Create the branch in all of `App::git_repos`
+    pub existing_branch: Option<String>, // Story branch to offer switching to
+    pub existing_worktree: Option<String>, // Path of the story's linked worktree
+    pub dirty: bool,    // The working tree has uncommitted changes
+    pub stash: bool,    // Stash them before switching branches
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum GitBranchOption {
+    SwitchToBranch,
+    OpenWorktree,
     CreateBranch,
     CreateWorktree,
     Cancel,
 }
 
+impl GitBranchPopupState {
+    /// Options offered in the popup, the ones reusing an existing branch or
+    /// worktree first. Those only apply to the current repository.
+    pub fn options(&self, bare_repo: bool) -> Vec<GitBranchOption> {
+        let mut options = Vec::new();
+        if !self.in_repos {
+            if self.existing_branch.is_some() && !bare_repo {
+                options.push(GitBranchOption::SwitchToBranch);
+            }
+            if self.existing_worktree.is_some() {
+                options.push(GitBranchOption::OpenWorktree);
+            }
+        }
+        options.push(if bare_repo {
+            GitBranchOption::CreateWorktree
+        } else {
+            GitBranchOption::CreateBranch
+        });
+        options.push(GitBranchOption::Cancel);
+        options
+    }
+
+    /// Move the selection by `offset` options, wrapping around
+    fn move_selection(&mut self, bare_repo: bool, offset: isize) {
+        let options = self.options(bare_repo);
+        let current = options
+            .iter()
+            .position(|option| *option == self.selected_option)
+            .unwrap_or(0);
+        let next = (current as isize + offset).rem_euclid(options.len() as isize);
+        self.selected_option = options[next as usize].clone();
+    }
+}
+
+/// Branch of the story to offer switching to: a local branch it is known by
+/// (the suggested name first), or else a branch linked in Shortcut, which
+/// `git checkout` sets up from the remote. `None` when it is already checked out.
+pub fn existing_story_branch(
+    story: &Story,
+    suggested_branch: &str,
+    local_branches: &HashSet<String>,
+    current_branch: Option<&str>,
+) -> Option<String> {
+    let linked: Vec<&str> = story.branches.iter().map(|b| b.name.as_str()).collect();
+    let branch = std::iter::once(suggested_branch)
+        .chain(story.formatted_vcs_branch_name.as_deref())
+        .chain(linked.iter().copied())
+        .find(|name| local_branches.contains(*name))
+        .or_else(|| linked.first().copied())?;
+    (Some(branch) != current_branch).then(|| branch.to_string())
+}
+
 #[derive(Debug, Clone)]
 pub struct GitResultState {
     pub success: bool,
@@ -243,12 +356,15 @@ pub struct GitResultState {
     #[allow(dead_code)]
     pub story_id: i64,
     pub selected_option: GitResultOption,
+    pub repo_results: Vec<RepoBranchResult>, // Per repository, for multi-repo branches
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum GitOperationType {
     CreateBranch,
     CreateWorktree,
+    Checkout,
+    OpenWorktree,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -276,6 +392,11 @@ impl Default for GitBranchPopupState {
             story_id: 0,
             editing_branch_name: false,
             editing_worktree_path: false,
+            in_repos: false,
+            existing_branch: None,
+            existing_worktree: None,
+            dirty: false,
+            stash: false,
         }
     }
 }
@@ -335,11 +456,13 @@ impl EditPopupState {
 }
 
 impl App {
+    /// Counts done stories from the start of `week`
     pub fn new(
         stories: Vec<Story>,
         workflows: Vec<Workflow>,
         search_query: String,
         next_page_token: Option<String>,
+        week: WeekConfig,
     ) -> Self {
         // Filter stories before grouping by state
         let filtered_stories = stories
@@ -348,13 +471,13 @@ impl App {
                 if is_done_state(story.workflow_state_id, &workflows) {
                     // For Done states, only keep stories completed in the current week
                     if let Some(completed_at) = &story.completed_at {
-                        return is_current_week(completed_at);
+                        return is_current_week(completed_at, &week);
                     } else if let Some(moved_at) = &story.moved_at {
                         // Fall back to moved_at if completed_at is not available
-                        return is_current_week(moved_at);
+                        return is_current_week(moved_at, &week);
                     } else {
                         // If no completion date available, use updated_at as fallback
-                        return is_current_week(&story.updated_at);
+                        return is_current_week(&story.updated_at, &week);
                     }
                 }
                 // Keep all non-Done stories
@@ -427,6 +550,13 @@ impl App {
             repo_type: crate::git::GitRepoType::NotARepo,
             current_branch: None,
         });
+        let worktree_story_ids = if git_context.is_git_repo() {
+            crate::git::list_worktrees()
+                .map(|worktrees| worktree::linked_story_ids(&worktree::story_worktrees(&worktrees)))
+                .unwrap_or_default()
+        } else {
+            HashSet::new()
+        };
 
         Self {
             show_detail: false,
@@ -462,6 +592,8 @@ impl App {
             member_cache: HashMap::new(),
             current_user_id: None,
             detail_scroll_offset: 0,
+            detail_tab: DetailTab::Details,
+            detail_git_index: 0,
             should_quit: false,
             selected_column,
             selected_row: 0,
@@ -500,6 +632,11 @@ impl App {
                 story_id: 0,
                 editing_branch_name: false,
                 editing_worktree_path: false,
+                in_repos: false,
+                existing_branch: None,
+                existing_worktree: None,
+                dirty: false,
+                stash: false,
             },
             git_branch_requested: false,
             show_git_result_popup: false,
@@ -511,7 +648,14 @@ impl App {
                 worktree_path: None,
                 story_id: 0,
                 selected_option: GitResultOption::Continue,
+                repo_results: Vec::new(),
             },
+            worktree_story_ids,
+            branch_naming: BranchNaming::default(),
+            git_base_branch: None,
+            git_repos: Vec::new(),
+            local_branches: HashSet::new(),
+            git_checkout: None,
             refresh_requested: false,
             epics: Vec::new(),
             selected_epic_filter: None,
@@ -550,6 +694,13 @@ impl App {
                 story_id: 0,
             },
             add_comment_requested: false,
+            running_timer: None,
+            timer_toggle_requested: false,
+            week,
+            burndown: None,
+            burndown_requested: false,
+            story_history: None,
+            history_requested: false,
         }
     }
 
@@ -670,9 +821,10 @@ impl App {
                 && !stories.is_empty()
             {
                 self.show_detail = !self.show_detail;
-                // Reset scroll offset when opening detail view
+                // Reset scroll offset and tab when opening detail view
                 if self.show_detail {
                     self.detail_scroll_offset = 0;
+                    self.detail_tab = DetailTab::Details;
                 }
             }
         }
@@ -790,14 +942,12 @@ impl App {
                 KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('?') => {
                     self.show_help_popup = false;
                 }
-                KeyCode::Up | KeyCode::Char('k') => {
-                    if self.help_selected_index > 0 {
-                        self.help_selected_index -= 1;
-                    }
+                KeyCode::Up | KeyCode::Char('k') if self.help_selected_index > 0 => {
+                    self.help_selected_index -= 1;
                 }
                 KeyCode::Down | KeyCode::Char('j') => {
-                    // Total commands: Navigation(4) + View(5) + Story Actions(6) + Application(2) = 17
-                    let total_commands = 17;
+                    // Total commands: Navigation(4) + View(5) + Story Actions(9) + Application(2) = 20
+                    let total_commands = 20;
                     if self.help_selected_index < total_commands - 1 {
                         self.help_selected_index += 1;
                     }
@@ -807,7 +957,7 @@ impl App {
                     self.show_help_popup = false;
 
                     // Map index to command
-                    // Navigation: 0-3, View: 4-8, Story Actions: 9-14, Application: 15-16
+                    // Navigation: 0-3, View: 4-8, Story Actions: 9-17, Application: 18-19
                     match self.help_selected_index {
                         // Navigation
                         0 => {} // Up - no action, just informational
@@ -815,42 +965,38 @@ impl App {
                         2 => {} // Left - no action, just informational
                         3 => {} // Right - no action, just informational
                         // View
-                        4 => {
-                            // Enter - Show story details
-                            if !self.show_detail && self.get_selected_story().is_some() {
-                                self.toggle_detail();
-                            }
+                        // Enter - Show story details
+                        4 if !self.show_detail && self.get_selected_story().is_some() => {
+                            self.toggle_detail();
                         }
                         5 => self.toggle_view_mode(), // v - Toggle view
                         6 => self.toggle_epic_selector(), // f - Filter by epic
                         7 => self.refresh_stories(),  // r - Refresh
-                        8 => {
-                            // n - Load more stories
-                            if self.has_more_stories() {
-                                self.request_load_more();
-                            }
-                        }
+                        // n - Load more stories
+                        8 if self.has_more_stories() => self.request_load_more(),
                         // Story Actions
-                        9 => {
-                            // Space - Move story
-                            if self.get_selected_story().is_some() {
-                                self.toggle_state_selector();
-                            }
-                        }
+                        // Space - Move story
+                        9 if self.get_selected_story().is_some() => self.toggle_state_selector(),
                         10 => self.take_ownership_requested = true, // o - Take ownership
                         11 => {
+                            // u - Open story URL
+                            if let Some(story) = self.get_selected_story() {
+                                let _ = open::that(&story.app_url);
+                            }
+                        }
+                        12 => {
                             // e - Edit story
                             if let Some(story) = self.get_selected_story().cloned() {
                                 self.show_edit_popup = true;
                                 self.edit_popup_state = EditPopupState::from_story(&story);
                             }
                         }
-                        12 => {
+                        13 => {
                             // a - Add story
                             self.show_create_popup = true;
                             self.create_popup_state = CreatePopupState::default();
                         }
-                        13 => {
+                        14 => {
                             // E - Create epic
                             self.show_create_epic_popup = true;
                             self.create_epic_popup_state.name_textarea.delete_line_by_head();
@@ -859,74 +1005,31 @@ impl App {
                             self.create_epic_popup_state.description_textarea.delete_line_by_end();
                             self.create_epic_popup_state.selected_field = CreateEpicField::Name;
                         }
-                        14 => {
+                        15 => {
                             // g - Create git branch
-                            if self.git_context.is_git_repo()
-                                && let Some(story) = self.get_selected_story().cloned()
-                            {
-                                let suggested_branch =
-                                    story.formatted_vcs_branch_name.unwrap_or_else(|| {
-                                        format!(
-                                            "sc-{}-{}",
-                                            story.id,
-                                            story
-                                                .name
-                                                .to_lowercase()
-                                                .chars()
-                                                .map(|c| if c.is_alphanumeric() { c } else { '-' })
-                                                .collect::<String>()
-                                                .split('-')
-                                                .filter(|s| !s.is_empty())
-                                                .take(5)
-                                                .collect::<Vec<_>>()
-                                                .join("-")
-                                        )
-                                    });
-                                self.show_git_popup = true;
-                                self.git_popup_state = GitBranchPopupState {
-                                    branch_name_textarea: {
-                                        let mut textarea = TextArea::default();
-                                        textarea.set_cursor_line_style(Style::default());
-                                        textarea.set_block(
-                                            Block::default()
-                                                .borders(Borders::ALL)
-                                                .title("Branch Name"),
-                                        );
-                                        textarea.insert_str(&suggested_branch);
-                                        textarea
-                                    },
-                                    worktree_path_textarea: {
-                                        let mut textarea = TextArea::default();
-                                        textarea.set_cursor_line_style(Style::default());
-                                        textarea.set_block(
-                                            Block::default()
-                                                .borders(Borders::ALL)
-                                                .title("Worktree Path"),
-                                        );
-                                        textarea.insert_str(crate::git::generate_worktree_path(
-                                            &suggested_branch,
-                                        ));
-                                        textarea
-                                    },
-                                    selected_option: if self.git_context.is_bare_repo() {
-                                        GitBranchOption::CreateWorktree
-                                    } else {
-                                        GitBranchOption::CreateBranch
-                                    },
-                                    story_id: story.id,
-                                    editing_branch_name: false,
-                                    editing_worktree_path: false,
-                                };
-                            }
+                            self.open_git_popup();
                         }
+                        // t - Start/stop timer
+                        16 if self.get_selected_story().is_some() => {
+                            self.timer_toggle_requested = true;
+                        }
+                        // b - Epic burndown
+                        17 if self.selected_story_has_epic() => self.burndown_requested = true,
                         // Application
-                        15 => {}                       // ? - Help (already closed)
-                        16 => self.should_quit = true, // q - Quit
+                        18 => {}                       // ? - Help (already closed)
+                        19 => self.should_quit = true, // q - Quit
                         _ => {}
                     }
                 }
                 _ => {}
             }
+        } else if self.burndown.is_some() {
+            if matches!(
+                key.code,
+                KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('b')
+            ) {
+                self.burndown = None;
+            }
         } else if self.show_git_result_popup {
             // Handle git result popup input
             match key.code {
@@ -949,29 +1052,27 @@ impl App {
                         self.should_quit = true;
                     }
                 }
-                KeyCode::Up | KeyCode::Char('k') => {
+                KeyCode::Up | KeyCode::Char('k')
                     if self.git_result_state.worktree_path.is_some()
-                        && self.git_result_state.success
-                    {
-                        // Toggle between Continue and ExitAndChange
-                        self.git_result_state.selected_option =
-                            match self.git_result_state.selected_option {
-                                GitResultOption::Continue => GitResultOption::ExitAndChange,
-                                GitResultOption::ExitAndChange => GitResultOption::Continue,
-                            };
-                    }
+                        && self.git_result_state.success =>
+                {
+                    // Toggle between Continue and ExitAndChange
+                    self.git_result_state.selected_option =
+                        match self.git_result_state.selected_option {
+                            GitResultOption::Continue => GitResultOption::ExitAndChange,
+                            GitResultOption::ExitAndChange => GitResultOption::Continue,
+                        };
                 }
-                KeyCode::Down | KeyCode::Char('j') => {
+                KeyCode::Down | KeyCode::Char('j')
                     if self.git_result_state.worktree_path.is_some()
-                        && self.git_result_state.success
-                    {
-                        // Toggle between Continue and ExitAndChange
-                        self.git_result_state.selected_option =
-                            match self.git_result_state.selected_option {
-                                GitResultOption::Continue => GitResultOption::ExitAndChange,
-                                GitResultOption::ExitAndChange => GitResultOption::Continue,
-                            };
-                    }
+                        && self.git_result_state.success =>
+                {
+                    // Toggle between Continue and ExitAndChange
+                    self.git_result_state.selected_option =
+                        match self.git_result_state.selected_option {
+                            GitResultOption::Continue => GitResultOption::ExitAndChange,
+                            GitResultOption::ExitAndChange => GitResultOption::Continue,
+                        };
                 }
                 _ => {}
             }
@@ -988,13 +1089,16 @@ impl App {
                         // Update worktree path when branch name changes
                         let branch_name =
                             self.git_popup_state.branch_name_textarea.lines().join("");
-                        let worktree_path = crate::git::generate_worktree_path(&branch_name);
-                        self.git_popup_state
-                            .worktree_path_textarea
-                            .delete_line_by_head();
-                        self.git_popup_state
-                            .worktree_path_textarea
-                            .insert_str(&worktree_path);
+                        if let Some(story) = self.get_selected_story() {
+                            let worktree_path =
+                                self.branch_naming.worktree_path(story, &branch_name);
+                            self.git_popup_state
+                                .worktree_path_textarea
+                                .delete_line_by_head();
+                            self.git_popup_state
+                                .worktree_path_textarea
+                                .insert_str(&worktree_path);
+                        }
                     }
                     _ => {
                         self.git_popup_state
@@ -1022,33 +1126,13 @@ impl App {
                 match key.code {
                     KeyCode::Esc => {
                         self.show_git_popup = false;
-                        self.git_popup_state = GitBranchPopupState {
-                            branch_name_textarea: {
-                                let mut textarea = TextArea::default();
-                                textarea.set_cursor_line_style(Style::default());
-                                textarea.set_block(
-                                    Block::default().borders(Borders::ALL).title("Branch Name"),
-                                );
-                                textarea
-                            },
-                            worktree_path_textarea: {
-                                let mut textarea = TextArea::default();
-                                textarea.set_cursor_line_style(Style::default());
-                                textarea.set_block(
-                                    Block::default()
-                                        .borders(Borders::ALL)
-                                        .title("Worktree Path"),
-                                );
-                                textarea
-                            },
-                            selected_option: GitBranchOption::CreateBranch,
-                            story_id: 0,
-                            editing_branch_name: false,
-                            editing_worktree_path: false,
-                        };
+                        self.git_popup_state = GitBranchPopupState::default();
                     }
                     KeyCode::Enter => match self.git_popup_state.selected_option {
-                        GitBranchOption::CreateBranch | GitBranchOption::CreateWorktree => {
+                        GitBranchOption::SwitchToBranch
+                        | GitBranchOption::OpenWorktree
+                        | GitBranchOption::CreateBranch
+                        | GitBranchOption::CreateWorktree => {
                             self.git_branch_requested = true;
                             self.show_git_popup = false;
                         }
@@ -1060,55 +1144,33 @@ impl App {
                         // Enter branch name editing mode
                         self.git_popup_state.editing_branch_name = true;
                     }
-                    KeyCode::Char('w') => {
+                    KeyCode::Char('w') if self.git_context.is_bare_repo() => {
                         // Enter worktree path editing mode (only for bare repos)
-                        if self.git_context.is_bare_repo() {
-                            self.git_popup_state.editing_worktree_path = true;
+                        self.git_popup_state.editing_worktree_path = true;
+                    }
+                    KeyCode::Char('r')
+                        if !self.git_repos.is_empty() && self.git_context.is_git_repo() =>
+                    {
+                        // Switch between this repository and the configured ones
+                        self.git_popup_state.in_repos = !self.git_popup_state.in_repos;
+                        let options = self
+                            .git_popup_state
+                            .options(self.git_context.is_bare_repo());
+                        if !options.contains(&self.git_popup_state.selected_option) {
+                            self.git_popup_state.selected_option = options[0].clone();
                         }
                     }
+                    KeyCode::Char('s') if self.git_popup_state.dirty => {
+                        // Stash uncommitted changes before switching branches
+                        self.git_popup_state.stash = !self.git_popup_state.stash;
+                    }
                     KeyCode::Up | KeyCode::Char('k') => {
-                        match self.git_popup_state.selected_option {
-                            GitBranchOption::CreateBranch => {
-                                self.git_popup_state.selected_option = GitBranchOption::Cancel;
-                            }
-                            GitBranchOption::CreateWorktree => {
-                                // CreateWorktree is only available in bare repos, so always go to Cancel
-                                self.git_popup_state.selected_option = GitBranchOption::Cancel;
-                            }
-                            GitBranchOption::Cancel => {
-                                if self.git_context.is_bare_repo() {
-                                    self.git_popup_state.selected_option =
-                                        GitBranchOption::CreateWorktree;
-                                } else {
-                                    self.git_popup_state.selected_option =
-                                        GitBranchOption::CreateBranch;
-                                }
-                            }
-                        }
+                        let bare_repo = self.git_context.is_bare_repo();
+                        self.git_popup_state.move_selection(bare_repo, -1);
                     }
                     KeyCode::Down | KeyCode::Char('j') => {
-                        match self.git_popup_state.selected_option {
-                            GitBranchOption::CreateBranch => {
-                                if self.git_context.is_bare_repo() {
-                                    self.git_popup_state.selected_option =
-                                        GitBranchOption::CreateWorktree;
-                                } else {
-                                    self.git_popup_state.selected_option = GitBranchOption::Cancel;
-                                }
-                            }
-                            GitBranchOption::CreateWorktree => {
-                                self.git_popup_state.selected_option = GitBranchOption::Cancel;
-                            }
-                            GitBranchOption::Cancel => {
-                                if self.git_context.is_bare_repo() {
-                                    self.git_popup_state.selected_option =
-                                        GitBranchOption::CreateWorktree;
-                                } else {
-                                    self.git_popup_state.selected_option =
-                                        GitBranchOption::CreateBranch;
-                                }
-                            }
-                        }
+                        let bare_repo = self.git_context.is_bare_repo();
+                        self.git_popup_state.move_selection(bare_repo, 1);
                     }
                     _ => {}
                 }
@@ -1468,6 +1530,26 @@ impl App {
             // Normal navigation
             match key.code {
                 KeyCode::Char('q') => self.should_quit = true,
+                KeyCode::Tab if self.show_detail => self.next_detail_tab(),
+                // The Git tab selects entries instead of scrolling
+                KeyCode::Char('j') | KeyCode::Down
+                    if self.show_detail && self.detail_tab == DetailTab::Git =>
+                {
+                    let count = self
+                        .get_selected_story()
+                        .map_or(0, |s| git_entries(s).len());
+                    if self.detail_git_index + 1 < count {
+                        self.detail_git_index += 1;
+                    }
+                }
+                KeyCode::Char('k') | KeyCode::Up
+                    if self.show_detail && self.detail_tab == DetailTab::Git =>
+                {
+                    self.detail_git_index = self.detail_git_index.saturating_sub(1);
+                }
+                KeyCode::Enter if self.show_detail && self.detail_tab == DetailTab::Git => {
+                    self.open_git_entry();
+                }
                 // Handle detail view scrolling first (more specific patterns)
                 KeyCode::Char('j') | KeyCode::Down if self.show_detail => {
                     // Simple scroll down - max scroll will be calculated in draw function
@@ -1479,6 +1561,7 @@ impl App {
                 KeyCode::Esc if self.show_detail => {
                     self.show_detail = false;
                     self.detail_scroll_offset = 0;
+                    self.detail_tab = DetailTab::Details;
                 }
                 KeyCode::Char('c') if self.show_detail => {
                     // Open comment popup for the current story
@@ -1498,22 +1581,14 @@ impl App {
                 // Regular navigation (less specific patterns)
                 KeyCode::Char('j') | KeyCode::Down => self.next(),
                 KeyCode::Char('k') | KeyCode::Up => self.previous(),
-                KeyCode::Char('l') | KeyCode::Right => {
-                    if !self.list_view_mode {
-                        self.next_column();
-                    }
-                }
-                KeyCode::Char('h') | KeyCode::Left => {
-                    if !self.list_view_mode {
-                        self.previous_column();
-                    }
+                KeyCode::Char('l') | KeyCode::Right if !self.list_view_mode => self.next_column(),
+                KeyCode::Char('h') | KeyCode::Left if !self.list_view_mode => {
+                    self.previous_column()
                 }
                 KeyCode::Enter => self.toggle_detail(),
                 KeyCode::Char(' ') => self.toggle_state_selector(),
-                KeyCode::Char('o') => {
-                    if self.get_selected_story().is_some() {
-                        self.take_ownership_requested = true;
-                    }
+                KeyCode::Char('o') if self.get_selected_story().is_some() => {
+                    self.take_ownership_requested = true;
                 }
                 KeyCode::Char('a') => {
                     self.show_create_popup = true;
@@ -1567,6 +1642,14 @@ impl App {
                     self.show_help_popup = true;
                     self.help_selected_index = 0;
                 }
+                KeyCode::Char('t') if self.get_selected_story().is_some() => {
+                    // Start or stop the timer on the selected story
+                    self.timer_toggle_requested = true;
+                }
+                KeyCode::Char('b') if self.selected_story_has_epic() => {
+                    // Chart the progress of the selected story's epic
+                    self.burndown_requested = true;
+                }
                 KeyCode::Char('u') => {
                     // Open story URL in browser
                     if let Some(story) = self.get_selected_story() {
@@ -1575,52 +1658,7 @@ impl App {
                 }
                 KeyCode::Char('g') => {
                     // Create git branch for selected story
-                    if self.git_context.is_git_repo()
-                        && let Some(story) = self.get_selected_story().cloned()
-                    {
-                        // Use the formatted VCS branch name from Shortcut if available, otherwise generate one
-                        let suggested_branch =
-                            story.formatted_vcs_branch_name.unwrap_or_else(|| {
-                                format!(
-                                    "sc-{}-{}",
-                                    story.id,
-                                    story.name.replace([' ', '/'], "-").to_lowercase()
-                                )
-                            });
-                        self.show_git_popup = true;
-                        self.git_popup_state = GitBranchPopupState {
-                            branch_name_textarea: {
-                                let mut textarea = TextArea::default();
-                                textarea.set_cursor_line_style(Style::default());
-                                textarea.set_block(
-                                    Block::default().borders(Borders::ALL).title("Branch Name"),
-                                );
-                                textarea.insert_str(&suggested_branch);
-                                textarea
-                            },
-                            worktree_path_textarea: {
-                                let mut textarea = TextArea::default();
-                                textarea.set_cursor_line_style(Style::default());
-                                textarea.set_block(
-                                    Block::default()
-                                        .borders(Borders::ALL)
-                                        .title("Worktree Path"),
-                                );
-                                textarea.insert_str(crate::git::generate_worktree_path(
-                                    &suggested_branch,
-                                ));
-                                textarea
-                            },
-                            selected_option: if self.git_context.is_bare_repo() {
-                                GitBranchOption::CreateWorktree
-                            } else {
-                                GitBranchOption::CreateBranch
-                            },
-                            story_id: story.id,
-                            editing_branch_name: false,
-                            editing_worktree_path: false,
-                        };
-                    }
+                    self.open_git_popup();
                 }
                 _ => {}
             }
@@ -1628,6 +1666,132 @@ impl App {
         Ok(())
     }
 
+    fn selected_story_has_epic(&self) -> bool {
+        self.get_selected_story()
+            .is_some_and(|story| story.epic_id.is_some())
+    }
+
+    /// Switch to the next tab of the detail popup. The local branches are
+    /// read again when the Git tab opens, so they reflect any checkouts, and
+    /// the history is loaded again when the History tab opens.
+    fn next_detail_tab(&mut self) {
+        self.detail_tab = self.detail_tab.next();
+        self.detail_scroll_offset = 0;
+        if self.detail_tab == DetailTab::Git {
+            self.detail_git_index = 0;
+            self.local_branches = if self.git_context.is_git_repo() {
+                crate::git::list_local_branches()
+                    .map(|branches| branches.into_iter().collect())
+                    .unwrap_or_default()
+            } else {
+                HashSet::new()
+            };
+        }
+        if self.detail_tab == DetailTab::History {
+            self.history_requested = true;
+        }
+    }
+
+    /// Act on the selected Git tab entry: check out a branch when in a git
+    /// repository, otherwise open the entry in the browser
+    fn open_git_entry(&mut self) {
+        let Some(story) = self.get_selected_story() else {
+            return;
+        };
+        let Some(entry) = git_entries(story).get(self.detail_git_index).copied() else {
+            return;
+        };
+        let url = match entry {
+            GitEntry::Branch(index) if self.git_context.is_git_repo() => {
+                self.git_checkout = Some(story.branches[index].name.clone());
+                return;
+            }
+            GitEntry::Branch(index) => &story.branches[index].url,
+            GitEntry::PullRequest(index) => &story.pull_requests[index].url,
+            GitEntry::Commit(index) => &story.commits[index].url,
+        };
+        let _ = open::that(url);
+    }
+
+    /// Open the branch/worktree popup for the selected story, pre-filled with
+    /// the names suggested by the workspace naming settings. Outside a git
+    /// repository the branch goes to the configured repositories, if any.
+    /// When the story already has a branch or a linked worktree, switching to
+    /// it is offered and preselected.
+    fn open_git_popup(&mut self) {
+        if !self.git_context.is_git_repo() && self.git_repos.is_empty() {
+            return;
+        }
+        let Some(story) = self.get_selected_story() else {
+            return;
+        };
+
+        let suggested_branch = self.branch_naming.branch_name(story);
+        let suggested_path = self.branch_naming.worktree_path(story, &suggested_branch);
+        let story_id = story.id;
+
+        let mut existing_branch = None;
+        let mut existing_worktree = None;
+        let mut dirty = false;
+        if self.git_context.is_git_repo() {
+            let local_branches: HashSet<String> = crate::git::list_local_branches()
+                .map(|branches| branches.into_iter().collect())
+                .unwrap_or_default();
+            existing_branch = existing_story_branch(
+                story,
+                &suggested_branch,
+                &local_branches,
+                self.git_context.current_branch.as_deref(),
+            );
+            if self.worktree_story_ids.contains(&story_id) {
+                existing_worktree = crate::git::list_worktrees().ok().and_then(|worktrees| {
+                    worktree::story_worktrees(&worktrees)
+                        .into_iter()
+                        .find(|entry| !entry.main && entry.story_id == Some(story_id))
+                        .map(|entry| entry.worktree.path.display().to_string())
+                });
+            }
+            dirty = existing_branch.is_some()
+                && !self.git_context.is_bare_repo()
+                && crate::git::has_uncommitted_changes(Path::new(".")).unwrap_or(false);
+        }
+
+        self.show_git_popup = true;
+        self.git_popup_state = GitBranchPopupState {
+            branch_name_textarea: {
+                let mut textarea = TextArea::default();
+                textarea.set_cursor_line_style(Style::default());
+                textarea.set_block(Block::default().borders(Borders::ALL).title("Branch Name"));
+                textarea.insert_str(&suggested_branch);
+                textarea
+            },
+            worktree_path_textarea: {
+                let mut textarea = TextArea::default();
+                textarea.set_cursor_line_style(Style::default());
+                textarea.set_block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title("Worktree Path"),
+                );
+                textarea.insert_str(&suggested_path);
+                textarea
+            },
+            selected_option: GitBranchOption::Cancel,
+            story_id,
+            editing_branch_name: false,
+            editing_worktree_path: false,
+            in_repos: !self.git_context.is_git_repo(),
+            existing_branch,
+            existing_worktree,
+            dirty,
+            stash: false,
+        };
+        self.git_popup_state.selected_option = self
+            .git_popup_state
+            .options(self.git_context.is_bare_repo())[0]
+            .clone();
+    }
+
     pub fn get_owner_names(&self, owner_ids: &[String]) -> Vec<String> {
         owner_ids
             .iter()
@@ -1665,13 +1829,13 @@ impl App {
                 if is_done_state(story.workflow_state_id, &self.workflows) {
                     // For Done states, only keep stories completed in the current week
                     if let Some(completed_at) = &story.completed_at {
-                        return is_current_week(completed_at);
+                        return is_current_week(completed_at, &self.week);
                     } else if let Some(moved_at) = &story.moved_at {
                         // Fall back to moved_at if completed_at is not available
-                        return is_current_week(moved_at);
+                        return is_current_week(moved_at, &self.week);
                     } else {
                         // If no completion date available, use updated_at as fallback
-                        return is_current_week(&story.updated_at);
+                        return is_current_week(&story.updated_at, &self.week);
                     }
                 }
                 // Keep all non-Done stories
@@ -1863,6 +2027,13 @@ pub fn draw(frame: &mut Frame, app: &mut App) {
         )
     };
 
+    let header_text = match app.running_timer {
+        Some((story_id, started_at)) => format!(
+            "{header_text} | ⏱ #{story_id} {}",
+            crate::timer::format_duration(Utc::now() - started_at)
+        ),
+        None => header_text,
+    };
     let header = Paragraph::new(header_text)
         .style(header_style)
         .alignment(Alignment::Center)
@@ -1880,8 +2051,11 @@ pub fn draw(frame: &mut Frame, app: &mut App) {
     // Footer
     let footer_text = if app.show_state_selector {
         "[↑/k] [↓/j] select state | [Enter] confirm | [Esc] cancel".to_string()
+    } else if app.show_detail && app.detail_tab == DetailTab::Git {
+        "[↑/k] [↓/j] select | [Enter] open / check out | [Tab] next tab | [Esc] close detail | [q] quit"
+            .to_string()
     } else if app.show_detail {
-        "[↑/k] [↓/j] scroll | [Esc] close detail | [q] quit".to_string()
+        "[↑/k] [↓/j] scroll | [Tab] next tab | [Esc] close detail | [q] quit".to_string()
     } else if app.is_loading {
         if app.refresh_requested {
             "Refreshing all stories... Please wait...".to_string()
@@ -1965,6 +2139,10 @@ pub fn draw(frame: &mut Frame, app: &mut App) {
         draw_git_result_popup(frame, app);
     }
 
+    if let Some(burndown) = &app.burndown {
+        draw_burndown_popup(frame, burndown);
+    }
+
     // Help popup
     if app.show_help_popup {
         draw_help_popup(frame, app);
@@ -1984,6 +2162,15 @@ fn draw_detail_popup(frame: &mut Frame, story: &Story, app: &mut App) {
     app.detail_area = Some(area);
     app.clickable_urls.clear();
 
+    if app.detail_tab == DetailTab::Git {
+        draw_detail_git_tab(frame, story, app, area);
+        return;
+    }
+    if app.detail_tab == DetailTab::History {
+        draw_detail_history_tab(frame, story, app, area);
+        return;
+    }
+
     let workflow_state = app
         .workflow_state_map
         .get(&story.workflow_state_id)
@@ -2100,15 +2287,8 @@ fn draw_detail_popup(frame: &mut Frame, story: &Story, app: &mut App) {
             Style::default().add_modifier(Modifier::BOLD),
         )]));
         for pr in &story.pull_requests {
-            let status = if pr.merged {
-                Span::styled("merged", Style::default().fg(Color::Magenta))
-            } else if pr.closed {
-                Span::styled("closed", Style::default().fg(Color::Red))
-            } else if pr.draft {
-                Span::styled("draft", Style::default().fg(Color::Yellow))
-            } else {
-                Span::styled("open", Style::default().fg(Color::Green))
-            };
+            let (status, color) = pull_request_status(pr);
+            let status = Span::styled(status, Style::default().fg(color));
 
             text_lines.push(Line::from(vec![
                 Span::raw("  "),
@@ -2262,6 +2442,7 @@ fn draw_detail_popup(frame: &mut Frame, story: &Story, app: &mut App) {
         .block(
             Block::default()
                 .title(scroll_indicator)
+                .title_top(detail_tabs_line(app.detail_tab))
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Color::Yellow)),
         )
@@ -2270,6 +2451,185 @@ fn draw_detail_popup(frame: &mut Frame, story: &Story, app: &mut App) {
     frame.render_widget(paragraph, area);
 }
 
+/// Right-aligned tab names for the detail popup border, the active one highlighted
+fn detail_tabs_line(active: DetailTab) -> Line<'static> {
+    let mut spans = vec![Span::raw(" [Tab] ")];
+    for (index, tab) in DetailTab::ALL.into_iter().enumerate() {
+        if index > 0 {
+            spans.push(Span::raw(" │ "));
+        }
+        let style = if tab == active {
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+        spans.push(Span::styled(tab.title(), style));
+    }
+    spans.push(Span::raw(" "));
+    Line::from(spans).right_aligned()
+}
+
+/// The Git tab: pull requests with their state, branches marked when they
+/// exist locally, and commits, one selectable entry per line
+fn draw_detail_git_tab(frame: &mut Frame, story: &Story, app: &App, area: Rect) {
+    let entries = git_entries(story);
+    let selected = app.detail_git_index.min(entries.len().saturating_sub(1));
+
+    let mut lines = Vec::new();
+    let mut selected_line = 0;
+    let mut previous_kind = None;
+    for (index, entry) in entries.iter().enumerate() {
+        let (kind, mut spans) = match *entry {
+            GitEntry::PullRequest(i) => {
+                let pr = &story.pull_requests[i];
+                let (status, color) = pull_request_status(pr);
+                let mut spans = vec![
+                    Span::styled(format!("[{status}]"), Style::default().fg(color)),
+                    Span::raw(" "),
+                ];
+                if let Some(number) = pr.number {
+                    spans.push(Span::styled(
+                        format!("#{number} "),
+                        Style::default().fg(Color::DarkGray),
+                    ));
+                }
+                spans.push(Span::raw(pr.title.clone()));
+                ("Pull Requests", spans)
+            }
+            GitEntry::Branch(i) => {
+                let branch = &story.branches[i];
+                let mut spans = vec![Span::styled(
+                    branch.name.clone(),
+                    Style::default().fg(Color::Green),
+                )];
+                if app.git_context.current_branch.as_deref() == Some(branch.name.as_str()) {
+                    spans.push(Span::styled(
+                        " ● current",
+                        Style::default()
+                            .fg(Color::Yellow)
+                            .add_modifier(Modifier::BOLD),
+                    ));
+                } else if app.local_branches.contains(&branch.name) {
+                    spans.push(Span::styled(" ● local", Style::default().fg(Color::Cyan)));
+                }
+                ("Branches", spans)
+            }
+            GitEntry::Commit(i) => {
+                let commit = &story.commits[i];
+                let short_hash: String = commit.hash.chars().take(7).collect();
+                let first_line = commit.message.lines().next().unwrap_or_default();
+                let spans = vec![
+                    Span::styled(short_hash, Style::default().fg(Color::Yellow)),
+                    Span::raw(" "),
+                    Span::raw(first_line.to_string()),
+                ];
+                ("Commits", spans)
+            }
+        };
+
+        if previous_kind != Some(kind) {
+            if previous_kind.is_some() {
+                lines.push(Line::from(""));
+            }
+            lines.push(Line::from(Span::styled(
+                format!("{kind}:"),
+                Style::default().add_modifier(Modifier::BOLD),
+            )));
+            previous_kind = Some(kind);
+        }
+
+        let marker = if index == selected { "▶ " } else { "  " };
+        spans.insert(0, Span::raw(marker));
+        let mut line = Line::from(spans);
+        if index == selected {
+            selected_line = lines.len();
+            line = line.style(Style::default().bg(Color::DarkGray));
+        }
+        lines.push(line);
+    }
+
+    if lines.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No branches, pull requests or commits are linked to this story",
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+
+    // Keep the selected entry in view
+    let content_height = area.height.saturating_sub(2) as usize;
+    let offset = (selected_line + 1).saturating_sub(content_height);
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(format!(" Git - #{} ", story.id))
+                .title_top(detail_tabs_line(app.detail_tab))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow)),
+        )
+        .scroll((offset as u16, 0));
+
+    frame.render_widget(paragraph, area);
+}
+
+/// The History tab: who changed the story and how, newest first so the
+/// latest changes are in view
+fn draw_detail_history_tab(frame: &mut Frame, story: &Story, app: &mut App, area: Rect) {
+    let mut lines = Vec::new();
+    match &app.story_history {
+        Some((story_id, events)) if *story_id == story.id => {
+            let now = Utc::now();
+            for (index, event) in events.iter().rev().enumerate() {
+                if index > 0 {
+                    lines.push(Line::from(""));
+                }
+                lines.push(Line::from(vec![
+                    Span::styled(
+                        event.member.clone(),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(
+                        format!(" · {}", event.relative_time(now)),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                ]));
+                for change in &event.changes {
+                    lines.push(Line::from(format!("  • {change}")));
+                }
+            }
+            if lines.is_empty() {
+                lines.push(Line::from(Span::styled(
+                    "No changes recorded for this story",
+                    Style::default().fg(Color::DarkGray),
+                )));
+            }
+        }
+        _ => lines.push(Line::from(Span::styled(
+            "Loading history...",
+            Style::default().fg(Color::DarkGray),
+        ))),
+    }
+
+    let content_height = area.height.saturating_sub(2) as usize;
+    app.detail_scroll_offset = app
+        .detail_scroll_offset
+        .min(lines.len().saturating_sub(content_height));
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(format!(" History - #{} ", story.id))
+                .title_top(detail_tabs_line(app.detail_tab))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow)),
+        )
+        .scroll((app.detail_scroll_offset as u16, 0));
+
+    frame.render_widget(paragraph, area);
+}
+
 fn draw_state_selector_popup(frame: &mut Frame, story: &Story, app: &App) {
     let area = centered_rect(50, 40, frame.area());
     frame.render_widget(Clear, area);
@@ -2766,8 +3126,14 @@ fn draw_git_popup(frame: &mut Frame, app: &App) {
     frame.render_widget(Clear, area);
 
     // Create the main popup block
+    let title = if app.git_popup_state.in_repos {
+        let names: Vec<&str> = app.git_repos.iter().map(|(name, _)| name.as_str()).collect();
+        format!("Create Git Branch in {}", names.join(", "))
+    } else {
+        "Create Git Branch".to_string()
+    };
     let popup = Block::default()
-        .title("Create Git Branch")
+        .title(title)
         .borders(Borders::ALL)
         .style(Style::default().bg(Color::Black).fg(Color::White));
     frame.render_widget(popup, area);
@@ -2840,24 +3206,25 @@ fn draw_git_popup(frame: &mut Frame, app: &App) {
     }
 
     // Options
-    let mut options = Vec::new();
-
-    if !app.git_context.is_bare_repo() {
-        let create_branch_style =
-            if app.git_popup_state.selected_option == GitBranchOption::CreateBranch {
-                Style::default()
-                    .bg(Color::DarkGray)
-                    .fg(Color::White)
-                    .add_modifier(Modifier::BOLD)
-            } else {
-                Style::default().fg(Color::White)
+    let state = &app.git_popup_state;
+    let mut options: Vec<ListItem> = state
+        .options(app.git_context.is_bare_repo())
+        .into_iter()
+        .map(|option| {
+            let label = match &option {
+                GitBranchOption::SwitchToBranch => format!(
+                    "Switch to Existing Branch '{}'",
+                    state.existing_branch.as_deref().unwrap_or_default()
+                ),
+                GitBranchOption::OpenWorktree => format!(
+                    "Open Existing Worktree at '{}'",
+                    state.existing_worktree.as_deref().unwrap_or_default()
+                ),
+                GitBranchOption::CreateBranch => "Create Branch".to_string(),
+                GitBranchOption::CreateWorktree => "Create Worktree".to_string(),
+                GitBranchOption::Cancel => "Cancel".to_string(),
             };
-        options.push(ListItem::new("Create Branch").style(create_branch_style));
-    }
-
-    if app.git_context.is_bare_repo() {
-        let create_worktree_style =
-            if app.git_popup_state.selected_option == GitBranchOption::CreateWorktree {
+            let style = if state.selected_option == option {
                 Style::default()
                     .bg(Color::DarkGray)
                     .fg(Color::White)
@@ -2865,18 +3232,19 @@ fn draw_git_popup(frame: &mut Frame, app: &App) {
             } else {
                 Style::default().fg(Color::White)
             };
-        options.push(ListItem::new("Create Worktree").style(create_worktree_style));
-    }
+            ListItem::new(label).style(style)
+        })
+        .collect();
 
-    let cancel_style = if app.git_popup_state.selected_option == GitBranchOption::Cancel {
-        Style::default()
-            .bg(Color::DarkGray)
-            .fg(Color::White)
-            .add_modifier(Modifier::BOLD)
-    } else {
-        Style::default().fg(Color::White)
-    };
-    options.push(ListItem::new("Cancel").style(cancel_style));
+    // Switching branches never carries uncommitted changes along
+    if state.dirty && state.selected_option == GitBranchOption::SwitchToBranch {
+        let warning = if state.stash {
+            "⚠ Uncommitted changes will be stashed first [s] keep them"
+        } else {
+            "⚠ Uncommitted changes prevent switching [s] stash them first"
+        };
+        options.push(ListItem::new(warning).style(Style::default().fg(Color::Yellow)));
+    }
 
     let list = List::new(options).block(
         Block::default()
@@ -2906,11 +3274,19 @@ fn draw_git_popup(frame: &mut Frame, app: &App) {
         let base_help = format!(
             "Git repo: {repo_type} | Current branch: {current_branch} | [↑/↓] select | [Tab/e] edit name | [Enter] confirm | [Esc] cancel"
         );
-        if app.git_context.is_bare_repo() {
+        let mut help = if app.git_context.is_bare_repo() {
             format!("{base_help} | [w] edit worktree path")
         } else {
             base_help
+        };
+        if !app.git_repos.is_empty() && app.git_context.is_git_repo() {
+            help.push_str(if app.git_popup_state.in_repos {
+                " | [r] this repository only"
+            } else {
+                " | [r] all repositories"
+            });
         }
+        help
     };
 
     let help = Paragraph::new(help_text)
@@ -2964,6 +3340,31 @@ fn draw_git_result_popup(frame: &mut Frame, app: &App) {
         .wrap(Wrap { trim: true });
     frame.render_widget(message_text, chunks[0]);
 
+    // Per-repository results of a multi-repo branch
+    if !app.git_result_state.repo_results.is_empty() {
+        let items: Vec<ListItem> = app
+            .git_result_state
+            .repo_results
+            .iter()
+            .map(|RepoBranchResult { repo, result }| {
+                let (icon, color) = if result.success {
+                    ("✅", Color::Green)
+                } else {
+                    ("❌", Color::Red)
+                };
+                ListItem::new(format!("{icon} {repo}: {}", result.message))
+                    .style(Style::default().fg(color))
+            })
+            .collect();
+        let list = List::new(items).block(
+            Block::default()
+                .title("Repositories")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow)),
+        );
+        frame.render_widget(list, chunks[1]);
+    }
+
     // Options (only for successful worktree creation)
     if app.git_result_state.success && app.git_result_state.worktree_path.is_some() {
         let options_block = Block::default()
@@ -3082,7 +3483,13 @@ fn draw_list_view(frame: &mut Frame, app: &mut App, area: Rect) {
                 .unwrap_or("Unknown");
 
             // Create first line with story info
-            let prefix = format!("[#{}] {} [{}] ", story.id, type_icon, state_name);
+            let prefix = format!(
+                "[#{}] {}{} [{}] ",
+                story.id,
+                type_icon,
+                worktree_marker(app, story.id),
+                state_name
+            );
             let first_line_width = available_width.saturating_sub(prefix.len());
 
             let mut line1_text = prefix.clone();
@@ -3145,6 +3552,15 @@ fn draw_list_view(frame: &mut Frame, app: &mut App, area: Rect) {
     frame.render_widget(list, area);
 }
 
+/// Marker shown next to stories that have a local worktree
+fn worktree_marker(app: &App, story_id: i64) -> &'static str {
+    if app.worktree_story_ids.contains(&story_id) {
+        " 🌳"
+    } else {
+        ""
+    }
+}
+
 fn draw_column_view(frame: &mut Frame, app: &App, area: Rect) {
     // Create columns for workflow states
     if !app.workflow_states.is_empty() {
@@ -3207,7 +3623,8 @@ fn draw_column_view(frame: &mut Frame, app: &App, area: Rect) {
                     };
 
                     // Create prefix for first line
-                    let prefix = format!("[#{}] {} ", story.id, type_icon);
+                    let prefix =
+                        format!("[#{}] {}{} ", story.id, type_icon, worktree_marker(app, story.id));
 
                     // Calculate available width for text based on actual column width
                     let first_line_width = available_width.saturating_sub(prefix.len());
@@ -3401,6 +3818,34 @@ fn draw_epic_selector_popup(frame: &mut Frame, app: &App) {
     frame.render_widget(list, area);
 }
 
+fn draw_burndown_popup(frame: &mut Frame, burndown: &Burndown) {
+    let area = centered_rect(80, 70, frame.area());
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" {} ", burndown.title))
+        .title_style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )
+        .title_bottom(Line::from(" [Esc] close ").centered())
+        .border_style(Style::default().fg(Color::Yellow));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(5), Constraint::Length(1)])
+        .split(inner);
+    frame.render_widget(burndown.chart(), chunks[0]);
+    frame.render_widget(
+        Paragraph::new(burndown.summary()).alignment(Alignment::Center),
+        chunks[1],
+    );
+}
+
 fn draw_help_popup(frame: &mut Frame, app: &App) {
     let area = centered_rect(70, 80, frame.area());
     frame.render_widget(Clear, area);
@@ -3436,6 +3881,8 @@ fn draw_help_popup(frame: &mut Frame, app: &App) {
                 ("a", "Add new story"),
                 ("E", "Create new epic"),
                 ("g", "Create git branch (if in git repo)"),
+                ("t", "Start/stop timer on story"),
+                ("b", "Burndown of the story's epic"),
             ],
         ),
         (
@@ -1,4 +1,5 @@
 use crate::api::{Story, Workflow, WorkflowState};
+use crate::config::WeekConfig;
 use crate::ui::App;
 
 #[cfg(test)]
@@ -15,19 +16,11 @@ mod tests {
                 workflow_state_id: 10,
                 app_url: "https://app.shortcut.com/org/story/1".to_string(),
                 story_type: "feature".to_string(),
-                labels: vec![],
                 owner_ids: vec!["user1".to_string()],
                 position: 1000,
                 created_at: "2024-01-01T00:00:00Z".to_string(),
                 updated_at: "2024-01-02T00:00:00Z".to_string(),
-                completed_at: None,
-                moved_at: None,
-                comments: vec![],
-                formatted_vcs_branch_name: None,
-                epic_id: None,
-                branches: vec![],
-                pull_requests: vec![],
-                commits: vec![],
+                ..Default::default()
             },
             Story {
                 id: 2,
@@ -36,19 +29,11 @@ mod tests {
                 workflow_state_id: 20,
                 app_url: "https://app.shortcut.com/org/story/2".to_string(),
                 story_type: "bug".to_string(),
-                labels: vec![],
                 owner_ids: vec!["user2".to_string()],
                 position: 2000,
                 created_at: "2024-01-01T00:00:00Z".to_string(),
                 updated_at: "2024-01-02T00:00:00Z".to_string(),
-                completed_at: None,
-                moved_at: None,
-                comments: vec![],
-                formatted_vcs_branch_name: None,
-                epic_id: None,
-                branches: vec![],
-                pull_requests: vec![],
-                commits: vec![],
+                ..Default::default()
             },
             Story {
                 id: 3,
@@ -57,19 +42,12 @@ mod tests {
                 workflow_state_id: 30,
                 app_url: "https://app.shortcut.com/org/story/3".to_string(),
                 story_type: "chore".to_string(),
-                labels: vec![],
                 owner_ids: vec!["user3".to_string()],
                 position: 3000,
                 created_at: "2024-01-01T00:00:00Z".to_string(),
                 updated_at: "2024-01-02T00:00:00Z".to_string(),
                 completed_at: Some("2025-08-01T10:00:00Z".to_string()),
-                moved_at: None,
-                comments: vec![],
-                formatted_vcs_branch_name: None,
-                epic_id: None,
-                branches: vec![],
-                pull_requests: vec![],
-                commits: vec![],
+                ..Default::default()
             },
         ]
     }
@@ -108,7 +86,13 @@ mod tests {
     fn test_app_creation() {
         let stories = create_test_stories();
         let workflows = create_test_workflows();
-        let app = App::new(stories.clone(), workflows, "test query".to_string(), None);
+        let app = App::new(
+            stories.clone(),
+            workflows,
+            "test query".to_string(),
+            None,
+            WeekConfig::default(),
+        );
 
         assert_eq!(app.selected_column, 0);
         assert_eq!(app.selected_row, 0);
@@ -123,7 +107,13 @@ mod tests {
     fn test_app_creation_empty_stories() {
         let stories = vec![];
         let workflows = create_test_workflows();
-        let app = App::new(stories, workflows, "test query".to_string(), None);
+        let app = App::new(
+            stories,
+            workflows,
+            "test query".to_string(),
+            None,
+            WeekConfig::default(),
+        );
 
         // Should show all workflow states even with no stories
         assert_eq!(app.workflow_states.len(), 3);
@@ -134,7 +124,13 @@ mod tests {
     fn test_navigation_next() {
         let stories = create_test_stories();
         let workflows = create_test_workflows();
-        let mut app = App::new(stories, workflows, "test query".to_string(), None);
+        let mut app = App::new(
+            stories,
+            workflows,
+            "test query".to_string(),
+            None,
+            WeekConfig::default(),
+        );
 
         // We have 3 stories, each in different workflow state
         // The app should have 3 columns, one for each state
@@ -157,7 +153,13 @@ mod tests {
     fn test_navigation_previous() {
         let stories = create_test_stories();
         let workflows = create_test_workflows();
-        let mut app = App::new(stories, workflows, "test query".to_string(), None);
+        let mut app = App::new(
+            stories,
+            workflows,
+            "test query".to_string(),
+            None,
+            WeekConfig::default(),
+        );
 
         // Start at column 0, row 0
         assert_eq!(app.selected_column, 0);
@@ -183,7 +185,13 @@ mod tests {
     fn test_navigation_empty_stories() {
         let stories = vec![];
         let workflows = create_test_workflows();
-        let mut app = App::new(stories, workflows, "test query".to_string(), None);
+        let mut app = App::new(
+            stories,
+            workflows,
+            "test query".to_string(),
+            None,
+            WeekConfig::default(),
+        );
 
         // Should not crash on empty list
         app.next();
@@ -212,7 +220,13 @@ mod tests {
     fn test_toggle_detail() {
         let stories = create_test_stories();
         let workflows = create_test_workflows();
-        let mut app = App::new(stories, workflows, "test query".to_string(), None);
+        let mut app = App::new(
+            stories,
+            workflows,
+            "test query".to_string(),
+            None,
+            WeekConfig::default(),
+        );
 
         assert!(!app.show_detail);
 
@@ -229,7 +243,13 @@ mod tests {
     fn test_toggle_detail_empty_stories() {
         let stories = vec![];
         let workflows = create_test_workflows();
-        let mut app = App::new(stories, workflows, "test query".to_string(), None);
+        let mut app = App::new(
+            stories,
+            workflows,
+            "test query".to_string(),
+            None,
+            WeekConfig::default(),
+        );
 
         // Should not toggle on empty list
         app.toggle_detail();
@@ -240,7 +260,13 @@ mod tests {
     fn test_set_current_user_id() {
         let stories = create_test_stories();
         let workflows = create_test_workflows();
-        let mut app = App::new(stories, workflows, "test query".to_string(), None);
+        let mut app = App::new(
+            stories,
+            workflows,
+            "test query".to_string(),
+            None,
+            WeekConfig::default(),
+        );
 
         // Initially no current user
         assert_eq!(app.current_user_id, None);
@@ -260,65 +286,23 @@ mod tests {
             Story {
                 id: 3,
                 name: "Third Story".to_string(),
-                description: "".to_string(),
                 workflow_state_id: 10,
-                app_url: "".to_string(),
-                story_type: "".to_string(),
-                labels: vec![],
-                owner_ids: vec![],
                 position: 3000, // Higher position
-                created_at: "".to_string(),
-                updated_at: "".to_string(),
-                completed_at: None,
-                moved_at: None,
-                comments: vec![],
-                formatted_vcs_branch_name: None,
-                epic_id: None,
-                branches: vec![],
-                pull_requests: vec![],
-                commits: vec![],
+                ..Default::default()
             },
             Story {
                 id: 1,
                 name: "First Story".to_string(),
-                description: "".to_string(),
                 workflow_state_id: 10,
-                app_url: "".to_string(),
-                story_type: "".to_string(),
-                labels: vec![],
-                owner_ids: vec![],
                 position: 1000, // Lower position (should come first)
-                created_at: "".to_string(),
-                updated_at: "".to_string(),
-                completed_at: None,
-                moved_at: None,
-                comments: vec![],
-                formatted_vcs_branch_name: None,
-                epic_id: None,
-                branches: vec![],
-                pull_requests: vec![],
-                commits: vec![],
+                ..Default::default()
             },
             Story {
                 id: 2,
                 name: "Second Story".to_string(),
-                description: "".to_string(),
                 workflow_state_id: 10,
-                app_url: "".to_string(),
-                story_type: "".to_string(),
-                labels: vec![],
-                owner_ids: vec![],
                 position: 2000, // Middle position
-                created_at: "".to_string(),
-                updated_at: "".to_string(),
-                completed_at: None,
-                moved_at: None,
-                comments: vec![],
-                formatted_vcs_branch_name: None,
-                epic_id: None,
-                branches: vec![],
-                pull_requests: vec![],
-                commits: vec![],
+                ..Default::default()
             },
         ];
 
@@ -334,7 +318,13 @@ mod tests {
             }],
         }];
 
-        let app = App::new(stories, workflows, "test query".to_string(), None);
+        let app = App::new(
+            stories,
+            workflows,
+            "test query".to_string(),
+            None,
+            WeekConfig::default(),
+        );
 
         // Check that stories are sorted by position
         let sorted_stories = app.stories_by_state.get(&10).unwrap();
@@ -348,7 +338,13 @@ mod tests {
     fn test_create_story_popup() {
         let stories = create_test_stories();
         let workflows = create_test_workflows();
-        let mut app = App::new(stories, workflows, "test query".to_string(), None);
+        let mut app = App::new(
+            stories,
+            workflows,
+            "test query".to_string(),
+            None,
+            WeekConfig::default(),
+        );
 
         // Initially popup should not be shown
         assert!(!app.show_create_popup);
@@ -406,11 +402,192 @@ mod tests {
         assert!(!app.show_create_popup);
     }
 
+    #[test]
+    fn test_git_popup_with_configured_repos() {
+        use crate::git::{GitContext, GitRepoType};
+
+        let press = |app: &mut App, c: char| {
+            app.handle_key_event(crossterm::event::KeyEvent {
+                code: crossterm::event::KeyCode::Char(c),
+                modifiers: crossterm::event::KeyModifiers::NONE,
+                kind: crossterm::event::KeyEventKind::Press,
+                state: crossterm::event::KeyEventState::NONE,
+            })
+            .unwrap();
+        };
+        let mut app = App::new(
+            create_test_stories(),
+            create_test_workflows(),
+            "test query".to_string(),
+            None,
+            WeekConfig::default(),
+        );
+        app.git_repos = vec![
+            ("api".to_string(), "/src/api".into()),
+            ("web".to_string(), "/src/web".into()),
+        ];
+
+        // Outside a repository the branch goes to the configured repositories
+        app.git_context = GitContext {
+            repo_type: GitRepoType::NotARepo,
+            current_branch: None,
+        };
+        press(&mut app, 'g');
+        assert!(app.show_git_popup);
+        assert!(app.git_popup_state.in_repos);
+        press(&mut app, 'r');
+        assert!(app.git_popup_state.in_repos);
+
+        // Inside one, 'r' switches between it and the configured repositories
+        app.show_git_popup = false;
+        app.git_context = GitContext {
+            repo_type: GitRepoType::Normal,
+            current_branch: Some("main".to_string()),
+        };
+        press(&mut app, 'g');
+        assert!(!app.git_popup_state.in_repos);
+        press(&mut app, 'r');
+        assert!(app.git_popup_state.in_repos);
+        press(&mut app, 'r');
+        assert!(!app.git_popup_state.in_repos);
+    }
+
+    #[test]
+    fn test_existing_story_branch() {
+        use crate::api::Branch;
+        use crate::ui::existing_story_branch;
+        use std::collections::HashSet;
+
+        let mut story = create_test_stories().remove(0);
+        story.branches = vec![Branch {
+            id: 1,
+            name: "feature/sc-1-first".to_string(),
+            url: String::new(),
+            repository_id: None,
+            created_at: None,
+            updated_at: None,
+        }];
+        let local: HashSet<String> = ["sc-1-first-story".to_string()].into();
+
+        // A local branch with the suggested name wins over the linked one
+        assert_eq!(
+            existing_story_branch(&story, "sc-1-first-story", &local, Some("main")).as_deref(),
+            Some("sc-1-first-story")
+        );
+        // Otherwise the branch linked in Shortcut is checked out from the remote
+        assert_eq!(
+            existing_story_branch(&story, "sc-1-other", &local, Some("main")).as_deref(),
+            Some("feature/sc-1-first")
+        );
+        // Nothing to switch to when it is already checked out
+        assert_eq!(
+            existing_story_branch(&story, "sc-1-first-story", &local, Some("sc-1-first-story")),
+            None
+        );
+        story.branches.clear();
+        assert_eq!(
+            existing_story_branch(&story, "sc-1-other", &local, Some("main")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_git_popup_existing_branch_options() {
+        use crate::git::{GitContext, GitRepoType};
+        use crate::ui::{GitBranchOption, GitBranchPopupState};
+        use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers};
+
+        let press = |app: &mut App, code: KeyCode| {
+            app.handle_key_event(KeyEvent {
+                code,
+                modifiers: KeyModifiers::NONE,
+                kind: KeyEventKind::Press,
+                state: KeyEventState::NONE,
+            })
+            .unwrap();
+        };
+        let mut app = App::new(
+            create_test_stories(),
+            create_test_workflows(),
+            "test query".to_string(),
+            None,
+            WeekConfig::default(),
+        );
+        app.git_context = GitContext {
+            repo_type: GitRepoType::Normal,
+            current_branch: Some("main".to_string()),
+        };
+        app.git_repos = vec![("api".to_string(), "/src/api".into())];
+        app.show_git_popup = true;
+        app.git_popup_state = GitBranchPopupState {
+            existing_branch: Some("sc-1-first-story".to_string()),
+            existing_worktree: Some("/src/sc-1".to_string()),
+            dirty: true,
+            selected_option: GitBranchOption::SwitchToBranch,
+            ..GitBranchPopupState::default()
+        };
+
+        assert_eq!(
+            app.git_popup_state.options(false),
+            vec![
+                GitBranchOption::SwitchToBranch,
+                GitBranchOption::OpenWorktree,
+                GitBranchOption::CreateBranch,
+                GitBranchOption::Cancel,
+            ]
+        );
+        // Switching is not offered in a bare repository
+        assert_eq!(
+            app.git_popup_state.options(true),
+            vec![
+                GitBranchOption::OpenWorktree,
+                GitBranchOption::CreateWorktree,
+                GitBranchOption::Cancel,
+            ]
+        );
+
+        press(&mut app, KeyCode::Up);
+        assert_eq!(app.git_popup_state.selected_option, GitBranchOption::Cancel);
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Down);
+        assert_eq!(
+            app.git_popup_state.selected_option,
+            GitBranchOption::OpenWorktree
+        );
+
+        press(&mut app, KeyCode::Char('s'));
+        assert!(app.git_popup_state.stash);
+
+        // The existing branch and worktree only belong to this repository
+        press(&mut app, KeyCode::Char('r'));
+        assert!(app.git_popup_state.in_repos);
+        assert_eq!(
+            app.git_popup_state.selected_option,
+            GitBranchOption::CreateBranch
+        );
+
+        press(&mut app, KeyCode::Char('r'));
+        press(&mut app, KeyCode::Up);
+        press(&mut app, KeyCode::Up);
+        press(&mut app, KeyCode::Enter);
+        assert!(app.git_branch_requested);
+        assert_eq!(
+            app.git_popup_state.selected_option,
+            GitBranchOption::SwitchToBranch
+        );
+    }
+
     #[test]
     fn test_detail_scroll_functionality() {
         let stories = create_test_stories();
         let workflows = create_test_workflows();
-        let mut app = App::new(stories, workflows, "test query".to_string(), None);
+        let mut app = App::new(
+            stories,
+            workflows,
+            "test query".to_string(),
+            None,
+            WeekConfig::default(),
+        );
 
         // Initially scroll offset should be 0
         assert_eq!(app.detail_scroll_offset, 0);
@@ -455,44 +632,18 @@ mod tests {
             Story {
                 id: 1,
                 name: "Story in second state".to_string(),
-                description: "".to_string(),
                 workflow_state_id: 20, // Second workflow state
-                app_url: "".to_string(),
                 story_type: "feature".to_string(),
-                labels: vec![],
-                owner_ids: vec![],
                 position: 1000,
-                created_at: "".to_string(),
-                updated_at: "".to_string(),
-                completed_at: None,
-                moved_at: None,
-                comments: vec![],
-                formatted_vcs_branch_name: None,
-                epic_id: None,
-                branches: vec![],
-                pull_requests: vec![],
-                commits: vec![],
+                ..Default::default()
             },
             Story {
                 id: 2,
                 name: "Another story in second state".to_string(),
-                description: "".to_string(),
                 workflow_state_id: 20, // Second workflow state
-                app_url: "".to_string(),
                 story_type: "bug".to_string(),
-                labels: vec![],
-                owner_ids: vec![],
                 position: 2000,
-                created_at: "".to_string(),
-                updated_at: "".to_string(),
-                completed_at: None,
-                moved_at: None,
-                comments: vec![],
-                formatted_vcs_branch_name: None,
-                epic_id: None,
-                branches: vec![],
-                pull_requests: vec![],
-                commits: vec![],
+                ..Default::default()
             },
         ];
 
@@ -524,7 +675,13 @@ mod tests {
             ],
         }];
 
-        let app = App::new(stories, workflows, "test query".to_string(), None);
+        let app = App::new(
+            stories,
+            workflows,
+            "test query".to_string(),
+            None,
+            WeekConfig::default(),
+        );
 
         // Should select column 1 (second workflow state) since first is empty
         assert_eq!(app.selected_column, 1);
@@ -541,23 +698,10 @@ mod tests {
         let stories = vec![Story {
             id: 1,
             name: "Story in first state".to_string(),
-            description: "".to_string(),
             workflow_state_id: 10, // First workflow state
-            app_url: "".to_string(),
             story_type: "feature".to_string(),
-            labels: vec![],
-            owner_ids: vec![],
             position: 1000,
-            created_at: "".to_string(),
-            updated_at: "".to_string(),
-            completed_at: None,
-            moved_at: None,
-            comments: vec![],
-            formatted_vcs_branch_name: None,
-            epic_id: None,
-            branches: vec![],
-            pull_requests: vec![],
-            commits: vec![],
+            ..Default::default()
         }];
 
         let workflows = vec![Workflow {
@@ -581,7 +725,13 @@ mod tests {
             ],
         }];
 
-        let app = App::new(stories, workflows, "test query".to_string(), None);
+        let app = App::new(
+            stories,
+            workflows,
+            "test query".to_string(),
+            None,
+            WeekConfig::default(),
+        );
 
         // Should select column 0 (first workflow state) since it has stories
         assert_eq!(app.selected_column, 0);
@@ -618,7 +768,13 @@ mod tests {
             ],
         }];
 
-        let app = App::new(stories, workflows, "test query".to_string(), None);
+        let app = App::new(
+            stories,
+            workflows,
+            "test query".to_string(),
+            None,
+            WeekConfig::default(),
+        );
 
         // Should still select column 0 (fallback behavior)
         assert_eq!(app.selected_column, 0);
@@ -634,23 +790,10 @@ mod tests {
         let stories = vec![Story {
             id: 1,
             name: "Story in last state".to_string(),
-            description: "".to_string(),
             workflow_state_id: 30, // Last workflow state
-            app_url: "".to_string(),
             story_type: "feature".to_string(),
-            labels: vec![],
-            owner_ids: vec![],
             position: 1000,
-            created_at: "".to_string(),
-            updated_at: "".to_string(),
-            completed_at: None,
-            moved_at: None,
-            comments: vec![],
-            formatted_vcs_branch_name: None,
-            epic_id: None,
-            branches: vec![],
-            pull_requests: vec![],
-            commits: vec![],
+            ..Default::default()
         }];
 
         let workflows = vec![Workflow {
@@ -681,7 +824,13 @@ mod tests {
             ],
         }];
 
-        let app = App::new(stories, workflows, "test query".to_string(), None);
+        let app = App::new(
+            stories,
+            workflows,
+            "test query".to_string(),
+            None,
+            WeekConfig::default(),
+        );
 
         // Should select column 2 (last workflow state) since others are empty
         assert_eq!(app.selected_column, 2);
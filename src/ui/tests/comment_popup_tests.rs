@@ -1,6 +1,7 @@
 #[cfg(test)]
 pub mod tests {
     use crate::api::{Story, Workflow, WorkflowState};
+    use crate::config::WeekConfig;
     use crate::ui::{App, CommentPopupState};
     use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
     use tui_textarea::TextArea;
@@ -13,19 +14,10 @@ pub mod tests {
             workflow_state_id: 100,
             app_url: format!("https://app.shortcut.com/org/story/{}", id),
             story_type: "feature".to_string(),
-            labels: vec![],
-            owner_ids: vec![],
             position: 1000,
             created_at: "2024-01-01T00:00:00Z".to_string(),
             updated_at: "2024-01-01T00:00:00Z".to_string(),
-            completed_at: None,
-            moved_at: None,
-            comments: vec![],
-            formatted_vcs_branch_name: None,
-            epic_id: None,
-            branches: vec![],
-            pull_requests: vec![],
-            commits: vec![],
+            ..Default::default()
         }
     }
 
@@ -56,7 +48,13 @@ pub mod tests {
     fn test_comment_popup_initialization() {
         let stories = vec![create_test_story(1)];
         let workflows = create_test_workflow();
-        let app = App::new(stories, workflows, "owner:test".to_string(), None);
+        let app = App::new(
+            stories,
+            workflows,
+            "owner:test".to_string(),
+            None,
+            WeekConfig::default(),
+        );
 
         // Initially, comment popup should not be shown
         assert!(!app.show_comment_popup);
@@ -67,7 +65,13 @@ pub mod tests {
     fn test_open_comment_popup_from_detail_view() {
         let stories = vec![create_test_story(42)];
         let workflows = create_test_workflow();
-        let mut app = App::new(stories, workflows, "owner:test".to_string(), None);
+        let mut app = App::new(
+            stories,
+            workflows,
+            "owner:test".to_string(),
+            None,
+            WeekConfig::default(),
+        );
 
         // Open detail view first
         app.show_detail = true;
@@ -85,7 +89,13 @@ pub mod tests {
     fn test_comment_popup_cancel_with_esc() {
         let stories = vec![create_test_story(42)];
         let workflows = create_test_workflow();
-        let mut app = App::new(stories, workflows, "owner:test".to_string(), None);
+        let mut app = App::new(
+            stories,
+            workflows,
+            "owner:test".to_string(),
+            None,
+            WeekConfig::default(),
+        );
 
         // Open comment popup
         app.show_detail = true;
@@ -113,7 +123,13 @@ pub mod tests {
     fn test_comment_popup_submit_with_ctrl_enter() {
         let stories = vec![create_test_story(42)];
         let workflows = create_test_workflow();
-        let mut app = App::new(stories, workflows, "owner:test".to_string(), None);
+        let mut app = App::new(
+            stories,
+            workflows,
+            "owner:test".to_string(),
+            None,
+            WeekConfig::default(),
+        );
 
         // Open comment popup with some text
         app.show_comment_popup = true;
@@ -140,7 +156,13 @@ pub mod tests {
     fn test_comment_popup_ignores_submit_when_empty() {
         let stories = vec![create_test_story(42)];
         let workflows = create_test_workflow();
-        let mut app = App::new(stories, workflows, "owner:test".to_string(), None);
+        let mut app = App::new(
+            stories,
+            workflows,
+            "owner:test".to_string(),
+            None,
+            WeekConfig::default(),
+        );
 
         // Open comment popup with empty text
         app.show_comment_popup = true;
@@ -163,7 +185,13 @@ pub mod tests {
     fn test_comment_popup_text_input() {
         let stories = vec![create_test_story(42)];
         let workflows = create_test_workflow();
-        let mut app = App::new(stories, workflows, "owner:test".to_string(), None);
+        let mut app = App::new(
+            stories,
+            workflows,
+            "owner:test".to_string(),
+            None,
+            WeekConfig::default(),
+        );
 
         // Open comment popup
         app.show_comment_popup = true;
@@ -188,7 +216,13 @@ pub mod tests {
     fn test_comment_popup_not_accessible_without_detail_view() {
         let stories = vec![create_test_story(42)];
         let workflows = create_test_workflow();
-        let mut app = App::new(stories, workflows, "owner:test".to_string(), None);
+        let mut app = App::new(
+            stories,
+            workflows,
+            "owner:test".to_string(),
+            None,
+            WeekConfig::default(),
+        );
 
         // Ensure detail view is NOT open
         app.show_detail = false;
@@ -205,7 +239,13 @@ pub mod tests {
     fn test_comment_popup_multiline_input() {
         let stories = vec![create_test_story(42)];
         let workflows = create_test_workflow();
-        let mut app = App::new(stories, workflows, "owner:test".to_string(), None);
+        let mut app = App::new(
+            stories,
+            workflows,
+            "owner:test".to_string(),
+            None,
+            WeekConfig::default(),
+        );
 
         // Open comment popup
         app.show_comment_popup = true;
@@ -1,6 +1,7 @@
 #[cfg(test)]
 mod tests {
     use crate::api::{Story, Workflow, WorkflowState};
+    use crate::config::WeekConfig;
     use crate::ui::{App, EditField, EditPopupState};
 
     fn create_test_story() -> Story {
@@ -11,19 +12,10 @@ mod tests {
             workflow_state_id: 1,
             app_url: "https://app.shortcut.com/test/story/123".to_string(),
             story_type: "feature".to_string(),
-            labels: vec![],
-            owner_ids: vec![],
             position: 1000,
             created_at: "2024-01-01T00:00:00Z".to_string(),
             updated_at: "2024-01-01T00:00:00Z".to_string(),
-            completed_at: None,
-            moved_at: None,
-            comments: vec![],
-            formatted_vcs_branch_name: None,
-            epic_id: None,
-            branches: vec![],
-            pull_requests: vec![],
-            commits: vec![],
+            ..Default::default()
         }
     }
 
@@ -81,7 +73,13 @@ mod tests {
     fn test_edit_story_trigger() {
         let stories = vec![create_test_story()];
         let workflows = vec![create_test_workflow()];
-        let mut app = App::new(stories, workflows, "test query".to_string(), None);
+        let mut app = App::new(
+            stories,
+            workflows,
+            "test query".to_string(),
+            None,
+            WeekConfig::default(),
+        );
 
         // Initially edit popup should be hidden
         assert!(!app.show_edit_popup);
@@ -113,7 +111,13 @@ mod tests {
         let story = create_test_story();
         let stories = vec![story.clone()];
         let workflows = vec![create_test_workflow()];
-        let mut app = App::new(stories, workflows, "test query".to_string(), None);
+        let mut app = App::new(
+            stories,
+            workflows,
+            "test query".to_string(),
+            None,
+            WeekConfig::default(),
+        );
 
         // Show edit popup
         app.show_edit_popup = true;
@@ -151,7 +155,13 @@ mod tests {
         let story = create_test_story();
         let stories = vec![story.clone()];
         let workflows = vec![create_test_workflow()];
-        let mut app = App::new(stories, workflows, "test query".to_string(), None);
+        let mut app = App::new(
+            stories,
+            workflows,
+            "test query".to_string(),
+            None,
+            WeekConfig::default(),
+        );
 
         // Show edit popup and navigate to Type field
         app.show_edit_popup = true;
@@ -190,7 +200,13 @@ mod tests {
         let story = create_test_story();
         let stories = vec![story.clone()];
         let workflows = vec![create_test_workflow()];
-        let mut app = App::new(stories, workflows, "test query".to_string(), None);
+        let mut app = App::new(
+            stories,
+            workflows,
+            "test query".to_string(),
+            None,
+            WeekConfig::default(),
+        );
 
         // Show edit popup
         app.show_edit_popup = true;
@@ -230,7 +246,13 @@ mod tests {
         let story = create_test_story();
         let stories = vec![story.clone()];
         let workflows = vec![create_test_workflow()];
-        let mut app = App::new(stories, workflows, "test query".to_string(), None);
+        let mut app = App::new(
+            stories,
+            workflows,
+            "test query".to_string(),
+            None,
+            WeekConfig::default(),
+        );
 
         // Show edit popup and navigate to Type field
         app.show_edit_popup = true;
@@ -261,7 +283,13 @@ mod tests {
         let story = create_test_story();
         let stories = vec![story.clone()];
         let workflows = vec![create_test_workflow()];
-        let mut app = App::new(stories, workflows, "test query".to_string(), None);
+        let mut app = App::new(
+            stories,
+            workflows,
+            "test query".to_string(),
+            None,
+            WeekConfig::default(),
+        );
 
         // Show edit popup
         app.show_edit_popup = true;
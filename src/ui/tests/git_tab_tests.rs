@@ -0,0 +1,210 @@
+use crate::api::{Branch, Commit, PullRequest, Story, Workflow, WorkflowState};
+use crate::config::WeekConfig;
+use crate::git::{GitContext, GitRepoType};
+use crate::ui::{App, DetailTab, GitEntry, draw, git_entries};
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers};
+use ratatui::{Terminal, backend::TestBackend};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn create_test_story() -> Story {
+        Story {
+            id: 42,
+            name: "Fix login".to_string(),
+            workflow_state_id: 500,
+            app_url: "https://app.shortcut.com/org/story/42".to_string(),
+            story_type: "bug".to_string(),
+            position: 1000,
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+            updated_at: "2024-01-02T00:00:00Z".to_string(),
+            branches: vec![
+                Branch {
+                    id: 1,
+                    name: "sc-42-fix-login".to_string(),
+                    url: "https://github.com/org/repo/tree/sc-42-fix-login".to_string(),
+                    repository_id: None,
+                    created_at: None,
+                    updated_at: None,
+                },
+                Branch {
+                    id: 2,
+                    name: "sc-42-old".to_string(),
+                    url: "https://github.com/org/repo/tree/sc-42-old".to_string(),
+                    repository_id: None,
+                    created_at: None,
+                    updated_at: None,
+                },
+            ],
+            pull_requests: vec![PullRequest {
+                id: 1,
+                title: "Fix login redirect".to_string(),
+                url: "https://github.com/org/repo/pull/3".to_string(),
+                number: Some(3),
+                draft: true,
+                closed: false,
+                merged: false,
+                created_at: None,
+                updated_at: None,
+            }],
+            commits: vec![Commit {
+                id: 1,
+                hash: "abcdef1234567890".to_string(),
+                message: "Redirect after login\n\nDetails".to_string(),
+                url: "https://github.com/org/repo/commit/abcdef1".to_string(),
+                author_id: None,
+                created_at: None,
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn create_test_app() -> App {
+        let workflows = vec![Workflow {
+            id: 1,
+            name: "Engineering".to_string(),
+            states: vec![WorkflowState {
+                id: 500,
+                name: "In Progress".to_string(),
+                color: String::new(),
+                position: 1,
+                state_type: "started".to_string(),
+            }],
+        }];
+        let mut app = App::new(
+            vec![create_test_story()],
+            workflows,
+            "test query".to_string(),
+            None,
+            WeekConfig::default(),
+        );
+        app.git_context = GitContext {
+            repo_type: GitRepoType::NotARepo,
+            current_branch: None,
+        };
+        app.show_detail = true;
+        app
+    }
+
+    fn press(app: &mut App, code: KeyCode) {
+        app.handle_key_event(KeyEvent {
+            code,
+            modifiers: KeyModifiers::NONE,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        })
+        .unwrap();
+    }
+
+    fn render(app: &mut App) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+        terminal.draw(|f| draw(f, app)).unwrap();
+        let buffer = terminal.backend().buffer();
+        let mut text = String::new();
+        for y in 0..buffer.area().height {
+            for x in 0..buffer.area().width {
+                text.push_str(buffer[(x, y)].symbol());
+            }
+            text.push('\n');
+        }
+        text
+    }
+
+    #[test]
+    fn test_git_entries_order() {
+        assert_eq!(
+            git_entries(&create_test_story()),
+            vec![
+                GitEntry::PullRequest(0),
+                GitEntry::Branch(0),
+                GitEntry::Branch(1),
+                GitEntry::Commit(0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tab_switches_detail_tabs() {
+        let mut app = create_test_app();
+        assert_eq!(app.detail_tab, DetailTab::Details);
+
+        press(&mut app, KeyCode::Tab);
+        assert_eq!(app.detail_tab, DetailTab::Git);
+        press(&mut app, KeyCode::Tab);
+        assert_eq!(app.detail_tab, DetailTab::History);
+        press(&mut app, KeyCode::Tab);
+        assert_eq!(app.detail_tab, DetailTab::Details);
+
+        // Closing the popup goes back to the details
+        press(&mut app, KeyCode::Tab);
+        press(&mut app, KeyCode::Esc);
+        assert!(!app.show_detail);
+        assert_eq!(app.detail_tab, DetailTab::Details);
+    }
+
+    #[test]
+    fn test_git_tab_selection_stays_in_bounds() {
+        let mut app = create_test_app();
+        press(&mut app, KeyCode::Tab);
+
+        press(&mut app, KeyCode::Up);
+        assert_eq!(app.detail_git_index, 0);
+        for _ in 0..10 {
+            press(&mut app, KeyCode::Char('j'));
+        }
+        assert_eq!(app.detail_git_index, 3);
+        press(&mut app, KeyCode::Char('k'));
+        assert_eq!(app.detail_git_index, 2);
+        // Scrolling the details is left alone
+        assert_eq!(app.detail_scroll_offset, 0);
+    }
+
+    #[test]
+    fn test_enter_on_branch_requests_checkout_in_repository() {
+        let mut app = create_test_app();
+        app.git_context = GitContext {
+            repo_type: GitRepoType::Normal,
+            current_branch: Some("main".to_string()),
+        };
+        press(&mut app, KeyCode::Tab);
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Enter);
+
+        assert_eq!(app.git_checkout.as_deref(), Some("sc-42-old"));
+        // Enter acts on the entry instead of closing the popup
+        assert!(app.show_detail);
+    }
+
+    #[test]
+    fn test_render_git_tab() {
+        let mut app = create_test_app();
+        press(&mut app, KeyCode::Tab);
+        app.local_branches.insert("sc-42-old".to_string());
+        app.git_context.current_branch = Some("sc-42-fix-login".to_string());
+
+        let screen = render(&mut app);
+
+        assert!(screen.contains("Git - #42"));
+        assert!(screen.contains("Pull Requests:"));
+        assert!(screen.contains("▶ [draft] #3 Fix login redirect"));
+        assert!(screen.contains("sc-42-fix-login ● current"));
+        assert!(screen.contains("sc-42-old ● local"));
+        assert!(screen.contains("abcdef1 Redirect after login"));
+        assert!(screen.contains("[Enter] open / check out"));
+    }
+
+    #[test]
+    fn test_render_git_tab_without_entries() {
+        let mut app = create_test_app();
+        let story = &mut app.stories_by_state.get_mut(&500).unwrap()[0];
+        story.branches.clear();
+        story.pull_requests.clear();
+        story.commits.clear();
+        press(&mut app, KeyCode::Tab);
+
+        assert!(render(&mut app).contains("No branches, pull requests or commits"));
+    }
+}
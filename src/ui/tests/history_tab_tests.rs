@@ -0,0 +1,131 @@
+use crate::api::{Story, Workflow, WorkflowState};
+use crate::config::WeekConfig;
+use crate::history::HistoryEvent;
+use crate::ui::{App, DetailTab, draw};
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers};
+use ratatui::{Terminal, backend::TestBackend};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_story() -> Story {
+        Story {
+            id: 42,
+            name: "Fix login".to_string(),
+            workflow_state_id: 500,
+            app_url: "https://app.shortcut.com/org/story/42".to_string(),
+            story_type: "bug".to_string(),
+            position: 1000,
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+            updated_at: "2024-01-02T00:00:00Z".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn create_test_app() -> App {
+        let workflows = vec![Workflow {
+            id: 1,
+            name: "Engineering".to_string(),
+            states: vec![WorkflowState {
+                id: 500,
+                name: "In Progress".to_string(),
+                color: String::new(),
+                position: 1,
+                state_type: "started".to_string(),
+            }],
+        }];
+        let mut app = App::new(
+            vec![create_test_story()],
+            workflows,
+            "test query".to_string(),
+            None,
+            WeekConfig::default(),
+        );
+        app.show_detail = true;
+        app
+    }
+
+    fn press(app: &mut App, code: KeyCode) {
+        app.handle_key_event(KeyEvent {
+            code,
+            modifiers: KeyModifiers::NONE,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        })
+        .unwrap();
+    }
+
+    fn render(app: &mut App) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+        terminal.draw(|f| draw(f, app)).unwrap();
+        let buffer = terminal.backend().buffer();
+        let mut text = String::new();
+        for y in 0..buffer.area().height {
+            for x in 0..buffer.area().width {
+                text.push_str(buffer[(x, y)].symbol());
+            }
+            text.push('\n');
+        }
+        text
+    }
+
+    fn open_history_tab(app: &mut App) {
+        while app.detail_tab != DetailTab::History {
+            press(app, KeyCode::Tab);
+        }
+    }
+
+    #[test]
+    fn test_opening_history_tab_requests_history() {
+        let mut app = create_test_app();
+        assert!(!app.history_requested);
+
+        open_history_tab(&mut app);
+
+        assert!(app.history_requested);
+    }
+
+    #[test]
+    fn test_history_tab_shows_loading_until_loaded_for_the_story() {
+        let mut app = create_test_app();
+        open_history_tab(&mut app);
+        // History loaded for another story is not shown
+        app.story_history = Some((7, vec![]));
+
+        assert!(render(&mut app).contains("Loading history..."));
+
+        app.story_history = Some((42, vec![]));
+        assert!(render(&mut app).contains("No changes recorded for this story"));
+    }
+
+    #[test]
+    fn test_history_tab_shows_newest_first() {
+        let mut app = create_test_app();
+        open_history_tab(&mut app);
+        app.story_history = Some((
+            42,
+            vec![
+                HistoryEvent {
+                    changed_at: "2024-01-01T09:00:00Z".to_string(),
+                    member: "Alice (alice)".to_string(),
+                    changes: vec!["created the story".to_string()],
+                },
+                HistoryEvent {
+                    changed_at: "2024-01-02T09:00:00Z".to_string(),
+                    member: "Bob (bob)".to_string(),
+                    changes: vec!["moved from Ready to In Progress".to_string()],
+                },
+            ],
+        ));
+
+        let screen = render(&mut app);
+
+        assert!(screen.contains("History - #42"));
+        let moved = screen.find("• moved from Ready to In Progress").unwrap();
+        let created = screen.find("• created the story").unwrap();
+        assert!(moved < created);
+        assert!(screen.contains("Bob (bob) · "));
+        assert!(screen.contains(" ago"));
+    }
+}
@@ -1,4 +1,5 @@
 use crate::api::{Story, Workflow, WorkflowState};
+use crate::config::WeekConfig;
 use crate::ui::App;
 
 #[cfg(test)]
@@ -15,19 +16,11 @@ mod tests {
                 workflow_state_id: 10,
                 app_url: "https://app.shortcut.com/org/story/1".to_string(),
                 story_type: "feature".to_string(),
-                labels: vec![],
                 owner_ids: vec!["user1".to_string()],
                 position: 1000,
                 created_at: "2024-01-01T00:00:00Z".to_string(),
                 updated_at: "2024-01-02T00:00:00Z".to_string(),
-                completed_at: None,
-                moved_at: None,
-                comments: vec![],
-                formatted_vcs_branch_name: None,
-                epic_id: None,
-                branches: vec![],
-                pull_requests: vec![],
-                commits: vec![],
+                ..Default::default()
             },
             Story {
                 id: 2,
@@ -36,19 +29,11 @@ mod tests {
                 workflow_state_id: 20,
                 app_url: "https://app.shortcut.com/org/story/2".to_string(),
                 story_type: "bug".to_string(),
-                labels: vec![],
                 owner_ids: vec!["user2".to_string()],
                 position: 2000,
                 created_at: "2024-01-01T00:00:00Z".to_string(),
                 updated_at: "2024-01-02T00:00:00Z".to_string(),
-                completed_at: None,
-                moved_at: None,
-                comments: vec![],
-                formatted_vcs_branch_name: None,
-                epic_id: None,
-                branches: vec![],
-                pull_requests: vec![],
-                commits: vec![],
+                ..Default::default()
             },
             Story {
                 id: 3,
@@ -57,19 +42,11 @@ mod tests {
                 workflow_state_id: 20, // Changed from 30 (Done) to 20 (In Progress)
                 app_url: "https://app.shortcut.com/org/story/3".to_string(),
                 story_type: "chore".to_string(),
-                labels: vec![],
                 owner_ids: vec!["user3".to_string()],
                 position: 500, // Smallest position, should be first in list
                 created_at: "2024-01-01T00:00:00Z".to_string(),
                 updated_at: "2024-01-02T00:00:00Z".to_string(),
-                completed_at: None,
-                moved_at: None,
-                comments: vec![],
-                formatted_vcs_branch_name: None,
-                epic_id: None,
-                branches: vec![],
-                pull_requests: vec![],
-                commits: vec![],
+                ..Default::default()
             },
         ]
     }
@@ -108,7 +85,13 @@ mod tests {
     fn test_app_creation_with_list_view_fields() {
         let stories = create_test_stories();
         let workflows = create_test_workflows();
-        let app = App::new(stories.clone(), workflows, "test query".to_string(), None);
+        let app = App::new(
+            stories.clone(),
+            workflows,
+            "test query".to_string(),
+            None,
+            WeekConfig::default(),
+        );
 
         // Should start in column view mode
         assert!(!app.list_view_mode);
@@ -126,7 +109,13 @@ mod tests {
     fn test_toggle_view_mode() {
         let stories = create_test_stories();
         let workflows = create_test_workflows();
-        let mut app = App::new(stories, workflows, "test query".to_string(), None);
+        let mut app = App::new(
+            stories,
+            workflows,
+            "test query".to_string(),
+            None,
+            WeekConfig::default(),
+        );
 
         // Initially in column view
         assert!(!app.list_view_mode);
@@ -149,7 +138,13 @@ mod tests {
     fn test_navigation_in_list_view() {
         let stories = create_test_stories();
         let workflows = create_test_workflows();
-        let mut app = App::new(stories, workflows, "test query".to_string(), None);
+        let mut app = App::new(
+            stories,
+            workflows,
+            "test query".to_string(),
+            None,
+            WeekConfig::default(),
+        );
 
         // Switch to list view
         app.toggle_view_mode();
@@ -182,7 +177,13 @@ mod tests {
     fn test_get_selected_story_in_list_view() {
         let stories = create_test_stories();
         let workflows = create_test_workflows();
-        let mut app = App::new(stories, workflows, "test query".to_string(), None);
+        let mut app = App::new(
+            stories,
+            workflows,
+            "test query".to_string(),
+            None,
+            WeekConfig::default(),
+        );
 
         // Switch to list view
         app.toggle_view_mode();
@@ -209,7 +210,13 @@ mod tests {
     fn test_get_selected_story_in_column_view() {
         let stories = create_test_stories();
         let workflows = create_test_workflows();
-        let app = App::new(stories, workflows, "test query".to_string(), None);
+        let app = App::new(
+            stories,
+            workflows,
+            "test query".to_string(),
+            None,
+            WeekConfig::default(),
+        );
 
         // Should be in column view by default
         assert!(!app.list_view_mode);
@@ -224,7 +231,13 @@ mod tests {
     fn test_navigation_in_column_view_vs_list_view() {
         let stories = create_test_stories();
         let workflows = create_test_workflows();
-        let mut app = App::new(stories, workflows, "test query".to_string(), None);
+        let mut app = App::new(
+            stories,
+            workflows,
+            "test query".to_string(),
+            None,
+            WeekConfig::default(),
+        );
 
         // In column view, navigation should work differently
         assert!(!app.list_view_mode);
@@ -253,7 +266,13 @@ mod tests {
     fn test_empty_stories_list_view() {
         let stories = vec![];
         let workflows = create_test_workflows();
-        let mut app = App::new(stories, workflows, "test query".to_string(), None);
+        let mut app = App::new(
+            stories,
+            workflows,
+            "test query".to_string(),
+            None,
+            WeekConfig::default(),
+        );
 
         // Switch to list view
         app.toggle_view_mode();
@@ -276,7 +295,13 @@ mod tests {
     fn test_merge_stories_updates_list_view() {
         let stories = create_test_stories();
         let workflows = create_test_workflows();
-        let mut app = App::new(stories, workflows, "test query".to_string(), None);
+        let mut app = App::new(
+            stories,
+            workflows,
+            "test query".to_string(),
+            None,
+            WeekConfig::default(),
+        );
 
         // Switch to list view
         app.toggle_view_mode();
@@ -290,19 +315,11 @@ mod tests {
             workflow_state_id: 10,
             app_url: "https://app.shortcut.com/org/story/4".to_string(),
             story_type: "feature".to_string(),
-            labels: vec![],
             owner_ids: vec!["user4".to_string()],
             position: 100, // Should be first in sorted order
             created_at: "2024-01-01T00:00:00Z".to_string(),
             updated_at: "2024-01-02T00:00:00Z".to_string(),
-            completed_at: None,
-            moved_at: None,
-            comments: vec![],
-            formatted_vcs_branch_name: None,
-            epic_id: None,
-            branches: vec![],
-            pull_requests: vec![],
-            commits: vec![],
+            ..Default::default()
         }];
 
         app.merge_stories(new_stories, None);
@@ -320,7 +337,13 @@ mod tests {
     fn test_keyboard_event_view_toggle() {
         let stories = create_test_stories();
         let workflows = create_test_workflows();
-        let mut app = App::new(stories, workflows, "test query".to_string(), None);
+        let mut app = App::new(
+            stories,
+            workflows,
+            "test query".to_string(),
+            None,
+            WeekConfig::default(),
+        );
 
         // Initially in column view
         assert!(!app.list_view_mode);
@@ -354,7 +377,13 @@ mod tests {
     fn test_list_view_scroll_initialization() {
         let stories = create_test_stories();
         let workflows = create_test_workflows();
-        let mut app = App::new(stories, workflows, "test query".to_string(), None);
+        let mut app = App::new(
+            stories,
+            workflows,
+            "test query".to_string(),
+            None,
+            WeekConfig::default(),
+        );
 
         // Switch to list view
         app.toggle_view_mode();
@@ -368,7 +397,13 @@ mod tests {
     fn test_update_list_scroll_with_small_list() {
         let stories = create_test_stories(); // 3 stories
         let workflows = create_test_workflows();
-        let mut app = App::new(stories, workflows, "test query".to_string(), None);
+        let mut app = App::new(
+            stories,
+            workflows,
+            "test query".to_string(),
+            None,
+            WeekConfig::default(),
+        );
 
         app.toggle_view_mode();
 
@@ -394,24 +429,22 @@ mod tests {
                 workflow_state_id: 10,
                 app_url: format!("https://app.shortcut.com/org/story/{}", i),
                 story_type: "feature".to_string(),
-                labels: vec![],
                 owner_ids: vec![format!("user{}", i)],
-                position: i as i64 * 1000,
+                position: i * 1000,
                 created_at: "2024-01-01T00:00:00Z".to_string(),
                 updated_at: "2024-01-02T00:00:00Z".to_string(),
-                completed_at: None,
-                moved_at: None,
-                comments: vec![],
-                formatted_vcs_branch_name: None,
-                epic_id: None,
-                branches: vec![],
-                pull_requests: vec![],
-                commits: vec![],
+                ..Default::default()
             });
         }
 
         let workflows = create_test_workflows();
-        let mut app = App::new(stories, workflows, "test query".to_string(), None);
+        let mut app = App::new(
+            stories,
+            workflows,
+            "test query".to_string(),
+            None,
+            WeekConfig::default(),
+        );
 
         app.toggle_view_mode();
 
@@ -447,24 +480,22 @@ mod tests {
                 workflow_state_id: 10,
                 app_url: format!("https://app.shortcut.com/org/story/{}", i),
                 story_type: "feature".to_string(),
-                labels: vec![],
                 owner_ids: vec![format!("user{}", i)],
-                position: i as i64 * 1000,
+                position: i * 1000,
                 created_at: "2024-01-01T00:00:00Z".to_string(),
                 updated_at: "2024-01-02T00:00:00Z".to_string(),
-                completed_at: None,
-                moved_at: None,
-                comments: vec![],
-                formatted_vcs_branch_name: None,
-                epic_id: None,
-                branches: vec![],
-                pull_requests: vec![],
-                commits: vec![],
+                ..Default::default()
             });
         }
 
         let workflows = create_test_workflows();
-        let mut app = App::new(stories, workflows, "test query".to_string(), None);
+        let mut app = App::new(
+            stories,
+            workflows,
+            "test query".to_string(),
+            None,
+            WeekConfig::default(),
+        );
 
         app.toggle_view_mode();
 
@@ -491,24 +522,22 @@ mod tests {
                 workflow_state_id: 10,
                 app_url: format!("https://app.shortcut.com/org/story/{}", i),
                 story_type: "feature".to_string(),
-                labels: vec![],
                 owner_ids: vec![format!("user{}", i)],
-                position: i as i64 * 1000,
+                position: i * 1000,
                 created_at: "2024-01-01T00:00:00Z".to_string(),
                 updated_at: "2024-01-02T00:00:00Z".to_string(),
-                completed_at: None,
-                moved_at: None,
-                comments: vec![],
-                formatted_vcs_branch_name: None,
-                epic_id: None,
-                branches: vec![],
-                pull_requests: vec![],
-                commits: vec![],
+                ..Default::default()
             });
         }
 
         let workflows = create_test_workflows();
-        let mut app = App::new(stories, workflows, "test query".to_string(), None);
+        let mut app = App::new(
+            stories,
+            workflows,
+            "test query".to_string(),
+            None,
+            WeekConfig::default(),
+        );
 
         // Switch to list view and scroll
         app.toggle_view_mode();
@@ -538,24 +567,22 @@ mod tests {
                 workflow_state_id: 10,
                 app_url: format!("https://app.shortcut.com/org/story/{}", i),
                 story_type: "feature".to_string(),
-                labels: vec![],
                 owner_ids: vec![format!("user{}", i)],
-                position: i as i64 * 1000,
+                position: i * 1000,
                 created_at: "2024-01-01T00:00:00Z".to_string(),
                 updated_at: "2024-01-02T00:00:00Z".to_string(),
-                completed_at: None,
-                moved_at: None,
-                comments: vec![],
-                formatted_vcs_branch_name: None,
-                epic_id: None,
-                branches: vec![],
-                pull_requests: vec![],
-                commits: vec![],
+                ..Default::default()
             });
         }
 
         let workflows = create_test_workflows();
-        let mut app = App::new(stories, workflows, "test query".to_string(), None);
+        let mut app = App::new(
+            stories,
+            workflows,
+            "test query".to_string(),
+            None,
+            WeekConfig::default(),
+        );
 
         app.toggle_view_mode();
 
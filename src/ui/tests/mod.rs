@@ -1,6 +1,8 @@
 mod app_tests;
 mod comment_popup_tests;
 mod edit_story_tests;
+mod git_tab_tests;
+mod history_tab_tests;
 mod list_view_tests;
 mod pagination_tests;
 mod rendering_tests;
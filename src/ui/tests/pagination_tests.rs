@@ -14,19 +14,10 @@ mod tests {
                 workflow_state_id: 100,
                 app_url: "https://example.com/1".to_string(),
                 story_type: "feature".to_string(),
-                labels: vec![],
-                owner_ids: vec![],
                 created_at: "2024-01-01T00:00:00Z".to_string(),
                 updated_at: "2024-01-01T00:00:00Z".to_string(),
-                completed_at: None,
-                moved_at: None,
-                comments: vec![],
-                formatted_vcs_branch_name: None,
-                epic_id: None,
-                branches: vec![],
-                pull_requests: vec![],
-                commits: vec![],
                 position: 1,
+                ..Default::default()
             },
             Story {
                 id: 2,
@@ -35,19 +26,10 @@ mod tests {
                 workflow_state_id: 200,
                 app_url: "https://example.com/2".to_string(),
                 story_type: "bug".to_string(),
-                labels: vec![],
-                owner_ids: vec![],
                 created_at: "2024-01-01T00:00:00Z".to_string(),
                 updated_at: "2024-01-01T00:00:00Z".to_string(),
-                completed_at: None,
-                moved_at: None,
-                comments: vec![],
-                formatted_vcs_branch_name: None,
-                epic_id: None,
-                branches: vec![],
-                pull_requests: vec![],
-                commits: vec![],
                 position: 1,
+                ..Default::default()
             },
         ]
     }
@@ -94,6 +76,7 @@ mod tests {
             workflows,
             search_query.clone(),
             next_page_token.clone(),
+            WeekConfig::default(),
         );
 
         assert_eq!(app.search_query, search_query);
@@ -110,7 +93,13 @@ mod tests {
         let workflows = create_test_workflows();
         let search_query = "owner:test".to_string();
 
-        let app = App::new(stories, workflows, search_query, None);
+        let app = App::new(
+            stories,
+            workflows,
+            search_query,
+            None,
+            WeekConfig::default(),
+        );
 
         assert!(!app.has_more_stories());
     }
@@ -122,7 +111,13 @@ mod tests {
         let search_query = "owner:test".to_string();
         let next_page_token = Some("next_token_123".to_string());
 
-        let mut app = App::new(stories, workflows, search_query, next_page_token);
+        let mut app = App::new(
+            stories,
+            workflows,
+            search_query,
+            next_page_token,
+            WeekConfig::default(),
+        );
 
         // Should be able to request load more
         app.request_load_more();
@@ -136,7 +131,13 @@ mod tests {
         let workflows = create_test_workflows();
         let search_query = "owner:test".to_string();
 
-        let mut app = App::new(stories, workflows, search_query, None);
+        let mut app = App::new(
+            stories,
+            workflows,
+            search_query,
+            None,
+            WeekConfig::default(),
+        );
 
         // Should not request load more when no token
         app.request_load_more();
@@ -151,7 +152,13 @@ mod tests {
         let search_query = "owner:test".to_string();
         let next_page_token = Some("next_token_123".to_string());
 
-        let mut app = App::new(stories, workflows, search_query, next_page_token);
+        let mut app = App::new(
+            stories,
+            workflows,
+            search_query,
+            next_page_token,
+            WeekConfig::default(),
+        );
 
         // Set already loading
         app.is_loading = true;
@@ -169,7 +176,13 @@ mod tests {
         let search_query = "owner:test".to_string();
         let initial_token = Some("initial_token".to_string());
 
-        let mut app = App::new(initial_stories, workflows, search_query, initial_token);
+        let mut app = App::new(
+            initial_stories,
+            workflows,
+            search_query,
+            initial_token,
+            WeekConfig::default(),
+        );
 
         // Simulate requesting load more
         app.load_more_requested = true;
@@ -198,7 +211,13 @@ mod tests {
         let workflows = create_test_workflows();
         let search_query = "owner:test".to_string();
 
-        let mut app = App::new(initial_stories, workflows, search_query, None);
+        let mut app = App::new(
+            initial_stories,
+            workflows,
+            search_query,
+            None,
+            WeekConfig::default(),
+        );
 
         // Create another story in the same state
         let mut new_story = create_test_stories()[0].clone();
@@ -224,7 +243,13 @@ mod tests {
         let workflows = create_test_workflows();
         let search_query = "owner:test".to_string();
 
-        let mut app = App::new(initial_stories, workflows, search_query, None);
+        let mut app = App::new(
+            initial_stories,
+            workflows,
+            search_query,
+            None,
+            WeekConfig::default(),
+        );
 
         // Try to merge some of the same stories (should be ignored)
         let duplicate_stories = vec![
@@ -246,7 +271,13 @@ mod tests {
         let workflows = create_test_workflows();
         let search_query = "owner:test".to_string();
 
-        let mut app = App::new(initial_stories, workflows, search_query, None);
+        let mut app = App::new(
+            initial_stories,
+            workflows,
+            search_query,
+            None,
+            WeekConfig::default(),
+        );
 
         // Mix of duplicate and new stories
         let mut new_story = create_test_stories()[0].clone();
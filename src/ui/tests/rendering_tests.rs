@@ -1,6 +1,9 @@
 use crate::api::{Story, Workflow, WorkflowState};
+use crate::burndown::{Burndown, BurndownUnit};
+use crate::config::WeekConfig;
 use crate::ui::{App, draw};
 use ratatui::{Terminal, backend::TestBackend};
+use std::collections::HashMap;
 
 #[cfg(test)]
 mod tests {
@@ -15,19 +18,11 @@ mod tests {
                 workflow_state_id: 456,
                 app_url: "https://app.shortcut.com/org/story/123".to_string(),
                 story_type: "feature".to_string(),
-                labels: vec![],
                 owner_ids: vec!["test-user".to_string()],
                 position: 1000,
                 created_at: "2024-01-01T00:00:00Z".to_string(),
                 updated_at: "2024-01-02T00:00:00Z".to_string(),
-                completed_at: None,
-                moved_at: None,
-                comments: vec![],
-                formatted_vcs_branch_name: None,
-            epic_id: None,
-                branches: vec![],
-                pull_requests: vec![],
-                commits: vec![],
+                ..Default::default()
             },
         ];
 
@@ -43,7 +38,13 @@ mod tests {
             }],
         }];
 
-        App::new(stories, workflows, "test query".to_string(), None)
+        App::new(
+            stories,
+            workflows,
+            "test query".to_string(),
+            None,
+            WeekConfig::default(),
+        )
     }
 
     #[test]
@@ -89,6 +90,101 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_render_worktree_marker() {
+        let mut app = create_test_app();
+        app.worktree_story_ids.insert(123);
+        let backend = TestBackend::new(80, 24);
+        let mut terminal = Terminal::new(backend).unwrap();
+
+        terminal.draw(|f| draw(f, &mut app)).unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let mut buffer_str = String::new();
+        for y in 0..buffer.area().height {
+            for x in 0..buffer.area().width {
+                if let Some(cell) = buffer.cell((x, y)) {
+                    buffer_str.push_str(cell.symbol());
+                }
+            }
+        }
+
+        assert!(buffer_str.contains("🌳"));
+    }
+
+    #[test]
+    fn test_render_running_timer() {
+        let mut app = create_test_app();
+        app.handle_key_event(crossterm::event::KeyEvent::from(
+            crossterm::event::KeyCode::Char('t'),
+        ))
+        .unwrap();
+        assert!(app.timer_toggle_requested);
+
+        app.running_timer = Some((123, chrono::Utc::now() - chrono::Duration::minutes(65)));
+        let backend = TestBackend::new(80, 24);
+        let mut terminal = Terminal::new(backend).unwrap();
+
+        terminal.draw(|f| draw(f, &mut app)).unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let mut buffer_str = String::new();
+        for y in 0..buffer.area().height {
+            for x in 0..buffer.area().width {
+                if let Some(cell) = buffer.cell((x, y)) {
+                    buffer_str.push_str(cell.symbol());
+                }
+            }
+        }
+
+        assert!(buffer_str.contains("#123 1h 05m"));
+    }
+
+    #[test]
+    fn test_render_burndown_popup() {
+        let mut app = create_test_app();
+        let b = crossterm::event::KeyEvent::from(crossterm::event::KeyCode::Char('b'));
+        // The selected story has no epic
+        app.handle_key_event(b).unwrap();
+        assert!(!app.burndown_requested);
+
+        app.stories_by_state.get_mut(&456).unwrap()[0].epic_id = Some(7);
+        app.handle_key_event(b).unwrap();
+        assert!(app.burndown_requested);
+
+        let stories = app.stories_by_state[&456].clone();
+        app.burndown = Some(Burndown::for_epic(
+            "Checkout",
+            &stories,
+            &HashMap::new(),
+            BurndownUnit::Stories,
+            chrono::NaiveDate::from_ymd_opt(2024, 1, 10).unwrap(),
+        ));
+        let backend = TestBackend::new(80, 24);
+        let mut terminal = Terminal::new(backend).unwrap();
+
+        terminal.draw(|f| draw(f, &mut app)).unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let mut buffer_str = String::new();
+        for y in 0..buffer.area().height {
+            for x in 0..buffer.area().width {
+                if let Some(cell) = buffer.cell((x, y)) {
+                    buffer_str.push_str(cell.symbol());
+                }
+            }
+        }
+
+        assert!(buffer_str.contains("Epic: Checkout"));
+        assert!(buffer_str.contains("0 of 1 stories completed"));
+
+        app.handle_key_event(crossterm::event::KeyEvent::from(
+            crossterm::event::KeyCode::Esc,
+        ))
+        .unwrap();
+        assert!(app.burndown.is_none());
+    }
+
     #[test]
     fn test_render_detail_view() {
         let mut app = create_test_app();
@@ -133,7 +229,13 @@ mod tests {
     fn test_render_empty_list() {
         let stories = vec![];
         let workflows = vec![];
-        let mut app = App::new(stories, workflows, "test query".to_string(), None);
+        let mut app = App::new(
+            stories,
+            workflows,
+            "test query".to_string(),
+            None,
+            WeekConfig::default(),
+        );
 
         let backend = TestBackend::new(80, 24);
         let mut terminal = Terminal::new(backend).unwrap();
@@ -168,44 +270,18 @@ mod tests {
             Story {
                 id: 1,
                 name: "First Story".to_string(),
-                description: "".to_string(),
                 workflow_state_id: 10,
-                app_url: "".to_string(),
                 story_type: "bug".to_string(),
-                labels: vec![],
-                owner_ids: vec![],
                 position: 1000,
-                created_at: "".to_string(),
-                updated_at: "".to_string(),
-                completed_at: None,
-                moved_at: None,
-                comments: vec![],
-                formatted_vcs_branch_name: None,
-                epic_id: None,
-                branches: vec![],
-                pull_requests: vec![],
-                commits: vec![],
+                ..Default::default()
             },
             Story {
                 id: 2,
                 name: "Second Story".to_string(),
-                description: "".to_string(),
                 workflow_state_id: 20,
-                app_url: "".to_string(),
                 story_type: "chore".to_string(),
-                labels: vec![],
-                owner_ids: vec![],
                 position: 1000,
-                created_at: "".to_string(),
-                updated_at: "".to_string(),
-                completed_at: None,
-                moved_at: None,
-                comments: vec![],
-                formatted_vcs_branch_name: None,
-                epic_id: None,
-                branches: vec![],
-                pull_requests: vec![],
-                commits: vec![],
+                ..Default::default()
             },
         ];
 
@@ -229,7 +305,13 @@ mod tests {
                 },
             ],
         }];
-        let mut app = App::new(stories, workflows, "test query".to_string(), None);
+        let mut app = App::new(
+            stories,
+            workflows,
+            "test query".to_string(),
+            None,
+            WeekConfig::default(),
+        );
 
         let backend = TestBackend::new(80, 24);
         let mut terminal = Terminal::new(backend).unwrap();
@@ -263,44 +345,18 @@ mod tests {
             Story {
                 id: 1,
                 name: "ThisIsAVeryLongStoryNameWithNoSpacesThatExceedsTheAvailableWidthForTheFirstLine".to_string(),
-                description: "".to_string(),
                 workflow_state_id: 10,
-                app_url: "".to_string(),
                 story_type: "feature".to_string(),
-                labels: vec![],
-                owner_ids: vec![],
                 position: 1000,
-                created_at: "".to_string(),
-                updated_at: "".to_string(),
-                completed_at: None,
-                moved_at: None,
-                comments: vec![],
-                formatted_vcs_branch_name: None,
-            epic_id: None,
-                branches: vec![],
-                pull_requests: vec![],
-                commits: vec![],
+                ..Default::default()
             },
             Story {
                 id: 2,
                 name: "This is a normal story name that should wrap properly at word boundaries".to_string(),
-                description: "".to_string(),
                 workflow_state_id: 10,
-                app_url: "".to_string(),
                 story_type: "bug".to_string(),
-                labels: vec![],
-                owner_ids: vec![],
                 position: 2000,
-                created_at: "".to_string(),
-                updated_at: "".to_string(),
-                completed_at: None,
-                moved_at: None,
-                comments: vec![],
-                formatted_vcs_branch_name: None,
-            epic_id: None,
-                branches: vec![],
-                pull_requests: vec![],
-                commits: vec![],
+                ..Default::default()
             },
         ];
 
@@ -316,7 +372,13 @@ mod tests {
             }],
         }];
 
-        let mut app = App::new(stories, workflows, "test query".to_string(), None);
+        let mut app = App::new(
+            stories,
+            workflows,
+            "test query".to_string(),
+            None,
+            WeekConfig::default(),
+        );
 
         let backend = TestBackend::new(80, 24);
         let mut terminal = Terminal::new(backend).unwrap();
@@ -352,44 +414,20 @@ mod tests {
             Story {
                 id: 1,
                 name: "My Story".to_string(),
-                description: "".to_string(),
                 workflow_state_id: 10,
-                app_url: "".to_string(),
                 story_type: "feature".to_string(),
-                labels: vec![],
                 owner_ids: vec!["current-user".to_string()],
                 position: 1000,
-                created_at: "".to_string(),
-                updated_at: "".to_string(),
-                completed_at: None,
-                moved_at: None,
-                comments: vec![],
-                formatted_vcs_branch_name: None,
-                epic_id: None,
-                branches: vec![],
-                pull_requests: vec![],
-                commits: vec![],
+                ..Default::default()
             },
             Story {
                 id: 2,
                 name: "Other Story".to_string(),
-                description: "".to_string(),
                 workflow_state_id: 10,
-                app_url: "".to_string(),
                 story_type: "bug".to_string(),
-                labels: vec![],
                 owner_ids: vec!["another-user".to_string()],
                 position: 2000,
-                created_at: "".to_string(),
-                updated_at: "".to_string(),
-                completed_at: None,
-                moved_at: None,
-                comments: vec![],
-                formatted_vcs_branch_name: None,
-                epic_id: None,
-                branches: vec![],
-                pull_requests: vec![],
-                commits: vec![],
+                ..Default::default()
             },
         ];
 
@@ -405,7 +443,13 @@ mod tests {
             }],
         }];
 
-        let mut app = App::new(stories, workflows, "test query".to_string(), None);
+        let mut app = App::new(
+            stories,
+            workflows,
+            "test query".to_string(),
+            None,
+            WeekConfig::default(),
+        );
         // Set current user to highlight owned stories
         app.set_current_user_id("current-user".to_string());
 
@@ -1,4 +1,5 @@
 use crate::api::{Story, Workflow, WorkflowState};
+use crate::config::WeekConfig;
 use crate::ui::App;
 
 #[cfg(test)]
@@ -14,19 +15,11 @@ mod tests {
             workflow_state_id: state_id,
             app_url: format!("https://app.shortcut.com/org/story/{}", id),
             story_type: "feature".to_string(),
-            labels: vec![],
             owner_ids: vec!["user1".to_string()],
             position: id * 1000, // Use id to generate position
             created_at: "2024-01-01T00:00:00Z".to_string(),
             updated_at: "2024-01-02T00:00:00Z".to_string(),
-            completed_at: None,
-            moved_at: None,
-            comments: vec![],
-            formatted_vcs_branch_name: None,
-            epic_id: None,
-                branches: vec![],
-                pull_requests: vec![],
-                commits: vec![],
+            ..Default::default()
         }
     }
 
@@ -64,7 +57,13 @@ mod tests {
     fn test_toggle_state_selector() {
         let stories = vec![create_test_story(1, 10)];
         let workflows = vec![create_test_workflow()];
-        let mut app = App::new(stories, workflows, "test query".to_string(), None);
+        let mut app = App::new(
+            stories,
+            workflows,
+            "test query".to_string(),
+            None,
+            WeekConfig::default(),
+        );
 
         // Initially state selector should be hidden
         assert!(!app.show_state_selector);
@@ -79,7 +78,13 @@ mod tests {
     fn test_toggle_state_selector_empty_column() {
         let stories = vec![];
         let workflows = vec![create_test_workflow()];
-        let mut app = App::new(stories, workflows, "test query".to_string(), None);
+        let mut app = App::new(
+            stories,
+            workflows,
+            "test query".to_string(),
+            None,
+            WeekConfig::default(),
+        );
 
         // Should not show state selector for empty column
         app.toggle_state_selector();
@@ -90,7 +95,13 @@ mod tests {
     fn test_get_available_states_for_story() {
         let stories = vec![create_test_story(1, 10)]; // Story in "To Do" state
         let workflows = vec![create_test_workflow()];
-        let app = App::new(stories, workflows, "test query".to_string(), None);
+        let app = App::new(
+            stories,
+            workflows,
+            "test query".to_string(),
+            None,
+            WeekConfig::default(),
+        );
 
         let story = app.get_selected_story().unwrap();
         let available_states = app.get_available_states_for_story(story);
@@ -105,7 +116,13 @@ mod tests {
     fn test_state_selector_navigation() {
         let stories = vec![create_test_story(1, 10)];
         let workflows = vec![create_test_workflow()];
-        let mut app = App::new(stories, workflows, "test query".to_string(), None);
+        let mut app = App::new(
+            stories,
+            workflows,
+            "test query".to_string(),
+            None,
+            WeekConfig::default(),
+        );
 
         app.toggle_state_selector();
         assert_eq!(app.state_selector_index, 0);
@@ -131,7 +148,13 @@ mod tests {
     fn test_get_selected_target_state() {
         let stories = vec![create_test_story(1, 10)]; // Story in "To Do" state
         let workflows = vec![create_test_workflow()];
-        let mut app = App::new(stories, workflows, "test query".to_string(), None);
+        let mut app = App::new(
+            stories,
+            workflows,
+            "test query".to_string(),
+            None,
+            WeekConfig::default(),
+        );
 
         app.toggle_state_selector();
 
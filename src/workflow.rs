@@ -0,0 +1,304 @@
+use crate::api::{Workflow, WorkflowState};
+use anyhow::Result;
+
+/// Direction to step through a workflow
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StateStep {
+    Next,
+    Prev,
+}
+
+/// Find the workflow that owns the given state
+pub fn workflow_for_state(workflows: &[Workflow], state_id: i64) -> Option<&Workflow> {
+    workflows
+        .iter()
+        .find(|w| w.states.iter().any(|s| s.id == state_id))
+}
+
+/// Find a workflow state by ID across all workflows
+pub fn find_state(workflows: &[Workflow], state_id: i64) -> Option<&WorkflowState> {
+    workflows
+        .iter()
+        .flat_map(|w| &w.states)
+        .find(|s| s.id == state_id)
+}
+
+/// Lowercase a state name and drop everything that is not alphanumeric,
+/// so that "In Review", "in-review" and "inreview" compare equal
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(|c| c.to_lowercase())
+        .collect()
+}
+
+/// Check whether all characters of `needle` appear in `haystack` in order
+fn is_subsequence(needle: &str, haystack: &str) -> bool {
+    let mut chars = haystack.chars();
+    needle.chars().all(|c| chars.any(|h| h == c))
+}
+
+/// Resolve a user-supplied state name to a workflow state.
+///
+/// When `current_state_id` is given, only the states of the workflow that
+/// contains it are considered. Matching is case-insensitive and tries, in
+/// order: exact name, prefix, substring and finally subsequence ("inrev"
+/// matches "In Review"). The first tier with a single match wins; a tier
+/// with several matches is reported as ambiguous.
+pub fn resolve_state<'a>(
+    workflows: &'a [Workflow],
+    current_state_id: Option<i64>,
+    query: &str,
+) -> Result<&'a WorkflowState> {
+    let needle = normalize(query);
+    if needle.is_empty() {
+        anyhow::bail!("State name cannot be empty");
+    }
+
+    let candidates: Vec<&WorkflowState> =
+        match current_state_id.and_then(|id| workflow_for_state(workflows, id)) {
+            Some(workflow) => workflow.states.iter().collect(),
+            None => workflows.iter().flat_map(|w| &w.states).collect(),
+        };
+
+    let tiers: [fn(&str, &str) -> bool; 4] = [
+        |needle, name| name == needle,
+        |needle, name| name.starts_with(needle),
+        |needle, name| name.contains(needle),
+        is_subsequence,
+    ];
+
+    for matches_tier in tiers {
+        let matches: Vec<&WorkflowState> = candidates
+            .iter()
+            .copied()
+            .filter(|s| matches_tier(&needle, &normalize(&s.name)))
+            .collect();
+
+        match matches.len() {
+            0 => continue,
+            1 => return Ok(matches[0]),
+            _ => {
+                let names: Vec<&str> = matches.iter().map(|s| s.name.as_str()).collect();
+                anyhow::bail!(
+                    "State '{}' is ambiguous, it matches: {}",
+                    query,
+                    names.join(", ")
+                );
+            }
+        }
+    }
+
+    let available: Vec<&str> = candidates.iter().map(|s| s.name.as_str()).collect();
+    anyhow::bail!(
+        "No workflow state matches '{}'. Available states: {}",
+        query,
+        available.join(", ")
+    )
+}
+
+/// Find the state before or after the current one in its workflow, ordered by position
+pub fn step_state(
+    workflows: &[Workflow],
+    current_state_id: i64,
+    step: StateStep,
+) -> Option<&WorkflowState> {
+    let workflow = workflow_for_state(workflows, current_state_id)?;
+    let mut states: Vec<&WorkflowState> = workflow.states.iter().collect();
+    states.sort_by_key(|s| s.position);
+
+    let index = states.iter().position(|s| s.id == current_state_id)?;
+    match step {
+        StateStep::Next => states.get(index + 1).copied(),
+        StateStep::Prev => index.checked_sub(1).and_then(|i| states.get(i).copied()),
+    }
+}
+
+/// Find the first state (by position) of the given type, e.g. "started", in the
+/// workflow of the current state, or across all workflows if it is unknown
+pub fn first_state_of_type<'a>(
+    workflows: &'a [Workflow],
+    current_state_id: i64,
+    state_type: &str,
+) -> Option<&'a WorkflowState> {
+    let states: Vec<&WorkflowState> = match workflow_for_state(workflows, current_state_id) {
+        Some(workflow) => workflow.states.iter().collect(),
+        None => workflows.iter().flat_map(|w| &w.states).collect(),
+    };
+    states
+        .into_iter()
+        .filter(|s| s.state_type == state_type)
+        .min_by_key(|s| s.position)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(id: i64, name: &str, position: i64, state_type: &str) -> WorkflowState {
+        WorkflowState {
+            id,
+            name: name.to_string(),
+            color: String::new(),
+            position,
+            state_type: state_type.to_string(),
+        }
+    }
+
+    fn create_test_workflows() -> Vec<Workflow> {
+        vec![
+            Workflow {
+                id: 1,
+                name: "Engineering".to_string(),
+                states: vec![
+                    state(100, "Backlog", 0, "unstarted"),
+                    state(101, "Ready for Development", 1, "unstarted"),
+                    state(102, "In Development", 2, "started"),
+                    state(103, "In Review", 3, "started"),
+                    state(104, "Done", 4, "done"),
+                ],
+            },
+            Workflow {
+                id: 2,
+                name: "Design".to_string(),
+                states: vec![
+                    state(200, "Ideas", 0, "unstarted"),
+                    state(202, "Done", 2, "done"),
+                    state(201, "Drafting", 1, "started"),
+                ],
+            },
+        ]
+    }
+
+    #[test]
+    fn test_resolve_state_exact_case_insensitive() {
+        let workflows = create_test_workflows();
+        let state = resolve_state(&workflows, Some(100), "in review").unwrap();
+        assert_eq!(state.id, 103);
+    }
+
+    #[test]
+    fn test_resolve_state_ignores_punctuation() {
+        let workflows = create_test_workflows();
+        let state = resolve_state(&workflows, Some(100), "in-review").unwrap();
+        assert_eq!(state.id, 103);
+    }
+
+    #[test]
+    fn test_resolve_state_prefix() {
+        let workflows = create_test_workflows();
+        let state = resolve_state(&workflows, Some(100), "back").unwrap();
+        assert_eq!(state.id, 100);
+    }
+
+    #[test]
+    fn test_resolve_state_substring() {
+        let workflows = create_test_workflows();
+        let state = resolve_state(&workflows, Some(100), "review").unwrap();
+        assert_eq!(state.id, 103);
+    }
+
+    #[test]
+    fn test_resolve_state_subsequence() {
+        let workflows = create_test_workflows();
+        let state = resolve_state(&workflows, Some(100), "inrev").unwrap();
+        assert_eq!(state.id, 103);
+    }
+
+    #[test]
+    fn test_resolve_state_ambiguous() {
+        let workflows = create_test_workflows();
+        let err = resolve_state(&workflows, Some(100), "in").unwrap_err();
+        assert!(err.to_string().contains("ambiguous"));
+        assert!(err.to_string().contains("In Development"));
+        assert!(err.to_string().contains("In Review"));
+    }
+
+    #[test]
+    fn test_resolve_state_scoped_to_story_workflow() {
+        let workflows = create_test_workflows();
+        // "Done" exists in both workflows, but the story lives in Design
+        let state = resolve_state(&workflows, Some(200), "done").unwrap();
+        assert_eq!(state.id, 202);
+    }
+
+    #[test]
+    fn test_resolve_state_ambiguous_across_workflows() {
+        let workflows = create_test_workflows();
+        let err = resolve_state(&workflows, None, "done").unwrap_err();
+        assert!(err.to_string().contains("ambiguous"));
+    }
+
+    #[test]
+    fn test_resolve_state_not_found() {
+        let workflows = create_test_workflows();
+        let err = resolve_state(&workflows, Some(100), "shipped").unwrap_err();
+        assert!(err.to_string().contains("No workflow state matches"));
+    }
+
+    #[test]
+    fn test_resolve_state_empty_query() {
+        let workflows = create_test_workflows();
+        assert!(resolve_state(&workflows, Some(100), " - ").is_err());
+    }
+
+    #[test]
+    fn test_step_state_next_and_prev() {
+        let workflows = create_test_workflows();
+        assert_eq!(
+            step_state(&workflows, 102, StateStep::Next).unwrap().id,
+            103
+        );
+        assert_eq!(
+            step_state(&workflows, 102, StateStep::Prev).unwrap().id,
+            101
+        );
+    }
+
+    #[test]
+    fn test_step_state_uses_position_not_declaration_order() {
+        let workflows = create_test_workflows();
+        assert_eq!(
+            step_state(&workflows, 200, StateStep::Next).unwrap().id,
+            201
+        );
+        assert_eq!(
+            step_state(&workflows, 201, StateStep::Next).unwrap().id,
+            202
+        );
+    }
+
+    #[test]
+    fn test_step_state_at_edges() {
+        let workflows = create_test_workflows();
+        assert!(step_state(&workflows, 104, StateStep::Next).is_none());
+        assert!(step_state(&workflows, 100, StateStep::Prev).is_none());
+    }
+
+    #[test]
+    fn test_step_state_unknown_state() {
+        let workflows = create_test_workflows();
+        assert!(step_state(&workflows, 999, StateStep::Next).is_none());
+    }
+
+    #[test]
+    fn test_first_state_of_type() {
+        let workflows = create_test_workflows();
+        assert_eq!(
+            first_state_of_type(&workflows, 100, "started").unwrap().id,
+            102
+        );
+        assert_eq!(
+            first_state_of_type(&workflows, 200, "started").unwrap().id,
+            201
+        );
+        assert!(first_state_of_type(&workflows, 100, "archived").is_none());
+    }
+
+    #[test]
+    fn test_find_state() {
+        let workflows = create_test_workflows();
+        assert_eq!(find_state(&workflows, 201).unwrap().name, "Drafting");
+        assert!(find_state(&workflows, 999).is_none());
+    }
+}
@@ -0,0 +1,159 @@
+use crate::api::{Story, Workflow};
+use crate::git::Worktree;
+use crate::story_ref::StoryRef;
+use crate::workflow;
+use std::collections::{HashMap, HashSet};
+
+/// A git worktree together with the story its branch or directory refers to
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoryWorktree {
+    pub worktree: Worktree,
+    pub story_id: Option<i64>,
+    /// The main worktree, which is never removed
+    pub main: bool,
+}
+
+impl StoryWorktree {
+    pub fn branch(&self) -> &str {
+        self.worktree.branch.as_deref().unwrap_or("(detached)")
+    }
+}
+
+/// Match worktrees with stories by branch name, then by directory name.
+/// Bare repository entries are skipped since they have no checkout.
+pub fn story_worktrees(worktrees: &[Worktree]) -> Vec<StoryWorktree> {
+    worktrees
+        .iter()
+        .enumerate()
+        .filter(|(_, w)| !w.bare)
+        .map(|(index, w)| {
+            let from_branch = w.branch.as_deref().and_then(StoryRef::from_name);
+            let from_dir = || {
+                w.path
+                    .file_name()
+                    .and_then(|name| StoryRef::from_name(&name.to_string_lossy()))
+            };
+            StoryWorktree {
+                worktree: w.clone(),
+                story_id: from_branch.or_else(from_dir).map(StoryRef::id),
+                main: index == 0,
+            }
+        })
+        .collect()
+}
+
+/// IDs of the stories that have a linked (non-main) worktree
+pub fn linked_story_ids(entries: &[StoryWorktree]) -> HashSet<i64> {
+    entries
+        .iter()
+        .filter(|e| !e.main)
+        .filter_map(|e| e.story_id)
+        .collect()
+}
+
+/// Linked worktrees whose story is in a "done" state
+pub fn done_worktrees<'a>(
+    entries: &'a [StoryWorktree],
+    stories: &HashMap<i64, Story>,
+    workflows: &[Workflow],
+) -> Vec<&'a StoryWorktree> {
+    entries
+        .iter()
+        .filter(|e| !e.main)
+        .filter(|e| {
+            e.story_id
+                .and_then(|id| stories.get(&id))
+                .and_then(|story| workflow::find_state(workflows, story.workflow_state_id))
+                .is_some_and(|state| state.state_type == "done")
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::WorkflowState;
+    use std::path::PathBuf;
+
+    fn worktree(path: &str, branch: Option<&str>, bare: bool) -> Worktree {
+        Worktree {
+            path: PathBuf::from(path),
+            head: None,
+            branch: branch.map(|b| b.to_string()),
+            bare,
+        }
+    }
+
+    fn create_test_story(id: i64, workflow_state_id: i64) -> Story {
+        Story {
+            id,
+            name: format!("Story {id}"),
+            workflow_state_id,
+            story_type: "feature".to_string(),
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+            updated_at: "2024-01-01T00:00:00Z".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn create_test_workflows() -> Vec<Workflow> {
+        let state = |id, state_type: &str| WorkflowState {
+            id,
+            name: state_type.to_string(),
+            color: String::new(),
+            position: id,
+            state_type: state_type.to_string(),
+        };
+        vec![Workflow {
+            id: 1,
+            name: "Engineering".to_string(),
+            states: vec![state(1, "started"), state(2, "done")],
+        }]
+    }
+
+    fn create_entries() -> Vec<StoryWorktree> {
+        story_worktrees(&[
+            worktree("/repo", Some("sc-1-main-checkout"), false),
+            worktree("/wt/sc-2-fix", Some("edo/sc-2/fix"), false),
+            worktree("/wt/sc-3", None, false),
+            worktree("/wt/misc", Some("misc"), false),
+        ])
+    }
+
+    #[test]
+    fn test_story_worktrees_detects_story_ids() {
+        let entries = create_entries();
+        let ids: Vec<Option<i64>> = entries.iter().map(|e| e.story_id).collect();
+        assert_eq!(ids, vec![Some(1), Some(2), Some(3), None]);
+        assert!(entries[0].main);
+        assert_eq!(entries[2].branch(), "(detached)");
+    }
+
+    #[test]
+    fn test_story_worktrees_skips_bare_repository() {
+        let entries = story_worktrees(&[
+            worktree("/repo.git", None, true),
+            worktree("/wt/sc-5", Some("sc-5"), false),
+        ]);
+        assert_eq!(entries.len(), 1);
+        assert!(!entries[0].main);
+    }
+
+    #[test]
+    fn test_linked_story_ids_excludes_main() {
+        assert_eq!(linked_story_ids(&create_entries()), HashSet::from([2, 3]));
+    }
+
+    #[test]
+    fn test_done_worktrees() {
+        let entries = create_entries();
+        let stories = HashMap::from([
+            (1, create_test_story(1, 2)),
+            (2, create_test_story(2, 2)),
+            (3, create_test_story(3, 1)),
+        ]);
+        let done = done_worktrees(&entries, &stories, &create_test_workflows());
+        assert_eq!(done.len(), 1);
+        assert_eq!(done[0].story_id, Some(2));
+    }
+}
@@ -371,3 +371,624 @@ fn test_cli_branch_with_both_flags() {
         .assert()
         .failure(); // Will fail on API call, but validates flag combination
 }
+
+#[test]
+fn test_cli_move_help() {
+    let mut cmd = Command::cargo_bin("sc-cli").unwrap();
+    cmd.arg("move")
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Move one or more stories"))
+        .stdout(predicate::str::contains("--next"))
+        .stdout(predicate::str::contains("--prev"));
+}
+
+#[test]
+fn test_cli_move_requires_state() {
+    let mut cmd = Command::cargo_bin("sc-cli").unwrap();
+    cmd.arg("move")
+        .arg("42")
+        .arg("--token")
+        .arg("fake-token")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Missing target state"));
+}
+
+#[test]
+fn test_cli_move_detects_story_without_ids() {
+    let dir = tempfile::tempdir().unwrap();
+    for args in [vec!["move", "--next"], vec!["move", "in review"]] {
+        let mut cmd = Command::cargo_bin("sc-cli").unwrap();
+        cmd.current_dir(dir.path())
+            .args(args)
+            .args(["--token", "fake-token"])
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains(
+                "none could be detected from the current git branch",
+            ));
+    }
+}
+
+#[test]
+fn test_cli_move_rejects_invalid_story_id() {
+    let mut cmd = Command::cargo_bin("sc-cli").unwrap();
+    cmd.arg("move")
+        .arg("abc")
+        .arg("done")
+        .arg("--token")
+        .arg("fake-token")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid story ID"));
+}
+
+#[test]
+fn test_cli_move_next_and_prev_conflict() {
+    let mut cmd = Command::cargo_bin("sc-cli").unwrap();
+    cmd.arg("move")
+        .arg("42")
+        .arg("--next")
+        .arg("--prev")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn test_cli_story_help() {
+    let mut cmd = Command::cargo_bin("sc-cli").unwrap();
+    cmd.arg("story")
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Print the full details of a single story",
+        ))
+        .stdout(predicate::str::contains("--format"))
+        .stdout(predicate::str::contains("--output"))
+        .stdout(predicate::str::contains("--web"));
+}
+
+#[test]
+fn test_cli_story_rejects_unknown_format() {
+    let mut cmd = Command::cargo_bin("sc-cli").unwrap();
+    cmd.arg("story")
+        .arg("42")
+        .arg("--output")
+        .arg("yaml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid value 'yaml'"));
+
+    let mut cmd = Command::cargo_bin("sc-cli").unwrap();
+    cmd.args(["story", "42", "--format", "csv"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid story format 'csv'"));
+
+    let mut cmd = Command::cargo_bin("sc-cli").unwrap();
+    cmd.args(["--output", "csv", "story", "42", "--token", "fake-token"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "A story can only be printed as text, json or markdown",
+        ));
+}
+
+#[test]
+fn test_cli_story_rejects_invalid_story_id() {
+    let mut cmd = Command::cargo_bin("sc-cli").unwrap();
+    cmd.arg("story")
+        .arg("abc")
+        .arg("--token")
+        .arg("fake-token")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid story ID"));
+}
+
+#[test]
+fn test_cli_show_help_lists_output_formats() {
+    let mut cmd = Command::cargo_bin("sc-cli").unwrap();
+    cmd.arg("show")
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--output"))
+        .stdout(predicate::str::contains("ndjson"))
+        .stdout(predicate::str::contains("--template"))
+        .stdout(predicate::str::contains("--no-pager"));
+}
+
+#[test]
+fn test_cli_output_rejects_unknown_format() {
+    let mut cmd = Command::cargo_bin("sc-cli").unwrap();
+    cmd.arg("show")
+        .arg("--output")
+        .arg("xml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid value 'xml'"));
+}
+
+#[test]
+fn test_cli_show_template_conflicts_with_output() {
+    let mut cmd = Command::cargo_bin("sc-cli").unwrap();
+    cmd.arg("show")
+        .arg("--template")
+        .arg("{{id}}")
+        .arg("--output")
+        .arg("json")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn test_cli_start_help() {
+    let mut cmd = Command::cargo_bin("sc-cli").unwrap();
+    cmd.arg("start")
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Start working on a story"))
+        .stdout(predicate::str::contains("--dry-run"))
+        .stdout(predicate::str::contains("--no-assign"))
+        .stdout(predicate::str::contains("--comment"));
+}
+
+#[test]
+fn test_cli_start_comment_conflicts_with_no_comment() {
+    let mut cmd = Command::cargo_bin("sc-cli").unwrap();
+    cmd.arg("start")
+        .arg("42")
+        .arg("--comment")
+        .arg("On it")
+        .arg("--no-comment")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn test_cli_start_rejects_invalid_story_id() {
+    let mut cmd = Command::cargo_bin("sc-cli").unwrap();
+    cmd.arg("start")
+        .arg("not-a-story")
+        .arg("--token")
+        .arg("fake-token")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid story ID"));
+}
+
+#[test]
+fn test_cli_current_help() {
+    let mut cmd = Command::cargo_bin("sc-cli").unwrap();
+    cmd.arg("current")
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Show the story detected from the current git branch",
+        ))
+        .stdout(predicate::str::contains("--id-only"));
+}
+
+#[test]
+fn test_cli_current_outside_story_branch() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut cmd = Command::cargo_bin("sc-cli").unwrap();
+    cmd.current_dir(dir.path())
+        .arg("current")
+        .arg("--id-only")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No story detected"));
+}
+
+#[test]
+fn test_cli_current_detects_worktree_directory() {
+    let dir = tempfile::tempdir().unwrap();
+    let worktree = dir.path().join("sc-77-fix-login");
+    std::fs::create_dir(&worktree).unwrap();
+    let mut cmd = Command::cargo_bin("sc-cli").unwrap();
+    cmd.current_dir(&worktree)
+        .arg("current")
+        .arg("--id-only")
+        .assert()
+        .success()
+        .stdout("77\n");
+}
+
+#[test]
+fn test_cli_story_rejects_non_story_url() {
+    let mut cmd = Command::cargo_bin("sc-cli").unwrap();
+    cmd.arg("story")
+        .arg("https://app.shortcut.com/acme/epic/42")
+        .arg("--token")
+        .arg("fake-token")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("does not point to a story"));
+}
+
+#[test]
+fn test_cli_finish_help_shows_cleanup_options() {
+    let mut cmd = Command::cargo_bin("sc-cli").unwrap();
+    cmd.arg("finish")
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--base"))
+        .stdout(predicate::str::contains("--no-cleanup"))
+        .stdout(predicate::str::contains("--yes"));
+}
+
+#[test]
+fn test_cli_branch_help_shows_base_and_push() {
+    let mut cmd = Command::cargo_bin("sc-cli").unwrap();
+    cmd.arg("branch")
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--base <REF>"))
+        .stdout(predicate::str::contains("--stash"))
+        .stdout(predicate::str::contains("--push"))
+        .stdout(predicate::str::contains("--repos <NAMES>"));
+}
+
+#[test]
+fn test_cli_branch_rejects_unknown_repos() {
+    let mut cmd = Command::cargo_bin("sc-cli").unwrap();
+    cmd.args([
+        "branch",
+        "42",
+        "--repos",
+        "api,web",
+        "--token",
+        "fake-token",
+    ])
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains(
+        "Unknown repository 'api'. Configured repositories: none",
+    ));
+}
+
+#[test]
+fn test_cli_worktree_help() {
+    let mut cmd = Command::cargo_bin("sc-cli").unwrap();
+    cmd.arg("worktree")
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("list"))
+        .stdout(predicate::str::contains("open"))
+        .stdout(predicate::str::contains("prune"));
+}
+
+#[test]
+fn test_cli_worktree_open_prints_story_worktree() {
+    let dir = tempfile::tempdir().unwrap();
+    let repo = dir.path().join("repo");
+    let git = |args: &[&str]| {
+        let status = std::process::Command::new("git")
+            .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .current_dir(dir.path())
+            .status()
+            .unwrap();
+        assert!(status.success());
+    };
+    git(&["init", "-q", "repo"]);
+    git(&["-C", "repo", "commit", "-q", "--allow-empty", "-m", "init"]);
+    git(&[
+        "-C",
+        "repo",
+        "worktree",
+        "add",
+        "-q",
+        "-b",
+        "sc-77-fix",
+        "../repo-sc-77",
+    ]);
+
+    let mut cmd = Command::cargo_bin("sc-cli").unwrap();
+    cmd.current_dir(&repo)
+        .arg("worktree")
+        .arg("open")
+        .arg("sc-77")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("repo-sc-77"));
+
+    let mut cmd = Command::cargo_bin("sc-cli").unwrap();
+    cmd.current_dir(&repo)
+        .arg("worktree")
+        .arg("open")
+        .arg("78")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No worktree found for story #78"));
+}
+
+#[test]
+fn test_cli_hooks_tag_commits_on_story_branches() {
+    let dir = tempfile::tempdir().unwrap();
+    let repo = dir.path().join("repo");
+    let git = |cwd: &std::path::Path, args: &[&str]| {
+        let output = std::process::Command::new("git")
+            .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .current_dir(cwd)
+            .env("HOME", dir.path())
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {args:?} failed");
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    };
+    let sc_cli = |cwd: &std::path::Path, args: &[&str]| {
+        let mut cmd = Command::cargo_bin("sc-cli").unwrap();
+        cmd.current_dir(cwd).env("HOME", dir.path()).args(args);
+        cmd.assert()
+    };
+    git(dir.path(), &["init", "-q", "-b", "main", "repo"]);
+    git(&repo, &["commit", "-q", "--allow-empty", "-m", "init"]);
+
+    sc_cli(&repo, &["hooks", "install"])
+        .success()
+        .stdout(predicate::str::contains("Fix login [sc-42]"));
+
+    git(&repo, &["checkout", "-q", "-b", "sc-42-fix-login"]);
+    git(&repo, &["commit", "-q", "--allow-empty", "-m", "Fix login"]);
+    assert_eq!(
+        git(&repo, &["log", "-1", "--format=%s"]),
+        "Fix login [sc-42]"
+    );
+
+    // Linked worktrees share the hook
+    git(
+        &repo,
+        &["worktree", "add", "-q", "-b", "sc-43-other", "../wt"],
+    );
+    let worktree = dir.path().join("wt");
+    git(&worktree, &["commit", "-q", "--allow-empty", "-m", "Other"]);
+    assert_eq!(
+        git(&worktree, &["log", "-1", "--format=%s"]),
+        "Other [sc-43]"
+    );
+
+    sc_cli(&worktree, &["hooks", "uninstall"])
+        .success()
+        .stdout(predicate::str::contains("Removed the commit-msg hook"));
+    git(&repo, &["commit", "-q", "--allow-empty", "-m", "Untagged"]);
+    assert_eq!(git(&repo, &["log", "-1", "--format=%s"]), "Untagged");
+
+    // Bare repositories keep hooks in the repository itself
+    git(dir.path(), &["init", "-q", "--bare", "bare.git"]);
+    sc_cli(&dir.path().join("bare.git"), &["hooks", "install"]).success();
+    assert!(dir.path().join("bare.git/hooks/commit-msg").exists());
+}
+
+#[test]
+fn test_cli_hooks_install_outside_repository() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut cmd = Command::cargo_bin("sc-cli").unwrap();
+    cmd.current_dir(dir.path())
+        .arg("hooks")
+        .arg("install")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Not a git repository"));
+}
+
+#[test]
+fn test_cli_changelog_lists_unreferenced_commits() {
+    let dir = tempfile::tempdir().unwrap();
+    let git = |args: &[&str]| {
+        let status = std::process::Command::new("git")
+            .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .current_dir(dir.path())
+            .status()
+            .unwrap();
+        assert!(status.success());
+    };
+    git(&["init", "-q", "-b", "main"]);
+    git(&["commit", "-q", "--allow-empty", "-m", "Initial commit"]);
+    git(&["tag", "v1.0.0"]);
+    git(&["commit", "-q", "--allow-empty", "-m", "Bump dependencies"]);
+
+    // No story is referenced, so nothing is fetched from Shortcut
+    let mut cmd = Command::cargo_bin("sc-cli").unwrap();
+    cmd.current_dir(dir.path())
+        .args(["changelog", "v1.0.0..HEAD", "--token", "test-token"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("# Changelog (v1.0.0..HEAD)"))
+        .stdout(predicate::str::contains("## Unreferenced Commits"))
+        .stdout(predicate::str::contains("Bump dependencies"))
+        .stdout(predicate::str::contains("Initial commit").not());
+
+    let mut cmd = Command::cargo_bin("sc-cli").unwrap();
+    cmd.current_dir(dir.path())
+        .args([
+            "changelog",
+            "v1.0.0..HEAD",
+            "--token",
+            "test-token",
+            "--output",
+            "json",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"unreferenced\""));
+
+    let mut cmd = Command::cargo_bin("sc-cli").unwrap();
+    cmd.current_dir(dir.path())
+        .args(["changelog", "v9.9.9..HEAD", "--token", "test-token"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Failed to read the commits in 'v9.9.9..HEAD'",
+        ));
+
+    let mut cmd = Command::cargo_bin("sc-cli").unwrap();
+    cmd.current_dir(dir.path())
+        .args([
+            "changelog",
+            "v1.0.0..HEAD",
+            "--token",
+            "test-token",
+            "--output",
+            "csv",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("markdown (the default) or json"));
+}
+
+#[test]
+fn test_cli_pr_body_help() {
+    let mut cmd = Command::cargo_bin("sc-cli").unwrap();
+    cmd.arg("pr-body")
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Print a pull request description built from a story",
+        ))
+        .stdout(predicate::str::contains("--title"))
+        .stdout(predicate::str::contains("--file"))
+        .stdout(predicate::str::contains("--template"));
+}
+
+#[test]
+fn test_cli_pr_body_rejects_invalid_story_id() {
+    let mut cmd = Command::cargo_bin("sc-cli").unwrap();
+    cmd.arg("pr-body")
+        .arg("abc")
+        .arg("--token")
+        .arg("fake-token")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid story ID"));
+}
+
+#[test]
+fn test_cli_timer_start_stop_and_status() {
+    let dir = tempfile::tempdir().unwrap();
+    let sc_cli = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("sc-cli").unwrap();
+        cmd.current_dir(dir.path())
+            .env("HOME", dir.path())
+            .args(["--workspace", "work"])
+            .args(args);
+        cmd.assert()
+    };
+
+    sc_cli(&["timesheet", "--week"])
+        .success()
+        .stdout(predicate::str::contains("No time logged this week"));
+
+    sc_cli(&["timer", "start", "sc-42"])
+        .success()
+        .stdout(predicate::str::contains("Started the timer on story #42"));
+    sc_cli(&["timer", "status", "42"])
+        .success()
+        .stdout(predicate::str::contains("Running on story #42"))
+        .stdout(predicate::str::contains(
+            "Logged on story #42: 0h 00m this week",
+        ));
+
+    // Starting another story stops the first one
+    sc_cli(&["timer", "start", "7"])
+        .success()
+        .stdout(predicate::str::contains("Stopped story #42"));
+    sc_cli(&["timer", "stop", "42"])
+        .failure()
+        .stderr(predicate::str::contains(
+            "The timer is running on story #7, not #42",
+        ));
+    sc_cli(&["timer", "stop"])
+        .success()
+        .stdout(predicate::str::contains("Stopped story #7"));
+    sc_cli(&["timer", "status"])
+        .success()
+        .stdout(predicate::str::contains("No timer is running"));
+
+    let log =
+        std::fs::read_to_string(dir.path().join(".config/sc-cli/timelog/work.jsonl")).unwrap();
+    assert_eq!(log.lines().count(), 4);
+}
+
+#[test]
+fn test_cli_standup_rejects_csv_output() {
+    let mut cmd = Command::cargo_bin("sc-cli").unwrap();
+    cmd.args(["--output", "csv", "standup", "--token", "fake-token"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "The standup can only be printed as text, markdown or json",
+        ));
+}
+
+#[test]
+fn test_cli_report_requires_week() {
+    let mut cmd = Command::cargo_bin("sc-cli").unwrap();
+    cmd.args(["report", "--token", "fake-token"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--week"));
+
+    let mut cmd = Command::cargo_bin("sc-cli").unwrap();
+    cmd.args(["--output", "json", "report", "--week"])
+        .args(["--token", "fake-token"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "The report can only be printed as text, markdown or csv",
+        ));
+}
+
+#[test]
+fn test_cli_stats_rejects_csv_output() {
+    let mut cmd = Command::cargo_bin("sc-cli").unwrap();
+    cmd.args(["--output", "csv", "stats", "--by", "owner"])
+        .args(["--token", "fake-token"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "The stats can only be printed as text or json",
+        ));
+}
+
+#[test]
+fn test_cli_burndown_requires_epic_or_iteration() {
+    let mut cmd = Command::cargo_bin("sc-cli").unwrap();
+    cmd.args(["burndown", "--token", "fake-token"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--epic"));
+
+    let mut cmd = Command::cargo_bin("sc-cli").unwrap();
+    cmd.args(["burndown", "--epic", "1", "--iteration", "current"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn test_cli_history_rejects_csv_output() {
+    let mut cmd = Command::cargo_bin("sc-cli").unwrap();
+    cmd.args(["--output", "csv", "history", "42", "--token", "fake-token"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "The history can only be printed as text or json",
+        ));
+}
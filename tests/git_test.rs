@@ -0,0 +1,330 @@
+//! Git helpers exercised against real repositories with a local bare remote.
+//!
+//! Every test gets its own clone and points the helpers at it, so the tests
+//! never depend on the process working directory. The command and gix
+//! backends must agree on everything they both read.
+
+use sc_cli::git::backend::CommandBackend;
+use sc_cli::git::operations::{
+    GitBranchRequest, GitOperation, execute_after_stash, execute_in_repos,
+};
+use sc_cli::git::{self, GitBackend, GitError, GitRepoType, GixBackend};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tempfile::TempDir;
+
+fn run_git(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "git {args:?} failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+/// A clone of a bare `remote.git` with one pushed commit on `main`, checked
+/// out on `other-feature` one commit ahead of it
+struct Fixture {
+    dir: TempDir,
+    remote: PathBuf,
+    repo: PathBuf,
+    main_head: String,
+}
+
+fn setup() -> Fixture {
+    let dir = tempfile::tempdir().unwrap();
+    let remote = dir.path().join("remote.git");
+    let repo = dir.path().join("repo");
+    run_git(
+        dir.path(),
+        &["init", "-q", "--bare", "-b", "main", "remote.git"],
+    );
+    run_git(dir.path(), &["clone", "-q", "remote.git", "repo"]);
+    run_git(&repo, &["config", "user.name", "Test"]);
+    run_git(&repo, &["config", "user.email", "test@example.com"]);
+
+    std::fs::write(repo.join("README.md"), "hello\n").unwrap();
+    run_git(&repo, &["add", "README.md"]);
+    run_git(&repo, &["commit", "-q", "-m", "Initial commit"]);
+    run_git(&repo, &["push", "-q", "origin", "main"]);
+    let main_head = run_git(&repo, &["rev-parse", "main"]);
+
+    run_git(&repo, &["checkout", "-q", "-b", "other-feature"]);
+    run_git(
+        &repo,
+        &["commit", "-q", "--allow-empty", "-m", "Unrelated work"],
+    );
+
+    Fixture {
+        dir,
+        remote,
+        repo,
+        main_head,
+    }
+}
+
+fn current_branch(dir: &Path) -> String {
+    run_git(dir, &["branch", "--show-current"])
+}
+
+#[test]
+fn test_stash_uncommitted_changes() {
+    let fx = setup();
+    std::fs::write(fx.repo.join("README.md"), "edited\n").unwrap();
+
+    assert!(git::has_uncommitted_changes(&fx.repo).unwrap());
+    git::stash_changes(&fx.repo, "sc-cli: before switching to sc-42-fix-login").unwrap();
+    assert!(!git::has_uncommitted_changes(&fx.repo).unwrap());
+    assert!(run_git(&fx.repo, &["stash", "list"]).contains("sc-42-fix-login"));
+}
+
+#[test]
+fn test_failed_branch_creation_restores_stash() {
+    let fx = setup();
+    std::fs::write(fx.repo.join("README.md"), "edited\n").unwrap();
+    let request = GitBranchRequest {
+        branch_name: "sc-42-fix-login".to_string(),
+        worktree_path: String::new(),
+        operation: GitOperation::CreateBranch,
+        story_id: 42,
+        base: Some("missing-base".to_string()),
+    };
+
+    let result = execute_after_stash(&GixBackend::at(&fx.repo), &fx.repo, &request).unwrap();
+
+    assert!(!result.success);
+    assert!(result.message.contains("stashed changes restored"));
+    assert_eq!(current_branch(&fx.repo), "other-feature");
+    assert_eq!(
+        std::fs::read_to_string(fx.repo.join("README.md")).unwrap(),
+        "edited\n"
+    );
+    assert_eq!(run_git(&fx.repo, &["stash", "list"]), "");
+}
+
+#[test]
+fn test_branch_creation_keeps_changes_stashed() {
+    let fx = setup();
+    std::fs::write(fx.repo.join("README.md"), "edited\n").unwrap();
+    let request = GitBranchRequest {
+        branch_name: "sc-42-fix-login".to_string(),
+        worktree_path: String::new(),
+        operation: GitOperation::CreateBranch,
+        story_id: 42,
+        base: Some("main".to_string()),
+    };
+
+    let result = execute_after_stash(&GixBackend::at(&fx.repo), &fx.repo, &request).unwrap();
+
+    assert!(result.success);
+    assert_eq!(current_branch(&fx.repo), "sc-42-fix-login");
+    assert!(!git::has_uncommitted_changes(&fx.repo).unwrap());
+    assert!(run_git(&fx.repo, &["stash", "list"]).contains("sc-42-fix-login"));
+}
+
+#[test]
+fn test_switch_branch_never_carries_changes_over() {
+    let fx = setup();
+    std::fs::write(fx.repo.join("README.md"), "edited\n").unwrap();
+
+    assert!(git::switch_branch(&fx.repo, "main", false).is_err());
+    assert_eq!(current_branch(&fx.repo), "other-feature");
+
+    assert!(git::switch_branch(&fx.repo, "main", true).unwrap());
+    assert_eq!(current_branch(&fx.repo), "main");
+    assert!(!git::has_uncommitted_changes(&fx.repo).unwrap());
+}
+
+#[test]
+fn test_failed_switch_restores_stash() {
+    let fx = setup();
+    let worktree = fx.dir.path().join("main-wt");
+    run_git(
+        &fx.repo,
+        &["worktree", "add", "-q", worktree.to_str().unwrap(), "main"],
+    );
+    std::fs::write(fx.repo.join("README.md"), "edited\n").unwrap();
+
+    // `main` is checked out in the other worktree, so the switch fails
+    let err = git::switch_branch(&fx.repo, "main", true).unwrap_err();
+
+    assert!(err.to_string().contains("stashed changes restored"));
+    assert_eq!(current_branch(&fx.repo), "other-feature");
+    assert_eq!(
+        std::fs::read_to_string(fx.repo.join("README.md")).unwrap(),
+        "edited\n"
+    );
+    assert_eq!(run_git(&fx.repo, &["stash", "list"]), "");
+}
+
+#[test]
+fn test_branch_and_worktree_start_from_base() {
+    let fx = setup();
+    let git = GixBackend::at(&fx.repo);
+
+    git.create_branch("sc-42-fix-login", Some("main")).unwrap();
+    assert_eq!(current_branch(&fx.repo), "sc-42-fix-login");
+    assert_eq!(run_git(&fx.repo, &["rev-parse", "HEAD"]), fx.main_head);
+
+    let worktree = fx.dir.path().join("sc-43-wt");
+    git.create_worktree("sc-43-wt", worktree.to_str().unwrap(), Some("main"))
+        .unwrap();
+    assert_eq!(run_git(&worktree, &["rev-parse", "HEAD"]), fx.main_head);
+}
+
+#[test]
+fn test_push_branch_sets_upstream() {
+    let fx = setup();
+    run_git(
+        &fx.repo,
+        &["checkout", "-q", "-b", "sc-42-fix-login", "main"],
+    );
+
+    git::push_branch(&fx.repo, "origin", "sc-42-fix-login").unwrap();
+    assert_eq!(
+        run_git(&fx.remote, &["rev-parse", "sc-42-fix-login"]),
+        fx.main_head
+    );
+    assert_eq!(
+        run_git(
+            &fx.repo,
+            &["rev-parse", "--abbrev-ref", "sc-42-fix-login@{upstream}"]
+        ),
+        "origin/sc-42-fix-login"
+    );
+
+    let err = git::push_branch(&fx.repo, "missing", "sc-42-fix-login").unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("Failed to push branch 'sc-42-fix-login' to 'missing'")
+    );
+}
+
+#[test]
+fn test_branch_pushed_only_when_tip_matches_upstream() {
+    let fx = setup();
+    assert!(!git::is_branch_pushed(&fx.repo, "other-feature").unwrap());
+    assert!(git::is_branch_pushed(&fx.repo, "main").unwrap());
+
+    run_git(&fx.repo, &["push", "-q", "-u", "origin", "other-feature"]);
+    assert!(git::is_branch_pushed(&fx.repo, "other-feature").unwrap());
+
+    // A commit made after the push, e.g. after the pull request was merged
+    run_git(
+        &fx.repo,
+        &["commit", "-q", "--allow-empty", "-m", "Late fix"],
+    );
+    assert!(!git::is_branch_pushed(&fx.repo, "other-feature").unwrap());
+    assert!(!git::is_branch_pushed(&fx.repo, "missing").unwrap());
+}
+
+#[test]
+fn test_backends_agree() {
+    let fx = setup();
+    let worktree = fx.dir.path().join("sc-43-wt");
+    run_git(
+        &fx.repo,
+        &[
+            "worktree",
+            "add",
+            "-q",
+            "-b",
+            "sc-43-wt",
+            worktree.to_str().unwrap(),
+        ],
+    );
+
+    let backends = |dir: &Path| -> [Box<dyn GitBackend>; 2] {
+        [
+            Box::new(CommandBackend::at(dir)),
+            Box::new(GixBackend::at(dir)),
+        ]
+    };
+
+    // Both backends read the same state and report structured errors
+    for backend in backends(&fx.repo) {
+        assert_eq!(backend.repo_type().unwrap(), GitRepoType::Normal);
+        assert_eq!(backend.current_branch().unwrap(), "other-feature");
+        assert!(backend.branch_exists("main").unwrap());
+        assert!(!backend.branch_exists("sc-99-missing").unwrap());
+        assert!(matches!(
+            backend.create_branch("main", None),
+            Err(GitError::BranchExists(name)) if name == "main"
+        ));
+    }
+
+    for backend in backends(&worktree) {
+        assert_eq!(backend.repo_type().unwrap(), GitRepoType::Normal);
+        assert_eq!(backend.current_branch().unwrap(), "sc-43-wt");
+    }
+
+    for backend in backends(&fx.remote) {
+        assert_eq!(backend.repo_type().unwrap(), GitRepoType::Bare);
+        assert!(backend.branch_exists("main").unwrap());
+    }
+
+    run_git(&fx.repo, &["checkout", "-q", "--detach"]);
+    for backend in backends(&fx.repo) {
+        assert_eq!(backend.current_branch().unwrap(), "HEAD");
+    }
+}
+
+#[test]
+fn test_branch_in_several_repos() {
+    let fx = setup();
+    let web = fx.dir.path().join("web");
+    run_git(fx.dir.path(), &["clone", "-q", "remote.git", "web"]);
+    let cwd = std::env::current_dir().unwrap();
+
+    // The same branch is created in several repositories, each reported on
+    // its own
+    let repos = vec![
+        ("api".to_string(), fx.repo.clone()),
+        ("web".to_string(), web.clone()),
+        ("remote".to_string(), fx.remote.clone()),
+        ("missing".to_string(), fx.dir.path().join("missing")),
+    ];
+    let request = GitBranchRequest {
+        branch_name: "sc-44-multi".to_string(),
+        worktree_path: String::new(),
+        operation: GitOperation::CreateBranch,
+        story_id: 44,
+        base: Some("main".to_string()),
+    };
+    let results = execute_in_repos(&repos, &request, |path| {
+        format!("../{}-sc-44", git::naming::repo_name(path).unwrap())
+    });
+
+    let summary: Vec<(&str, bool)> = results
+        .iter()
+        .map(|r| (r.repo.as_str(), r.result.success))
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            ("api", true),
+            ("web", true),
+            ("remote", true),
+            ("missing", false)
+        ]
+    );
+    assert_eq!(current_branch(&fx.repo), "sc-44-multi");
+    assert_eq!(current_branch(&web), "sc-44-multi");
+    // The bare repository gets a worktree named after it instead
+    assert_eq!(
+        results[2].result.worktree_path.as_deref(),
+        Some("../remote-sc-44")
+    );
+    assert_eq!(
+        run_git(&fx.dir.path().join("remote-sc-44"), &["rev-parse", "HEAD"]),
+        fx.main_head
+    );
+    assert!(results[3].result.message.contains("does not exist"));
+    assert_eq!(std::env::current_dir().unwrap(), cwd);
+}
@@ -5,7 +5,7 @@ use std::process::Command;
 #[test]
 fn test_help_command() {
     let output = Command::new("cargo")
-        .args(&["run", "--", "--help"])
+        .args(["run", "--", "--help"])
         .output()
         .expect("Failed to execute command");
 
@@ -21,7 +21,7 @@ fn test_help_command() {
 #[test]
 fn test_version_command() {
     let output = Command::new("cargo")
-        .args(&["run", "--", "--version"])
+        .args(["run", "--", "--version"])
         .output()
         .expect("Failed to execute command");
 
@@ -33,7 +33,7 @@ fn test_version_command() {
 #[test]
 fn test_missing_required_args() {
     let output = Command::new("cargo")
-        .args(&["run", "--", "view", "testuser"])
+        .args(["run", "--", "view", "testuser"])
         .output()
         .expect("Failed to execute command");
 
@@ -107,7 +107,7 @@ mod api_integration_tests {
             .create();
 
         // Verify mocks work correctly
-        assert_eq!(server.url().starts_with("http://"), true);
+        assert!(server.url().starts_with("http://"));
     }
 
     #[rstest]
@@ -138,6 +138,6 @@ mod api_integration_tests {
             .create();
 
         // Verify the status code logic
-        assert_eq!(status_code >= 200 && status_code < 300, should_succeed);
+        assert_eq!((200..300).contains(&status_code), should_succeed);
     }
 }